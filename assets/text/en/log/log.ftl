@@ -24,20 +24,39 @@ change-floor = {$player} moved to the next floor.
 # Messages about character status
 
 skill-level-up = {$chara}'s {$skill} level increased.
+skill-reach-cap = {$chara}'s {$skill} cannot be raised any more.
+learn-skill = {$chara} learned {$skill}.
+read-skill-book = {$chara} read a book about {$skill}.
+training-succeed = {$chara} trained {$skill}.
+training-lack-of-money = {$chara} do not have enough money for the training.
+chara-level-up = {$chara} reached level {$level}!
 
 # Messages about combat
 
 attack = {$attacker} attacked {$target}.
 shot-target = {$attacker} shot {$target}.
 no-ranged-weapon-equipped = No ranged weapon equipped!
+no-ammo = No {$ammo} left!
+throw-item = {$chara} threw {$item}.
+potion-shatter = The {$item} shattered.
+potion-hit = {$chara} was splashed with {$item}.
 no-target = {$chara} could not find any target.
+not-enough-sp = {$chara} does not have enough SP.
+cast-spell = {$chara} casts {$spell}.
+learn-spell = {$chara} learned {$spell}.
+already-learned-spell = {$chara} has already learned {$spell}.
 target-chara = {$chara} targeted {$target}.
+target-tile = {$chara} targeted the tile.
 attack-evade = {$chara} evaded.
+critical-hit = Critical hit!
 damaged-chara = {$chara} was damaged ({$damage}).
+damaged-chara-element = {$chara} was damaged by {$element} ({$damage}).
+damaged-chara-resisted = {$chara} was damaged by {$element} ({$damage}, resistance {$resistance}%).
 arrow-hit = The arrow hit {$chara}.
 killed-by-melee-attack = {$chara} was killed.
 killed-by-ranged-attack = {$chara} was killed.
 killed-by-poison-damage = {$chara} was killed by poison.
+killed-by-bleeding-damage = {$chara} bled to death.
 killed-by-starve-damage = {$chara} starved to death.
 
 # Messages about character action
@@ -67,12 +86,20 @@ fall-asleep = {$chara} fell asleep.
 poisoned = {$chara} is poisoned.
 asleep = {$chara} is asleep.
 poison-damage = {$chara} was damaged by poison ({$damage}).
+bleeding = {$chara} is bleeding.
+bleeding-damage = {$chara} lost blood ({$damage}).
+confused = {$chara} is confused.
+confusion-end = {$chara} is no longer confused.
+blessed = {$chara} is blessed.
 shop-lack-of-money = {$chara} do not have enough money to buy it.
 
 # Messages about quest
 
 quest-complete-slay_monsters = Quest completed! Slayed {$monster} x {$n}.
 quest-reward-receive-money = Recieved {$money} gold as the quest reward.
+quest-start = Undertook the quest "{$quest}".
+quest-complete = Quest completed! "{$quest}"
+receive-item = {$chara} received {$item} x {$n}.
 
 # Messages about creation
 
@@ -82,3 +109,9 @@ creation-finish = {$chara} finished making {$product}.
 # Messages about harvest
 
 harvest-chop = {$chara} chopped a tree, and get {$item} x {$n}.
+harvest-crop = {$chara} harvested {$item} x {$n}.
+harvest-crop-not-grown = {$item} has not grown yet.
+
+# Messages about weather
+
+weather-change = The weather has changed to {$weather}.
@@ -3,4 +3,6 @@ item_info_text-medical_effect = {$medical_effect}
 item_info_text-nutrition = Nutrition: {$nutrition}
 item_info_text-melee_weapon = Melee attack: {$dice_x}d{$dice_n}
 item_info_text-ranged_weapon = Ranged attack: {$dice_x}d{$dice_n}
+item_info_text-element = Element: {$element}
+item_info_text-ammo = Additional damage: {$dice_x}d{$dice_n}
 item_info_text-defence = Defence: Physical {$physical}, Fire {$fire}, Cold {$cold}, Shock {$shock}, Poison {$poison}, Spirit {$spirit}
@@ -5,6 +5,15 @@
 dungeon_kind-cave = Cave
 dungeon_kind-ruin = Ruin
 
+# CharaClass
+
+chara_class-none = None
+chara_class-adventurer = Adventurer
+chara_class-rogue = Rogue
+chara_class-sorcerer = Sorcerer
+chara_class-warrior = Warrior
+chara_class-civilian = Civilian
+
 # CharaStatus
 
 chara_status-hungry = Hungry
@@ -12,6 +21,10 @@ chara_status-weak = Weak
 chara_status-starving = Starving
 chara_status-asleep = Asleep
 chara_status-poisoned = Poisoned
+chara_status-creation = Creating
+chara_status-bleeding = Bleeding
+chara_status-confused = Confused
+chara_status-blessed = Blessed
 
 # ItemKind
 
@@ -33,6 +46,9 @@ skill_kind-bare_hands = Bare Hands
 skill_kind-defence = Defence
 skill_kind-evasion = Evasion
 skill_kind-magic_device = Magic Device
+skill_kind-throwing = Throwing
+magic_school-destruction = Destruction
+magic_school-enhancement = Enhancement
 
 # WeaponKind
 
@@ -44,14 +60,46 @@ weapon_kind-spear = Spear
 weapon_kind-sword = Sword
 weapon_kind-whip = Whip
 
+# AmmoKind
+
+ammo_kind-arrow = Arrow
+ammo_kind-bolt = Bolt
+ammo_kind-bullet = Bullet
+
 # ArmorKind
 
 armor_kind-body = Body
 armor_kind-shield = Shield
 
+# Element
+
+element-none = None
+element-physical = Physical
+element-fire = Fire
+element-cold = Cold
+element-shock = Shock
+element-poison = Poison
+element-spirit = Spirit
+
 # MedicalEffect
 
 medical_effect-none = No effect
 medical_effect-heal = Heals HP
 medical_effect-sleep = Sleep
 medical_effect-poison = Poison
+
+# Spell
+
+spell-magic_arrow = Magic Arrow
+spell-fire_ball = Fire Ball
+spell-lightning_bolt = Lightning Bolt
+spell-frost_breath = Frost Breath
+spell-bless = Bless
+
+# WeatherKind
+
+weather-clear = Clear
+weather-rain = Rain
+weather-snow = Snow
+weather-fog = Fog
+weather-storm = Storm
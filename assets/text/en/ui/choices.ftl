@@ -1,7 +1,10 @@
 creation-start = Start
 dialog-choice-close = Close
+dialog-choice-delete = Delete
+dialog-choice-duplicate = Duplicate
 dialog-choice-exit = Exit
 dialog-choice-exit_game = Exit Game
+dialog-choice-load = Load
 dialog-choice-loadgame = Load Game
 dialog-choice-newgame = New Game
 dialog-choice-save_game = Save Game
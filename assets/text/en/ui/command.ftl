@@ -11,5 +11,7 @@ command-drop_item = Drop Item
 command-drink_item = Drink Item
 command-eat_item = Eat Item
 command-release_item = Release Item
+command-throw_item = Throw Item
+command-cast_spell = Cast Spell
 command-targetting_mode = Targetting Mode
 command-shot = Shot
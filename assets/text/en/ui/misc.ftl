@@ -1 +1,4 @@
 item-charges = charges
+save_file-play_time = Play time
+save_file-saved_at = Saved
+term-unsupported = This window is not available in the terminal.
@@ -3,6 +3,9 @@ dialog-exit_to_regionmap = Do you want to exit to region map?
 dialog-gameover = You die...
 dialog-move_floor = Do you want to move from this floor?
 dialog-enter_site = Do you want to enter {$site_name}?
+dialog-delete_save = Do you want to delete this save data?
+dialog-load_newer_version = This save data was made by a newer version of Rusted Ruins.
+dialog-load_failed = Failed to load the save data.
 dialog-undertake_quest = Do you undertake this quest?
 newgame-chooseclass = Choose your class
 newgame-inputplayername = Please input your name.
@@ -12,20 +12,39 @@ change-floor = {$player}は別の階へ移動した。
 # Messages about character status
 
 skill-level-up = {$chara}の{$skill}スキルが上昇した。
+skill-reach-cap = {$chara}の{$skill}スキルはこれ以上上がらない。
+learn-skill = {$chara}は{$skill}スキルを習得した。
+read-skill-book = {$chara}は{$skill}の本を読んだ。
+training-succeed = {$chara}は{$skill}スキルを訓練した。
+training-lack-of-money = {$chara}は訓練のためのお金が足りない。
+chara-level-up = {$chara}はレベル{$level}になった！
 
 # Messages about combat
 
 attack = {$attacker}は{$target}を攻撃した。
 shot-target = {$attacker}は{$target}を撃った。
 no-ranged-weapon-equipped = 遠隔武器を装備していない。
+no-ammo = {$ammo}がない。
+throw-item = {$chara}は{$item}を投げた。
+potion-shatter = {$item}は砕け散った。
+potion-hit = {$chara}に{$item}がかかった。
 no-target = {$chara}はターゲットを見つけられなかった。
+not-enough-sp = {$chara}のSPが足りない。
+cast-spell = {$chara}は{$spell}を唱えた。
+learn-spell = {$chara}は{$spell}を習得した。
+already-learned-spell = {$chara}は既に{$spell}を習得している。
 target-chara = {$chara}は{$target}をターゲットにした。
+target-tile = {$chara}はタイルをターゲットにした。
 attack-evade = {$chara}は攻撃を避けた。
+critical-hit = 会心の一撃！
 damaged-chara = {$chara}はダメージを受けた({$damage})。
+damaged-chara-element = {$chara}は{$element}のダメージを受けた({$damage})。
+damaged-chara-resisted = {$chara}は{$element}のダメージを受けた({$damage}、耐性{$resistance}%)。
 arrow-hit = 矢は{$chara}に命中した。
 killed-by-melee-attack = {$chara}は倒された。
 killed-by-ranged-attack = {$chara}は倒された。
 killed-by-poison-damage = {$chara}は毒により死んだ。
+killed-by-bleeding-damage = {$chara}は失血により死んだ。
 killed-by-starve-damage = {$chara}は餓死した。
 
 # Messages about character action
@@ -43,14 +62,31 @@ fall-asleep = {$chara}は眠りに落ちた。
 poisoned = {$chara}は毒を受けた。
 asleep = {$chara}は眠っている。
 poison-damage = {$chara}は毒のダメージを受けた({$damage})。
+bleeding = {$chara}は出血した。
+bleeding-damage = {$chara}は出血によるダメージを受けた({$damage})。
+confused = {$chara}は混乱した。
+confusion-end = {$chara}の混乱が解けた。
+blessed = {$chara}は祝福された。
 shop-lack-of-money = {$chara}はそれを買うのに十分なお金を持っていない。
 
 # Messages about quest
 
 quest-complete-slay_monsters = {$monster}を{$n}体始末した。クエストを達成した。
 quest-reward-receive-money = {$money}ゴールドを報酬として受け取った。
+quest-start = クエスト「{$quest}」を引き受けた。
+quest-complete = クエスト「{$quest}」を達成した。
+receive-item = {$chara}は{$item}を{$n}個受け取った。
 
 # Messages about creation
 
 creation-start = {$chara}は{$product}の作成を始めた。
 creation-finish = {$chara}は{$product}の作成を終了した。
+
+# Messages about harvest
+
+harvest-crop = {$chara}は{$item}を{$n}個収穫した。
+harvest-crop-not-grown = {$item}はまだ育っていない。
+
+# Messages about weather
+
+weather-change = 天気が{$weather}に変わった。
@@ -3,4 +3,6 @@ item_info_text-medical_effect = {$medical_effect}
 item_info_text-nutrition = 栄養価: {$nutrition}
 item_info_text-melee_weapon = 近接攻撃力: {$dice_x}d{$dice_n}
 item_info_text-ranged_weapon = 遠隔攻撃力: {$dice_x}d{$dice_n}
+item_info_text-element = 属性: {$element}
+item_info_text-ammo = 追加攻撃力: {$dice_x}d{$dice_n}
 item_info_text-defence = 守備力: 物理 {$physical}, 火炎 {$fire}, 冷気 {$cold}, 電撃 {$shock}, 毒 {$poison}, 精神 {$spirit}
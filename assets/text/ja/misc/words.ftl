@@ -5,6 +5,15 @@
 dungeon_kind-cave = 洞窟
 dungeon_kind-ruin = 遺跡
 
+# CharaClass
+
+chara_class-none = なし
+chara_class-adventurer = 冒険者
+chara_class-rogue = 盗賊
+chara_class-sorcerer = 魔術師
+chara_class-warrior = 戦士
+chara_class-civilian = 市民
+
 # CharaStatus
 
 chara_status-hungry = 空腹
@@ -12,6 +21,10 @@ chara_status-weak = 衰弱
 chara_status-starving = 飢え
 chara_status-asleep = 睡眠
 chara_status-poisoned = 毒
+chara_status-creation = 製作中
+chara_status-bleeding = 出血
+chara_status-confused = 混乱
+chara_status-blessed = 祝福
 
 # ItemKind
 
@@ -32,6 +45,9 @@ skill_kind-bare_hands = 格闘
 skill_kind-defence = 守備
 skill_kind-evasion = 回避
 skill_kind-magic_device = 魔道具
+skill_kind-throwing = 投擲
+magic_school-destruction = 破壊魔法
+magic_school-enhancement = 強化魔法
 
 # WeaponKind
 
@@ -43,14 +59,46 @@ weapon_kind-spear = 槍
 weapon_kind-sword = 剣
 weapon_kind-whip = 鞭
 
+# AmmoKind
+
+ammo_kind-arrow = 矢
+ammo_kind-bolt = 矢弾
+ammo_kind-bullet = 弾丸
+
 # ArmorKind
 
 armor_kind-body = 体防具
 armor_kind-shield = 盾
 
+# Element
+
+element-none = なし
+element-physical = 物理
+element-fire = 火炎
+element-cold = 冷気
+element-shock = 電撃
+element-poison = 毒
+element-spirit = 精神
+
 # MedicalEffect
 
 medical_effect-none = 効果なし
 medical_effect-heal = HP回復
 medical_effect-sleep = 睡眠
 medical_effect-poison = 毒
+
+# Spell
+
+spell-magic_arrow = マジックアロー
+spell-fire_ball = ファイアボール
+spell-lightning_bolt = ライトニングボルト
+spell-frost_breath = フロストブレス
+spell-bless = 祝福
+
+# WeatherKind
+
+weather-clear = 晴れ
+weather-rain = 雨
+weather-snow = 雪
+weather-fog = 霧
+weather-storm = 嵐
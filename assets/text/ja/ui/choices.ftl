@@ -5,3 +5,6 @@ dialog-choice-close = 閉じる
 dialog-choice-exit_game = ゲーム終了
 dialog-choice-save_game = ゲームを保存
 creation-start = 開始
+dialog-choice-load = ロード
+dialog-choice-duplicate = 複製
+dialog-choice-delete = 削除
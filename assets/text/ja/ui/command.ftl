@@ -11,5 +11,7 @@ command-drop_item = アイテムを置く
 command-drink_item = アイテムを飲む
 command-eat_item = アイテムを食べる
 command-release_item = 魔道具を使う
+command-throw_item = 投げる
+command-cast_spell = 魔法を唱える
 command-targetting_mode = ターゲットモード
 command-shot = 撃つ
@@ -1 +1,4 @@
 item-charges = チャージ回数
+save_file-play_time = プレイ時間
+save_file-saved_at = 保存日時
+term-unsupported = このウィンドウは端末では使えません。
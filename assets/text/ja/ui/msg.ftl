@@ -3,6 +3,9 @@ dialog-exit_to_regionmap = リージョンマップに戻りますか?
 dialog-gameover = やられてしまった…。
 dialog-move_floor = この階から移動しますか?
 dialog-enter_site = {$site_name}に入りますか?
+dialog-delete_save = このセーブデータを削除しますか?
+dialog-load_newer_version = このセーブデータは新しいバージョンのRusted Ruinsで作成されています。
+dialog-load_failed = セーブデータの読み込みに失敗しました。
 dialog-undertake_quest = このクエストを受諾しますか?
 newgame-chooseclass = クラスを選択して下さい
 newgame-inputplayername = プレイヤー名を入力して下さい
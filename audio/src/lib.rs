@@ -11,13 +11,14 @@ mod wavtable;
 
 use crate::musictable::MusicTable;
 use crate::wavtable::WavTable;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::path::Path;
 
 thread_local!(static AUDIO_PLAYER: RefCell<Option<AudioPlayer>> = RefCell::new(None));
+thread_local!(static NULL_SINK: Cell<bool> = const { Cell::new(false) });
 
 pub struct AudioContext {
-    _mixer_context: sdl2::mixer::Sdl2MixerContext,
+    _mixer_context: Option<sdl2::mixer::Sdl2MixerContext>,
 }
 
 /// Initialize AudioPlayer
@@ -29,11 +30,26 @@ pub fn init<P: AsRef<Path>>(data_dirs: &[P]) -> AudioContext {
         *a.borrow_mut() = Some(AudioPlayer::new(data_dirs));
     });
     AudioContext {
-        _mixer_context: mixer_context,
+        _mixer_context: Some(mixer_context),
+    }
+}
+
+/// Initialize as a null sink that does not open any audio device.
+/// All sounds and musics are ignored. Used for headless mode.
+pub fn init_null() -> AudioContext {
+    NULL_SINK.with(|n| {
+        assert!(!n.get());
+        n.set(true);
+    });
+    AudioContext {
+        _mixer_context: None,
     }
 }
 
 pub fn with_audio_player<F: FnOnce(&AudioPlayer)>(f: F) {
+    if NULL_SINK.with(|n| n.get()) {
+        return;
+    }
     AUDIO_PLAYER.with(|a| {
         assert!(a.borrow().is_some());
         f(a.borrow().as_ref().unwrap());
@@ -47,6 +63,13 @@ pub fn play_sound(name: &str) {
     });
 }
 
+/// Play an sound repeatedly as ambience, or stop the ambience if None
+pub fn play_ambience(name: Option<&str>) {
+    with_audio_player(|a| {
+        a.play_ambience(name);
+    });
+}
+
 /// Play an music (ogg file)
 pub fn play_music(name: &str) {
     with_audio_player(|a| {
@@ -55,6 +78,9 @@ pub fn play_music(name: &str) {
 }
 
 fn finalize() {
+    if NULL_SINK.with(|n| n.replace(false)) {
+        return;
+    }
     AUDIO_PLAYER.with(|a| {
         assert!(a.borrow().is_some());
         *a.borrow_mut() = None;
@@ -88,6 +114,12 @@ impl AudioPlayer {
         }
     }
 
+    pub fn play_ambience(&self, name: Option<&str>) {
+        if let Err(e) = self.wavtable.play_ambience(name) {
+            warn!("{}", e);
+        }
+    }
+
     pub fn play_music(&self, name: &str) {
         if let Err(e) = self.musictable.play(name) {
             warn!("{}", e);
@@ -106,7 +138,7 @@ fn init_device() -> sdl2::mixer::Sdl2MixerContext {
     sdl2::mixer::open_audio(frequency, format, channels, chunk_size).unwrap();
     let mixer_context = sdl2::mixer::init(InitFlag::OGG).unwrap();
 
-    sdl2::mixer::allocate_channels(1);
+    sdl2::mixer::allocate_channels(2);
 
     mixer_context
 }
@@ -1,10 +1,14 @@
 use sdl2::mixer::{self, Channel, Chunk};
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
 pub struct WavTable {
     channel: Channel,
+    /// Channel for ambient sounds played repeatedly
+    ambience_channel: Channel,
+    ambience: RefCell<Option<String>>,
     chunks: HashMap<String, Chunk>,
 }
 
@@ -37,6 +41,8 @@ impl WavTable {
 
         WavTable {
             channel: mixer::Channel(0),
+            ambience_channel: mixer::Channel(1),
+            ambience: RefCell::new(None),
             chunks,
         }
     }
@@ -49,4 +55,26 @@ impl WavTable {
             Err(format!("Unknown sound effect \"{}\"", name))
         }
     }
+
+    /// Play an ambient sound repeatedly. Stop it if None
+    pub fn play_ambience(&self, name: Option<&str>) -> Result<(), String> {
+        let mut current = self.ambience.borrow_mut();
+        if current.as_deref() == name {
+            return Ok(());
+        }
+        *current = name.map(|name| name.to_owned());
+
+        if let Some(name) = name {
+            if let Some(chunk) = self.chunks.get(name) {
+                self.ambience_channel.play(chunk, -1)?;
+                Ok(())
+            } else {
+                self.ambience_channel.halt();
+                Err(format!("Unknown sound effect \"{}\"", name))
+            }
+        } else {
+            self.ambience_channel.halt();
+            Ok(())
+        }
+    }
 }
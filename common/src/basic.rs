@@ -27,6 +27,12 @@ pub const MAX_EQUIP_SLOT: usize = 16;
 
 pub const WAIT_TIME_NUMERATOR: u32 = 100000;
 
+/// The version of save data format.
+/// Increment this and add a migration when the format is changed.
+pub const SAVE_VERSION: u32 = 2;
+/// The number of backups kept for each save
+pub const SAVE_BACKUPS: u32 = 3;
+
 /// Needed exp value to level up
 pub const SKILL_EXP_LVUP: u16 = 10000;
 
@@ -4,7 +4,9 @@ use super::map::MapId;
 use super::site::SiteId;
 use super::skill::SkillList;
 use super::unknown_id_err;
+use super::variables::LocalVars;
 use crate::objholder::CharaTemplateIdx;
+use geom::Vec2d;
 use std::collections::HashMap;
 
 /// Character's races
@@ -55,6 +57,21 @@ pub enum Relationship {
     HOSTILE,
 }
 
+impl std::str::FromStr for Relationship {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use self::Relationship::*;
+        match s {
+            "ally" => Ok(ALLY),
+            "friendly" => Ok(FRIENDLY),
+            "neutral" => Ok(NEUTRAL),
+            "hostile" => Ok(HOSTILE),
+            _ => Err(()),
+        }
+    }
+}
+
 impl Relationship {
     pub fn relative(&self, other: Relationship) -> Relationship {
         use self::Relationship::*;
@@ -93,6 +110,15 @@ pub struct Chara {
     pub rel: Relationship,
     /// When talked, execute this script
     pub trigger_talk: Option<String>,
+    /// Ids of learned spells
+    #[serde(default)]
+    pub spells: Vec<String>,
+    /// Exp toward the next level
+    #[serde(default)]
+    pub exp: u32,
+    /// Variables referenced by scripts executed by this character
+    #[serde(default)]
+    pub vars: LocalVars,
 }
 
 /// Character attributes
@@ -132,7 +158,7 @@ pub struct CharaBaseAttr {
     pub spd: i16,
 }
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
 pub struct CharaAttrRevision {
     pub hp: i32,
     pub str: i16,
@@ -159,26 +185,38 @@ impl CharaBaseAttr {
     }
 }
 
-/// Represents chara status
-#[derive(Clone, PartialEq, PartialOrd, Debug, Serialize, Deserialize)]
-pub enum CharaStatus {
-    /// Sp status
-    Hungry,
-    /// Sp status
-    Weak,
-    /// Sp status
-    Starving,
-    Asleep {
-        turn_left: u16,
-    },
-    Poisoned,
+/// Represents chara status.
+/// The effects of each status are defined in rules by its id.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct CharaStatus {
+    pub id: String,
+    /// Remaining turns. None if this status continues until removed
+    pub turn_left: Option<u16>,
+    /// The number of stacks for stackable statuses
+    pub stacks: u16,
+    /// Additional data used when this status expires
+    pub data: Option<CharaStatusData>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum CharaStatusData {
     Creation {
-        turn_left: u16,
         recipe: Recipe,
         ingredients: Vec<Item>,
     },
 }
 
+impl CharaStatus {
+    pub fn new<S: ToString>(id: S, turn_left: Option<u16>) -> CharaStatus {
+        CharaStatus {
+            id: id.to_string(),
+            turn_left,
+            stacks: 1,
+            data: None,
+        }
+    }
+}
+
 impl Default for Chara {
     fn default() -> Chara {
         Chara {
@@ -197,6 +235,9 @@ impl Default for Chara {
             skills: SkillList::default(),
             rel: Relationship::NEUTRAL,
             trigger_talk: None,
+            spells: Vec::new(),
+            exp: 0,
+            vars: LocalVars::default(),
         }
     }
 }
@@ -226,27 +267,62 @@ pub enum CharaId {
 }
 
 /// Data to determine NPC character's actions
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct CharaAI {
     pub kind: NpcAIKind,
+    /// Current state. This is changed by the situation around the npc
+    pub state: AIState,
+    /// The state this npc goes back to when the current situation is resolved
+    pub default_state: AIState,
+    /// Home position on the map. The npc returns here when it goes too far
+    pub home: Option<Vec2d>,
+    /// Positions for patrolling
+    pub waypoints: Vec<Vec2d>,
 }
 
 /// Rough kind of NPC AI
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum NpcAIKind {
-    /// This npc does not do anything.
+    /// This npc does not do anything except by its AI state.
     None,
-    /// This npc will not move
+    /// This npc will not move except by its AI state
     NoMove,
     /// This npc will chase near enemies, and try melee atacks
     Melee,
+    /// This npc will keep distance from near enemies, and shoot them by ranged weapons
+    Ranged,
+    /// This npc will cast learned spells or use magic devices to near enemies
+    Caster,
+}
+
+/// State of NPC AI
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AIState {
+    /// Acts by NpcAIKind
+    #[default]
+    Normal,
+    /// Moves around waypoints. The index of the next waypoint is stored
+    Patrol { next: u32 },
+    /// Goes back to home position
+    ReturnHome,
+    /// Stays around home position, and attacks enemies coming into the area
+    Guard { range: u32 },
+    /// Runs away from enemies
+    Flee,
+    /// Goes back to home position and stays there during the night
+    Rest,
 }
 
 impl Default for CharaAI {
     fn default() -> CharaAI {
         CharaAI {
             kind: NpcAIKind::None,
+            state: AIState::default(),
+            default_state: AIState::default(),
+            home: None,
+            waypoints: Vec::new(),
         }
     }
 }
@@ -291,6 +367,12 @@ impl CharaHolder {
         .unwrap_or_else(|| unknown_id_err(cid))
     }
 
+    /// Iterate over characters in this holder.
+    /// Characters on other maps than the current map are not included.
+    pub fn iter(&self) -> impl Iterator<Item = (&CharaId, &Chara)> {
+        self.c.iter().chain(self.on_map.iter())
+    }
+
     pub(crate) fn remove_chara(&mut self, cid: CharaId) {
         match cid {
             CharaId::OnMap { .. } => &mut self.on_map,
@@ -1,10 +1,11 @@
 //! Miscellaneous type definitions
 
+use super::skill::SkillKind;
 use crate::objholder::ItemIdx;
 use std::ops::{Index, IndexMut};
 
 /// Elements of damage/attack
-#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum Element {
     None = -1,
@@ -84,11 +85,27 @@ impl Default for MedicalEffect {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum MagicalEffect {
     None,
     Arrow,
+    /// Damages characters within the radius around the target
+    Ball {
+        radius: u32,
+    },
+    /// Damages characters on the line from the caster toward the target
+    Beam {
+        length: u32,
+    },
+    /// Damages characters in the fan-shaped area from the caster toward the target
+    Cone {
+        length: u32,
+    },
+    /// Gives a status to the caster
+    SelfBuff {
+        status: String,
+    },
 }
 
 impl Default for MagicalEffect {
@@ -116,6 +133,14 @@ impl Default for ToolEffect {
 pub enum UseEffect {
     None,
     Deed,
+    /// Teaches the spell to the user
+    LearnSpell {
+        spell: String,
+    },
+    /// Teaches the skill, or gives exp if the user already has it
+    SkillBook {
+        skill: SkillKind,
+    },
 }
 
 impl Default for UseEffect {
@@ -129,6 +154,9 @@ impl Default for UseEffect {
 pub struct Reward {
     pub money: i64,
     pub item: Vec<ItemIdx>,
+    /// Character exp
+    #[serde(default)]
+    pub exp: u32,
 }
 
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
@@ -137,6 +165,9 @@ pub struct Harvest {
     pub target_item: String,
     pub difficulty: u32,
     pub n_yield: u32,
+    /// Hours for crops to become harvestable in the standard condition
+    #[serde(default)]
+    pub growing_hours: u32,
 }
 
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
@@ -0,0 +1,92 @@
+//! Event triggers which start scripts
+
+use super::time::{Time, SECS_PER_DAY};
+use geom::Vec2d;
+
+/// Events which start scripts
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    /// Player enters the map
+    EnterMap,
+    /// Player steps on the tile
+    StepOn(Vec2d),
+    /// Player picks up the item (item id)
+    PickUpItem(String),
+    /// A character generated from the template is killed (chara template id)
+    Kill(String),
+    /// Every day at the hour
+    Daily(u16),
+}
+
+/// Starts a section of the script when the event occurs
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct EventTrigger {
+    pub event: EventKind,
+    pub script_id: String,
+    pub section: String,
+    /// Remove this trigger after executed once
+    pub once: bool,
+    /// The last time this trigger started the script
+    pub last_time: Option<Time>,
+}
+
+impl EventTrigger {
+    pub fn new(event: EventKind, script_id: String, section: String, once: bool) -> EventTrigger {
+        EventTrigger {
+            event,
+            script_id,
+            section,
+            once,
+            last_time: None,
+        }
+    }
+
+    /// Scheduled triggers are executed once a day after the given hour
+    pub fn is_scheduled(&self, now: Time) -> bool {
+        let hour = match self.event {
+            EventKind::Daily(hour) => hour,
+            _ => return false,
+        };
+        if now.into_date().hour < hour {
+            return false;
+        }
+        if let Some(last_time) = self.last_time {
+            last_time.as_secs() / SECS_PER_DAY < now.as_secs() / SECS_PER_DAY
+        } else {
+            true
+        }
+    }
+}
+
+#[test]
+fn is_scheduled_test() {
+    use super::time::GameTime;
+
+    let mut trigger = EventTrigger::new(
+        EventKind::Daily(9),
+        "test".to_owned(),
+        "start".to_owned(),
+        false,
+    );
+    let time_at = |days: u32, hours: u32| GameTime::new(1, 1, days, hours).current_time();
+
+    // Not before the hour
+    assert!(!trigger.is_scheduled(time_at(1, 8)));
+    assert!(trigger.is_scheduled(time_at(1, 9)));
+    assert!(trigger.is_scheduled(time_at(1, 23)));
+
+    // Once per day
+    trigger.last_time = Some(time_at(1, 9));
+    assert!(!trigger.is_scheduled(time_at(1, 10)));
+    assert!(!trigger.is_scheduled(time_at(2, 8)));
+    assert!(trigger.is_scheduled(time_at(2, 9)));
+
+    let trigger = EventTrigger::new(
+        EventKind::EnterMap,
+        "test".to_owned(),
+        "start".to_owned(),
+        false,
+    );
+    assert!(!trigger.is_scheduled(time_at(1, 12)));
+}
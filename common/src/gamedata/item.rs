@@ -38,6 +38,8 @@ pub struct ItemObject {
     pub gen_level: u32,
     pub dice_n: u16,
     pub dice_x: u16,
+    /// Attack element for weapons and magic devices
+    pub element: Element,
     /// Defence
     pub def: ElementArray<u16>,
     /// Effectiveness of this item
@@ -54,6 +56,8 @@ pub struct ItemObject {
     pub harvest: Option<Harvest>,
     /// Facility type for creation
     pub facility: Option<String>,
+    /// Radius of the light from this item. 0 if this is not a light source
+    pub light: u32,
 }
 
 impl Ord for Item {
@@ -124,6 +128,8 @@ pub enum ItemKind {
     Material,
     /// Other items that might not have effects, but have some price.
     Object,
+    /// Consumed by ranged weapons.
+    Ammo(AmmoKind),
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
@@ -139,6 +145,7 @@ pub enum ItemKindRough {
     Special,
     Material,
     Object,
+    Ammo,
 }
 
 impl ItemKind {
@@ -154,6 +161,7 @@ impl ItemKind {
             ItemKind::Special => ItemKindRough::Special,
             ItemKind::Material => ItemKindRough::Material,
             ItemKind::Object => ItemKindRough::Object,
+            ItemKind::Ammo(_) => ItemKindRough::Ammo,
         }
     }
 }
@@ -201,6 +209,8 @@ pub enum ItemAttribute {
     /// Data to generate the contents.
     /// Used to fix generated contents when this item is opened.
     ContentGen { level: u32, seed: u32 },
+    /// Growth of crops. Increased by 100 per hour in the standard condition
+    Growth { n: u32 },
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
@@ -221,8 +231,26 @@ impl WeaponKind {
     }
 
     pub fn is_ranged(self) -> bool {
-        self.is_melee()
+        !self.is_melee()
     }
+
+    /// The kind of ammunition consumed by this weapon
+    pub fn ammo_kind(self) -> Option<AmmoKind> {
+        match self {
+            WeaponKind::Bow => Some(AmmoKind::Arrow),
+            WeaponKind::Crossbow => Some(AmmoKind::Bolt),
+            WeaponKind::Firearm => Some(AmmoKind::Bullet),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AmmoKind {
+    Arrow,
+    Bolt,
+    Bullet,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
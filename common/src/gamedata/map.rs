@@ -1,8 +1,10 @@
 use crate::basic::{MAX_ITEM_FOR_DRAW, N_TILE_IMG_LAYER};
 use crate::gamedata::chara::{Chara, CharaId};
+use crate::gamedata::event::EventTrigger;
 use crate::gamedata::item::{Item, ItemList};
 use crate::gamedata::region::RegionId;
 use crate::gamedata::site::SiteId;
+use crate::gamedata::time::Time;
 use crate::objholder::*;
 use arrayvec::ArrayVec;
 use geom::*;
@@ -28,6 +30,15 @@ pub struct Map {
     /// If this is None, nearest tile's infomation will be used
     pub outside_tile: Option<OutsideTileInfo>,
     pub boundary: MapBoundary,
+    /// Scripts started by events on this map
+    #[serde(default)]
+    pub triggers: Vec<EventTrigger>,
+    /// On dark maps, player can see only tiles lit by light sources
+    #[serde(default)]
+    pub dark: bool,
+    /// The last time crops on this map grew
+    #[serde(default)]
+    pub last_growth: Time,
 }
 
 pub type TileArray = ArrayVec<[TileIdxPP; N_TILE_IMG_LAYER]>;
@@ -169,6 +180,9 @@ pub struct TileInfo {
     pub item_list: Option<ItemList>,
     pub chara: Option<CharaId>,
     pub special: SpecialTileKind,
+    /// Lit tiles can be seen without light sources on dark maps
+    #[serde(default)]
+    pub lit: bool,
 }
 
 /// The data for map drawing
@@ -221,6 +235,7 @@ impl Default for TileInfo {
             item_list: None,
             chara: None,
             special: SpecialTileKind::None,
+            lit: false,
         }
     }
 }
@@ -249,6 +264,9 @@ impl Map {
             charas: Some(HashMap::new()),
             outside_tile: None,
             boundary: MapBoundary::default(),
+            triggers: Vec::new(),
+            dark: false,
+            last_growth: Time::default(),
         }
     }
 
@@ -506,6 +524,20 @@ impl MapId {
             _ => false,
         }
     }
+
+    /// Returns a number that is unique for each map.
+    /// It is used as the key to derive seeds of random number streams.
+    pub fn as_u64(self) -> u64 {
+        match self {
+            MapId::SiteMap { sid, floor } => {
+                ((sid.rid.0 as u64) << 48)
+                    | ((sid.kind as u64) << 40)
+                    | ((sid.n as u64 & 0xF_FFFF) << 20)
+                    | (floor as u64 & 0xF_FFFF)
+            }
+            MapId::RegionMap { rid } => (1 << 63) | rid.0 as u64,
+        }
+    }
 }
 
 impl Default for MapId {
@@ -1,8 +1,38 @@
+use super::chara::CharaClass;
+use super::time::Date;
+use crate::basic::SAVE_VERSION;
+
 /// Meta data
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct MetaData {
     /// Save directory name
     save_name: String,
+    /// Save data format version. Saves without this field are version 0
+    #[serde(default)]
+    version: u32,
+    /// Summary of the game shown in the save file list.
+    /// They are updated when saving.
+    #[serde(default)]
+    pub player_name: String,
+    #[serde(default)]
+    pub chara_class: Option<CharaClass>,
+    #[serde(default)]
+    pub level: u32,
+    /// The name of the site or region where the player is
+    #[serde(default)]
+    pub site_name: String,
+    /// None if the player is on a region map
+    #[serde(default)]
+    pub floor: Option<u32>,
+    /// In-game date
+    #[serde(default)]
+    pub date: Option<Date>,
+    /// Real play time in seconds
+    #[serde(default)]
+    pub play_time: u64,
+    /// Real time of saving as UNIX time
+    #[serde(default)]
+    pub timestamp: u64,
 }
 
 impl MetaData {
@@ -13,12 +43,29 @@ impl MetaData {
     pub fn set_save_name(&mut self, s: &str) {
         self.save_name = s.to_owned();
     }
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    pub(crate) fn set_version(&mut self, version: u32) {
+        self.version = version;
+    }
 }
 
 impl Default for MetaData {
     fn default() -> MetaData {
         MetaData {
             save_name: "uninit".to_owned(),
+            version: SAVE_VERSION,
+            player_name: String::new(),
+            chara_class: None,
+            level: 0,
+            site_name: String::new(),
+            floor: None,
+            date: None,
+            play_time: 0,
+            timestamp: 0,
         }
     }
 }
@@ -1,5 +1,6 @@
 pub mod chara;
 pub mod defs;
+pub mod event;
 pub mod item;
 pub mod map;
 pub mod meta;
@@ -12,11 +13,13 @@ pub mod skill;
 pub mod time;
 pub mod town;
 pub mod variables;
+pub mod weather;
 
 use geom::Vec2d;
 
 pub use self::chara::*;
 pub use self::defs::*;
+pub use self::event::*;
 pub use self::item::*;
 pub use self::map::*;
 pub use self::meta::*;
@@ -29,6 +32,7 @@ pub use self::skill::*;
 pub use self::time::*;
 pub use self::town::*;
 pub use self::variables::*;
+pub use self::weather::*;
 
 /// Includes all data for one game world.
 /// This can be a snapshot of the current game, so it must implement Serialize and Deserialize.
@@ -42,6 +46,8 @@ pub struct GameData {
     pub player: Player,
     pub quest: QuestHolder,
     pub vars: Variables,
+    /// The seed for random number streams of this world
+    pub world_seed: u64,
     current_mapid: MapId,
 }
 
@@ -55,6 +61,7 @@ impl GameData {
             player: Player::default(),
             quest: QuestHolder::new(),
             vars: Variables::new(),
+            world_seed: 0,
             current_mapid: MapId::default(),
         }
     }
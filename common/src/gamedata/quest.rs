@@ -31,6 +31,28 @@ impl QuestHolder {
         self.quests.push((QuestState::Active, quest));
     }
 
+    /// Returns the state of the quest managed by scripts
+    pub fn custom_quest_state(&self, quest_id: &str) -> Option<QuestState> {
+        self.quests.iter().find_map(|(state, quest)| match quest {
+            Quest::Custom { id } if id == quest_id => Some(*state),
+            _ => None,
+        })
+    }
+
+    /// Set the state of the quest managed by scripts. Returns false if not found.
+    pub fn set_custom_quest_state(&mut self, quest_id: &str, new_state: QuestState) -> bool {
+        for (state, quest) in self.quests.iter_mut() {
+            match quest {
+                Quest::Custom { id } if id == quest_id => {
+                    *state = new_state;
+                    return true;
+                }
+                _ => (),
+            }
+        }
+        false
+    }
+
     pub fn remove_reward_received(&mut self) {
         self.quests
             .retain(|&(state, _)| state != QuestState::RewardReceived);
@@ -45,12 +67,21 @@ pub enum Quest {
         goal: u32,
         killed: u32,
     },
+    /// Quest started and completed by scripts. Rewards are given by scripts.
+    Custom { id: String },
 }
 
+static NO_REWARD: Reward = Reward {
+    money: 0,
+    item: Vec::new(),
+    exp: 0,
+};
+
 impl Quest {
     pub fn reward(&self) -> &Reward {
         match self {
             Quest::SlayMonsters { reward, .. } => reward,
+            Quest::Custom { .. } => &NO_REWARD,
         }
     }
 }
@@ -1,6 +1,7 @@
 use super::map::*;
 use super::site::*;
 use super::unknown_id_err;
+use super::weather::Weather;
 use filebox::FileBox;
 use geom::*;
 use std::collections::HashMap;
@@ -17,6 +18,8 @@ pub struct Region {
     pub(crate) sites: HashMap<SiteId, SiteInfo>,
     /// An map to represents this region
     pub(crate) map: BoxedMap,
+    #[serde(default)]
+    pub weather: Weather,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -214,6 +217,7 @@ impl Region {
             id: RegionId(0),
             sites: HashMap::new(),
             map: FileBox::new(map_random_id, map),
+            weather: Weather::default(),
         }
     }
 
@@ -1,6 +1,8 @@
+use super::event::EventTrigger;
 use super::map::Map;
 use super::region::RegionId;
 use super::town::Town;
+use super::variables::LocalVars;
 use filebox::FileBox;
 
 pub type BoxedMap = FileBox<Map>;
@@ -15,6 +17,12 @@ pub struct Site {
     max_floor: u32,
     /// Site kind specific data
     pub content: SiteContent,
+    /// Variables referenced by scripts executed on this site
+    #[serde(default)]
+    pub vars: LocalVars,
+    /// Scripts started by events on any floor of this site
+    #[serde(default)]
+    pub triggers: Vec<EventTrigger>,
 }
 
 /// Site kind specific data
@@ -44,6 +52,8 @@ impl Site {
             map: Vec::new(),
             max_floor,
             content: SiteContent::Other,
+            vars: LocalVars::default(),
+            triggers: Vec::new(),
         }
     }
 
@@ -11,6 +11,47 @@ pub enum SkillKind {
     MagicDevice,
     BareHands,
     Weapon(WeaponKind),
+    Throwing,
+    Magic(MagicSchool),
+}
+
+/// Schools of learnable spells. Each school has its own skill
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MagicSchool {
+    /// Spells to damage enemies
+    Destruction,
+    /// Spells to give good status to the caster
+    Enhancement,
+}
+
+impl std::str::FromStr for SkillKind {
+    type Err = ();
+
+    /// Parse skill kind like "healing", "weapon.sword" or "magic.destruction"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use SkillKind::*;
+        let kind = match s {
+            "endurance" => Endurance,
+            "healing" => Healing,
+            "defence" => Defence,
+            "evasion" => Evasion,
+            "magic_device" => MagicDevice,
+            "bare_hands" => BareHands,
+            "throwing" => Throwing,
+            "weapon.sword" => Weapon(WeaponKind::Sword),
+            "weapon.spear" => Weapon(WeaponKind::Spear),
+            "weapon.axe" => Weapon(WeaponKind::Axe),
+            "weapon.whip" => Weapon(WeaponKind::Whip),
+            "weapon.bow" => Weapon(WeaponKind::Bow),
+            "weapon.crossbow" => Weapon(WeaponKind::Crossbow),
+            "weapon.firearm" => Weapon(WeaponKind::Firearm),
+            "magic.destruction" => Magic(MagicSchool::Destruction),
+            "magic.enhancement" => Magic(MagicSchool::Enhancement),
+            _ => return Err(()),
+        };
+        Ok(kind)
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
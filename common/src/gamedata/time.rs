@@ -50,6 +50,10 @@ impl Time {
         Time { secs }
     }
 
+    pub const fn as_secs(self) -> u64 {
+        self.secs
+    }
+
     pub fn duration_from(&self, t: Time) -> Duration {
         assert!(t.secs <= self.secs);
         Duration::from_seconds(self.secs - t.secs)
@@ -134,7 +138,7 @@ impl Duration {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct Date {
     pub sec: u16,
     pub minute: u16,
@@ -35,3 +35,17 @@ impl Variables {
         self.global.insert("?".to_owned(), v);
     }
 }
+
+/// Holds variables which belong to a character, a site or an execution of a script
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LocalVars(HashMap<String, Value>);
+
+impl LocalVars {
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.0.get(name)
+    }
+
+    pub fn set<S: ToString>(&mut self, name: S, v: Value) {
+        self.0.insert(name.to_string(), v);
+    }
+}
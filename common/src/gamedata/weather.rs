@@ -0,0 +1,57 @@
+//! Weather and seasons
+
+use super::time::{Date, Time};
+
+/// Season determined by the month
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    pub fn from_month(month: u16) -> Season {
+        match month {
+            3..=5 => Season::Spring,
+            6..=8 => Season::Summer,
+            9..=11 => Season::Autumn,
+            _ => Season::Winter,
+        }
+    }
+}
+
+impl Date {
+    pub fn season(&self) -> Season {
+        Season::from_month(self.month)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WeatherKind {
+    Clear,
+    Rain,
+    Snow,
+    Fog,
+    Storm,
+}
+
+/// Current weather of a region
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Weather {
+    pub kind: WeatherKind,
+    /// The weather will change after this time
+    pub next_change: Time,
+}
+
+impl Default for Weather {
+    fn default() -> Weather {
+        Weather {
+            kind: WeatherKind::Clear,
+            next_change: Time::default(),
+        }
+    }
+}
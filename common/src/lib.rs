@@ -20,6 +20,7 @@ pub mod gamedata;
 pub mod gobj;
 pub mod impl_filebox;
 pub mod maptemplate;
+pub mod migration;
 pub mod objholder;
 pub mod pakutil;
 pub mod piece_pattern;
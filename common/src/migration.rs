@@ -0,0 +1,235 @@
+//! Migrations for save data created by older versions.
+//!
+//! Save data are converted step by step from their version to the current version
+//! before loading. Each step rewrites the raw CBOR values of the save files,
+//! so it does not need the old definitions of data types.
+
+use crate::basic::SAVE_VERSION;
+use crate::gamedata::MetaData;
+use crate::utils::to_writer_packed;
+use flate2::bufread::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde_cbor::Value;
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
+use thiserror::Error;
+
+type MigrationFn = fn(&Path) -> Result<(), MigrationError>;
+
+/// Migration functions. The n-th function converts version n to version n + 1.
+const MIGRATIONS: &[MigrationFn] = &[v0_to_v1, v1_to_v2];
+
+#[derive(Error, Debug)]
+pub enum MigrationError {
+    #[error("save data version {save} is newer than supported version {current}")]
+    NewerVersion { save: u32, current: u32 },
+    #[error("invalid save data: {0}")]
+    InvalidData(&'static str),
+    #[error("io error")]
+    Io(#[from] std::io::Error),
+    #[error("cbor error")]
+    Cbor(#[from] serde_cbor::error::Error),
+    #[error("json error")]
+    Json(#[from] serde_json::error::Error),
+}
+
+/// Convert the save data in the directory to the current version.
+/// The version in the metadata is updated after each step,
+/// so an interrupted migration is resumed from the failed step.
+pub fn migrate<P: AsRef<Path>>(save_dir: P, meta: &mut MetaData) -> Result<(), MigrationError> {
+    debug_assert_eq!(MIGRATIONS.len(), SAVE_VERSION as usize);
+    let save_dir = save_dir.as_ref();
+    let version = meta.version();
+
+    if version > SAVE_VERSION {
+        return Err(MigrationError::NewerVersion {
+            save: version,
+            current: SAVE_VERSION,
+        });
+    }
+
+    for v in version..SAVE_VERSION {
+        info!(
+            "Migrating save data \"{}\" from version {} to {}",
+            meta.save_name(),
+            v,
+            v + 1
+        );
+        MIGRATIONS[v as usize](save_dir)?;
+        meta.set_version(v + 1);
+        let mut file = BufWriter::new(File::create(save_dir.join("metadata"))?);
+        serde_json::to_writer_pretty(&mut file, meta)?;
+        file.flush()?;
+    }
+
+    Ok(())
+}
+
+/// Version 1 adds the world seed to GameData, and AI states to CharaAI
+fn v0_to_v1(save_dir: &Path) -> Result<(), MigrationError> {
+    update_gamedata(save_dir, |gd| {
+        let gd = as_map_mut(gd)?;
+        // world_seed is inserted before current_mapid
+        let mapid = gd
+            .remove(&int(6))
+            .ok_or(MigrationError::InvalidData("current map id"))?;
+        gd.insert(int(6), int(new_world_seed()));
+        gd.insert(int(7), mapid);
+        Ok(())
+    })?;
+
+    update_charas(save_dir, v1_chara_ai)
+}
+
+/// Version 2 changes CharaStatus from an enum to a struct with the status id
+fn v1_to_v2(save_dir: &Path) -> Result<(), MigrationError> {
+    update_charas(save_dir, |chara| {
+        if let Value::Array(status) = field_mut(as_map_mut(chara)?, 11)? {
+            for s in status.iter_mut() {
+                *s = v2_chara_status(std::mem::replace(s, Value::Null))?;
+            }
+        }
+        Ok(())
+    })
+}
+
+fn v2_chara_status(old: Value) -> Result<Value, MigrationError> {
+    let (variant, mut fields) = match old {
+        Value::Integer(variant) => (variant, BTreeMap::new()),
+        Value::Map(map) => match map.into_iter().next() {
+            Some((Value::Integer(variant), Value::Map(fields))) => (variant, fields),
+            _ => return Err(MigrationError::InvalidData("chara status")),
+        },
+        _ => return Err(MigrationError::InvalidData("chara status")),
+    };
+    let id = match variant {
+        0 => "hungry",
+        1 => "weak",
+        2 => "starving",
+        3 => "asleep",
+        4 => "poisoned",
+        5 => "creation",
+        _ => return Err(MigrationError::InvalidData("chara status")),
+    };
+    let turn_left = fields.remove(&int(0)).unwrap_or(Value::Null);
+    let data = if variant == 5 {
+        // CharaStatusData::Creation { recipe, ingredients }
+        let mut creation = BTreeMap::new();
+        creation.insert(int(0), fields.remove(&int(1)).unwrap_or(Value::Null));
+        creation.insert(int(1), fields.remove(&int(2)).unwrap_or(Value::Null));
+        let mut data = BTreeMap::new();
+        data.insert(int(0), Value::Map(creation));
+        Value::Map(data)
+    } else {
+        Value::Null
+    };
+
+    let mut status = BTreeMap::new();
+    status.insert(int(0), Value::Text(id.to_owned()));
+    status.insert(int(1), turn_left);
+    status.insert(int(2), int(1));
+    status.insert(int(3), data);
+    Ok(Value::Map(status))
+}
+
+fn v1_chara_ai(chara: &mut Value) -> Result<(), MigrationError> {
+    let ai = as_map_mut(field_mut(as_map_mut(chara)?, 8)?)?;
+    // state and default_state are AIState::Normal
+    ai.insert(int(1), int(0));
+    ai.insert(int(2), int(0));
+    // No home and waypoints
+    ai.insert(int(3), Value::Null);
+    ai.insert(int(4), Value::Array(Vec::new()));
+    Ok(())
+}
+
+fn new_world_seed() -> u64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+/// Apply f to all characters in the game data and map files
+fn update_charas<F>(save_dir: &Path, mut f: F) -> Result<(), MigrationError>
+where
+    F: FnMut(&mut Value) -> Result<(), MigrationError>,
+{
+    update_gamedata(save_dir, |gd| {
+        // CharaHolder has two chara tables
+        let chara_holder = as_map_mut(field_mut(as_map_mut(gd)?, 0)?)?;
+        for i in 0..2 {
+            for chara in as_map_mut(field_mut(chara_holder, i)?)?.values_mut() {
+                f(chara)?;
+            }
+        }
+        Ok(())
+    })?;
+
+    update_maps(save_dir, |map| {
+        if let Some(Value::Map(charas)) = as_map_mut(map)?.get_mut(&int(7)) {
+            for chara in charas.values_mut() {
+                f(chara)?;
+            }
+        }
+        Ok(())
+    })
+}
+
+fn update_gamedata<F>(save_dir: &Path, f: F) -> Result<(), MigrationError>
+where
+    F: FnOnce(&mut Value) -> Result<(), MigrationError>,
+{
+    let path = save_dir.join("gamedata");
+    let mut value: Value = serde_cbor::from_reader(BufReader::new(File::open(&path)?))?;
+    f(&mut value)?;
+    let mut file = BufWriter::new(File::create(&path)?);
+    to_writer_packed(&mut file, &value)?;
+    file.flush()?;
+    Ok(())
+}
+
+fn update_maps<F>(save_dir: &Path, mut f: F) -> Result<(), MigrationError>
+where
+    F: FnMut(&mut Value) -> Result<(), MigrationError>,
+{
+    let map_dir = save_dir.join("maps");
+    if !map_dir.exists() {
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(&map_dir)? {
+        let path = entry?.path();
+        if !path.is_file() {
+            continue;
+        }
+        let mut value: Value =
+            serde_cbor::from_reader(GzDecoder::new(BufReader::new(File::open(&path)?)))?;
+        f(&mut value)?;
+        let mut file = GzEncoder::new(BufWriter::new(File::create(&path)?), Compression::fast());
+        to_writer_packed(&mut file, &value)?;
+        file.finish()?.flush()?;
+    }
+    Ok(())
+}
+
+fn int(i: impl Into<i128>) -> Value {
+    Value::Integer(i.into())
+}
+
+fn as_map_mut(value: &mut Value) -> Result<&mut BTreeMap<Value, Value>, MigrationError> {
+    match value {
+        Value::Map(map) => Ok(map),
+        _ => Err(MigrationError::InvalidData("expected a struct")),
+    }
+}
+
+/// Get a field of a struct serialized in packed format
+fn field_mut(map: &mut BTreeMap<Value, Value>, i: u32) -> Result<&mut Value, MigrationError> {
+    map.get_mut(&int(i))
+        .ok_or(MigrationError::InvalidData("missing field"))
+}
@@ -53,6 +53,8 @@ pub struct CharaTemplateObject {
 pub struct DecoObject {
     pub id: String,
     pub img: Img,
+    /// Radius of the light from this deco. 0 if this is not a light source
+    pub light: u32,
 }
 
 #[derive(Serialize, Deserialize)]
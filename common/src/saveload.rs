@@ -1,64 +1,219 @@
-use crate::basic::SAVE_EXTENSION;
+use crate::basic::{SAVE_BACKUPS, SAVE_EXTENSION, SAVE_VERSION};
 use crate::gamedata::*;
-use crate::impl_filebox::MapLoadError;
+use crate::migration::MigrationError;
 use crate::utils::to_writer_packed;
-use serde_cbor::from_reader;
+use flate2::bufread::GzDecoder;
+use serde_cbor::{from_reader, Value};
 use std::fs::{self, create_dir_all, File};
 use std::io::{BufReader, BufWriter, Write};
 use std::path::{Path, PathBuf};
 
 #[cfg(feature = "global_state_obj")]
 impl GameData {
-    /// Save game data to the specified directory
+    /// Save game data to the specified directory.
+    /// Data are written to a temporary directory at first, and then it replaces the old save.
+    /// The old save is kept as a backup.
     pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn std::error::Error>> {
         if cfg!(debug_assertions) {
             print_save_data_size(self); // Debug code for save file size optimization
         }
 
         let save_dir = path.as_ref();
+        let tmp_dir = with_suffix(save_dir, ".tmp");
+        if tmp_dir.exists() {
+            fs::remove_dir_all(&tmp_dir)?;
+        }
 
         // Create directory
-        create_dir_all(&save_dir)?;
+        create_dir_all(&tmp_dir)?;
 
         // Write id table file
-        let mut file = BufWriter::new(File::create(save_dir.join("idtable"))?);
+        let mut file = BufWriter::new(File::create(tmp_dir.join("idtable"))?);
         writeln!(file, "{:016x}", *crate::gobj::OBJ_HOLDER_HASH)?;
         crate::gobj::get_objholder().write_table(&mut file)?;
+        file.into_inner()?.sync_all()?;
 
         // Write metadata file
-        let mut file = BufWriter::new(File::create(save_dir.join("metadata"))?);
+        let mut file = BufWriter::new(File::create(tmp_dir.join("metadata"))?);
         serde_json::to_writer_pretty(&mut file, &self.meta)?;
+        file.into_inner()?.sync_all()?;
 
         // Write GameData
-        let mut file = BufWriter::new(File::create(save_dir.join("gamedata"))?);
+        let mut file = BufWriter::new(File::create(tmp_dir.join("gamedata"))?);
         to_writer_packed(&mut file, &self)?;
+        file.into_inner()?.sync_all()?;
 
-        // Write maps
+        // Write maps. Maps not loaded are not changed, so they are copied from the old save.
         let map_dir = save_dir.join("maps");
-        create_dir_all(&map_dir)?;
+        let tmp_map_dir = tmp_dir.join("maps");
+        create_dir_all(&tmp_map_dir)?;
 
-        let mut errors: Vec<MapLoadError> = Vec::new();
-        self.region
-            .visit_all_maps(|_mid, map| match BoxedMap::write(map, &map_dir) {
-                Ok(_) => (),
-                Err(e) => errors.push(e),
-            });
+        let mut errors: Vec<Box<dyn std::error::Error>> = Vec::new();
+        self.region.visit_all_maps(|_mid, map| {
+            let result = if map.is_loaded() {
+                BoxedMap::write_force(map, &tmp_map_dir).map_err(|e| e.into())
+            } else {
+                fs::copy(map.path(&map_dir), map.path(&tmp_map_dir))
+                    .map(|_| ())
+                    .map_err(|e| e.into())
+            };
+            if let Err(e) = result {
+                errors.push(e);
+            }
+        });
 
         if !errors.is_empty() {
-            return Err(errors.into_iter().next().unwrap().into());
+            return Err(errors.into_iter().next().unwrap());
+        }
+
+        // Rotate backups and swap the new save in
+        if save_dir.exists() {
+            let oldest = backup_dir(save_dir, SAVE_BACKUPS);
+            if oldest.exists() {
+                fs::remove_dir_all(&oldest)?;
+            }
+            for n in (1..SAVE_BACKUPS).rev() {
+                let backup = backup_dir(save_dir, n);
+                if backup.exists() {
+                    fs::rename(&backup, backup_dir(save_dir, n + 1))?;
+                }
+            }
+            if SAVE_BACKUPS > 0 {
+                fs::rename(save_dir, backup_dir(save_dir, 1))?;
+            } else {
+                fs::remove_dir_all(save_dir)?;
+            }
         }
+        fs::rename(&tmp_dir, save_dir)?;
 
         Ok(())
     }
 
-    /// Load game data from specified directory
+    /// Load game data from specified directory.
+    /// Save data created by older versions are converted in place.
+    /// If the save data is broken, it is restored from the newest valid backup.
     pub fn load<P: AsRef<Path>>(path: P) -> Result<GameData, Box<dyn std::error::Error>> {
         let save_dir = path.as_ref();
 
-        // Read metadata file
-        let mut file = BufReader::new(File::open(save_dir.join("metadata"))?);
-        let meta: MetaData = serde_json::from_reader(&mut file)?;
+        // If saving is stopped between moving the old save to the backup and moving
+        // the new save in, only the temporary directory has the newest data
+        let tmp_dir = with_suffix(save_dir, ".tmp");
+        if !save_dir.exists() && tmp_dir.exists() {
+            match verify_save_dir(&tmp_dir) {
+                Ok(_) => {
+                    warn!(
+                        "Save data {} is missing. Use the new save in {}",
+                        save_dir.to_string_lossy(),
+                        tmp_dir.to_string_lossy()
+                    );
+                    fs::rename(&tmp_dir, save_dir)?;
+                }
+                Err(e) => warn!(
+                    "Temporary save data {} is broken: {}",
+                    tmp_dir.to_string_lossy(),
+                    e
+                ),
+            }
+        }
+
+        let e = match verify_save_dir(save_dir).and_then(|_| load_migrated(save_dir)) {
+            Ok(gamedata) => return Ok(gamedata),
+            Err(e) => e,
+        };
+        warn!(
+            "Failed to load save data {}: {}",
+            save_dir.to_string_lossy(),
+            e
+        );
+        // Saves of newer versions are not broken, so backups are not used
+        if is_newer_version(&*e) {
+            return Err(e);
+        }
+
+        let backups: Vec<PathBuf> = (1..=SAVE_BACKUPS)
+            .map(|n| backup_dir(save_dir, n))
+            .filter(|backup| backup.exists())
+            .filter(|backup| match verify_save_dir(backup) {
+                Ok(_) => true,
+                Err(e) => {
+                    warn!("Backup {} is broken: {}", backup.to_string_lossy(), e);
+                    false
+                }
+            })
+            .collect();
+        if backups.is_empty() {
+            return Err(e);
+        }
+
+        // The broken save is kept for investigation
+        let broken = unused_broken_dir(save_dir);
+        if save_dir.exists() {
+            fs::rename(save_dir, &broken)?;
+        }
+        for backup in &backups {
+            match restore_backup(save_dir, backup).and_then(|_| load_migrated(save_dir)) {
+                Ok(gamedata) => {
+                    warn!(
+                        "Restored save data from {}. The broken save is moved to {}",
+                        backup.to_string_lossy(),
+                        broken.to_string_lossy()
+                    );
+                    return Ok(gamedata);
+                }
+                Err(e) => warn!(
+                    "Failed to restore save data from {}: {}",
+                    backup.to_string_lossy(),
+                    e
+                ),
+            }
+        }
+
+        // Put the broken save back if no backup can be loaded
+        if save_dir.exists() {
+            fs::remove_dir_all(save_dir)?;
+        }
+        if broken.exists() {
+            fs::rename(&broken, save_dir)?;
+        }
+        Err(e)
+    }
+
+    /// Load game data from specified directory without modifying any files.
+    /// Backups are not used. Save data created by older versions are converted
+    /// in a temporary copy, and all maps are loaded from it.
+    pub fn load_dir<P: AsRef<Path>>(path: P) -> Result<GameData, Box<dyn std::error::Error>> {
+        let save_dir = path.as_ref();
+        let mut meta = load_metadata(save_dir)?;
+        if meta.version() > SAVE_VERSION {
+            return Err(MigrationError::NewerVersion {
+                save: meta.version(),
+                current: SAVE_VERSION,
+            }
+            .into());
+        } else if meta.version() == SAVE_VERSION {
+            return GameData::load_files(save_dir, meta, false);
+        }
+
+        let tmp_dir =
+            std::env::temp_dir().join(format!("rusted-ruins-load-{}", std::process::id()));
+        if tmp_dir.exists() {
+            fs::remove_dir_all(&tmp_dir)?;
+        }
+        copy_dir(save_dir, &tmp_dir)?;
+        let result = crate::migration::migrate(&tmp_dir, &mut meta)
+            .map_err(|e| e.into())
+            .and_then(|_| GameData::load_files(&tmp_dir, meta, true));
+        fs::remove_dir_all(&tmp_dir)?;
+        result
+    }
 
+    /// Load files in the save directory of the current version.
+    /// If preload_all is false, only the current map is loaded.
+    fn load_files(
+        save_dir: &Path,
+        meta: MetaData,
+        preload_all: bool,
+    ) -> Result<GameData, Box<dyn std::error::Error>> {
         // Read index conversion table
         let mut file = BufReader::new(File::open(save_dir.join("idtable"))?);
         let idx_conv_table =
@@ -75,7 +230,7 @@ impl GameData {
         gamedata.meta = meta;
 
         let map_dir = save_dir.join("maps");
-        if is_table_changed {
+        if is_table_changed || preload_all {
             // Preload is needed if id table is changed
             let mut mid_vec = Vec::new();
             gamedata.region.visit_all_maps(|mid, _map| {
@@ -131,6 +286,151 @@ impl GameData {
     }
 }
 
+/// Convert the save data in the directory to the current version, and load it
+#[cfg(feature = "global_state_obj")]
+fn load_migrated(save_dir: &Path) -> Result<GameData, Box<dyn std::error::Error>> {
+    let mut meta = load_metadata(save_dir)?;
+    crate::migration::migrate(save_dir, &mut meta)?;
+    GameData::load_dir(save_dir)
+}
+
+/// Returns true if the error is caused by a save of a newer version
+pub fn is_newer_version(e: &(dyn std::error::Error + 'static)) -> bool {
+    matches!(
+        e.downcast_ref::<MigrationError>(),
+        Some(MigrationError::NewerVersion { .. })
+    )
+}
+
+/// Read only the metadata file in the save directory
+pub fn load_metadata<P: AsRef<Path>>(save_dir: P) -> Result<MetaData, Box<dyn std::error::Error>> {
+    let file = BufReader::new(File::open(save_dir.as_ref().join("metadata"))?);
+    Ok(serde_json::from_reader(file)?)
+}
+
+/// Delete the save directory and its backups
+pub fn delete_save<P: AsRef<Path>>(save_dir: P) -> Result<(), std::io::Error> {
+    let save_dir = save_dir.as_ref();
+    let mut dirs = vec![save_dir.to_owned(), with_suffix(save_dir, ".tmp")];
+    dirs.extend((1..=SAVE_BACKUPS).map(|n| backup_dir(save_dir, n)));
+    dirs.extend(broken_dirs(save_dir)?);
+
+    for dir in &dirs {
+        if dir.exists() {
+            fs::remove_dir_all(dir)?;
+        }
+    }
+    Ok(())
+}
+
+/// Copy the save directory as a new save with the given name.
+/// Returns the path of the new save directory.
+pub fn duplicate_save<P: AsRef<Path>>(
+    save_dir: P,
+    new_name: &str,
+) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let save_dir = save_dir.as_ref();
+    let new_dir = save_dir.with_file_name(format!("{}.{}", new_name, SAVE_EXTENSION));
+    if new_dir.exists() {
+        return Err(format!("{} already exists", new_dir.to_string_lossy()).into());
+    }
+
+    let mut meta = load_metadata(save_dir)?;
+    meta.set_save_name(new_name);
+    copy_dir(save_dir, &new_dir)?;
+
+    let mut file = BufWriter::new(File::create(new_dir.join("metadata"))?);
+    serde_json::to_writer_pretty(&mut file, &meta)?;
+    file.flush()?;
+    Ok(new_dir)
+}
+
+/// Check all files in the save directory can be decoded.
+/// Only the data format is checked, so this is applicable to old versions.
+fn verify_save_dir(save_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let _: MetaData =
+        serde_json::from_reader(BufReader::new(File::open(save_dir.join("metadata"))?))?;
+    let _: Value = from_reader(BufReader::new(File::open(save_dir.join("gamedata"))?))?;
+
+    let map_dir = save_dir.join("maps");
+    if map_dir.exists() {
+        for entry in fs::read_dir(&map_dir)? {
+            let path = entry?.path();
+            if path.is_file() {
+                let _: Value = from_reader(GzDecoder::new(BufReader::new(File::open(&path)?)))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Copy the backup to the save directory.
+/// The broken save has been moved already, so the save directory
+/// is only a copy of another backup restored by the previous try.
+fn restore_backup(save_dir: &Path, backup: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    if save_dir.exists() {
+        fs::remove_dir_all(save_dir)?;
+    }
+    copy_dir(backup, save_dir)?;
+    Ok(())
+}
+
+/// Path to move a broken save to, such as "*.broken.1"
+fn unused_broken_dir(save_dir: &Path) -> PathBuf {
+    (1..)
+        .map(|n| with_suffix(save_dir, &format!(".broken.{}", n)))
+        .find(|dir| !dir.exists())
+        .unwrap()
+}
+
+/// Broken saves moved by restoring backups
+fn broken_dirs(save_dir: &Path) -> Result<Vec<PathBuf>, std::io::Error> {
+    let prefix = match save_dir.file_name() {
+        Some(name) => format!("{}.broken.", name.to_string_lossy()),
+        None => return Ok(Vec::new()),
+    };
+    let parent = match save_dir.parent() {
+        Some(parent) if parent.exists() => parent,
+        _ => return Ok(Vec::new()),
+    };
+    let mut dirs = Vec::new();
+    for entry in fs::read_dir(parent)? {
+        let path = entry?.path();
+        if path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with(&prefix))
+        {
+            dirs.push(path);
+        }
+    }
+    Ok(dirs)
+}
+
+fn copy_dir(src: &Path, dest: &Path) -> Result<(), std::io::Error> {
+    create_dir_all(dest)?;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let dest = dest.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &dest)?;
+        } else {
+            fs::copy(entry.path(), dest)?;
+        }
+    }
+    Ok(())
+}
+
+/// Path of the n-th newest backup of the save directory
+pub fn backup_dir<P: AsRef<Path>>(save_dir: P, n: u32) -> PathBuf {
+    with_suffix(save_dir.as_ref(), &format!(".bak{}", n))
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut s = path.as_os_str().to_owned();
+    s.push(suffix);
+    PathBuf::from(s)
+}
+
 /// Print save data size
 #[cfg(debug_assertions)]
 fn print_save_data_size(gd: &GameData) {
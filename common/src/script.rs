@@ -1,5 +1,6 @@
-use crate::gamedata::Time;
+use crate::gamedata::{Relationship, SkillKind, Time};
 use crate::hashmap::HashMap;
+use geom::Vec2d;
 use std::fmt;
 use std::ops::Index;
 
@@ -20,6 +21,22 @@ pub enum Instruction {
     RemoveItem(String),
     /// Special Instruction
     Special(SpecialInstruction),
+    /// Player receive items (item id, the number of items)
+    GiveItem(String, Expr),
+    /// Change the relationship of the talking character to player
+    SetRelationship(Relationship),
+    /// Start a quest managed by scripts
+    StartQuest(String),
+    /// Complete a quest managed by scripts
+    CompleteQuest(String),
+    /// Teleport player to the position. If floor is given, move to the floor of the current site
+    Teleport(Option<u32>, Vec2d),
+    /// Set variable of the character executing the script
+    CSet(String, Expr),
+    /// Set variable of the current site
+    SSet(String, Expr),
+    /// Set variable which is valid during this script execution
+    LSet(String, Expr),
 }
 
 /// Special Instructions
@@ -35,6 +52,8 @@ pub enum SpecialInstruction {
     QuestWindow,
     /// Special instruction to receive quest rewards
     ReceiveQuestRewards,
+    /// Special instruction to open skill training window
+    Train,
 }
 
 impl std::str::FromStr for SpecialInstruction {
@@ -48,6 +67,7 @@ impl std::str::FromStr for SpecialInstruction {
             "get_dungeon_location" => Ok(GetDungeonLocation),
             "quest_window" => Ok(QuestWindow),
             "receive_quest_rewards" => Ok(ReceiveQuestRewards),
+            "train" => Ok(Train),
             _ => Err(()),
         }
     }
@@ -64,6 +84,26 @@ pub enum Expr {
     CurrentTime,
     DurationHour(Box<Expr>, Box<Expr>),
     HasItem(String),
+    PlayerLevel,
+    SkillLevel(SkillKind),
+    Money,
+    IsQuestActive(String),
+    /// The quest managed by scripts is completed or not
+    IsQuestCompleted(String),
+    /// The relationship of the talking character to player is the given one or not
+    Relationship(Relationship),
+    /// Floor number of the current map. -1 on region maps
+    CurrentFloor,
+    /// Player is in the town of the given id or not
+    InTown(String),
+    /// The number of the items player has
+    ItemCount(String),
+    /// Reference to variable of the character executing the script
+    CVar(String),
+    /// Reference to variable of the current site
+    SVar(String),
+    /// Reference to variable which is valid during this script execution
+    LVar(String),
 }
 
 /// Value is the result of evaluation of Expr.
@@ -1,5 +1,7 @@
+use crate::gamedata::event::EventKind;
 use crate::gamedata::shop::ShopKind;
 use crate::gamedata::site::SiteKind;
+use crate::gamedata::skill::SkillKind;
 use geom::Vec2d;
 
 /// Hold data for site generation
@@ -10,6 +12,8 @@ pub struct SiteGenObject {
     pub map_template_id: Vec<String>,
     pub unique_citizens: Vec<UniqueCitizenGenData>,
     pub shops: Vec<ShopGenData>,
+    pub trainers: Vec<TrainerGenData>,
+    pub triggers: Vec<EventTriggerGenData>,
 }
 
 /// Data to generate a unique citizen
@@ -22,6 +26,12 @@ pub struct UniqueCitizenGenData {
     pub n: u32,
     pub chara_template_id: String,
     pub talk_script_id: Option<String>,
+    /// Positions this citizen patrols between
+    #[serde(default)]
+    pub waypoints: Vec<Vec2d>,
+    /// If given, this citizen guards the area within this range from pos
+    #[serde(default)]
+    pub guard_range: Option<u32>,
 }
 
 /// Data to generate a shop on the site
@@ -31,3 +41,25 @@ pub struct ShopGenData {
     pub chara_n: u32,
     pub kind: ShopKind,
 }
+
+/// Data to make a citizen on the site a skill trainer
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct TrainerGenData {
+    /// Trainer's id (n)
+    pub chara_n: u32,
+    /// Skills this trainer can raise
+    pub skills: Vec<SkillKind>,
+}
+
+/// Data to set an event trigger on the site
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct EventTriggerGenData {
+    /// If given, the trigger is set to the map of this floor. Otherwise set to the site.
+    pub floor: Option<u32>,
+    pub event: EventKind,
+    pub script_id: String,
+    /// Section to start. "start" if not given.
+    pub section: Option<String>,
+    #[serde(default)]
+    pub once: bool,
+}
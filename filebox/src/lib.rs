@@ -72,6 +72,11 @@ impl<T: WithId> FileBox<T> {
         self.id
     }
 
+    /// Returns true if the inner data is on memory
+    pub fn is_loaded(&self) -> bool {
+        self.inner.is_some()
+    }
+
     pub fn write_force<P: AsRef<Path>>(s: &Self, p: P) -> Result<(), T::Error> {
         if let Some(a) = &s.inner {
             let mut file = GzEncoder::new(
@@ -0,0 +1,6 @@
+//! Used instead of the audio crate if the "audio" feature is disabled.
+//! All sounds are ignored.
+
+pub fn play_sound(_name: &str) {}
+
+pub fn play_ambience(_name: Option<&str>) {}
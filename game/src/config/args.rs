@@ -0,0 +1,64 @@
+use crate::config::Config;
+use clap::{App, Arg, ArgMatches};
+
+fn get_matches() -> ArgMatches<'static> {
+    App::new("Rusted Ruins")
+        .version(env!("CARGO_PKG_VERSION"))
+        .arg(
+            Arg::with_name("fix-rand")
+                .long("fix-rand")
+                .help("Fixes the state of RNG when game start"),
+        )
+        .arg(
+            Arg::with_name("headless")
+                .long("headless")
+                .value_name("TURNS")
+                .min_values(0)
+                .help(
+                    "Runs the given number of turns without window and audio. \
+                     With --replay, processes the replay and checks its end state",
+                ),
+        )
+        .arg(
+            Arg::with_name("record")
+                .long("record")
+                .value_name("REPLAY")
+                .help("Records the played session to the replay directory"),
+        )
+        .arg(
+            Arg::with_name("replay")
+                .long("replay")
+                .value_name("REPLAY")
+                .help("Starts the game from the replay and processes its commands"),
+        )
+        .get_matches()
+}
+
+pub fn modify_config_by_args(mut config: Config) -> Config {
+    let matches = get_matches();
+
+    if matches.is_present("fix-rand") {
+        config.fix_rand = true;
+    }
+
+    if matches.is_present("headless") {
+        config.headless = true;
+    }
+
+    if let Some(turns) = matches.value_of("headless") {
+        match turns.parse() {
+            Ok(turns) => config.headless_turns = Some(turns),
+            Err(e) => warn!("Invalid number of turns for headless mode : {}", e),
+        }
+    }
+
+    if let Some(path) = matches.value_of("record") {
+        config.record = Some(path.into());
+    }
+
+    if let Some(path) = matches.value_of("replay") {
+        config.replay = Some(path.into());
+    }
+
+    config
+}
@@ -0,0 +1,154 @@
+mod args;
+pub mod input;
+
+use crate::util::read_file_as_string;
+use common::basic;
+use serde::de::DeserializeOwned;
+use std::env;
+use std::path::PathBuf;
+use std::process::exit;
+
+/// Load a config file in the config directory. Exits if the file cannot be loaded.
+pub fn load_config_file<T: DeserializeOwned>(path: &str) -> T {
+    let path = cfg_path(path);
+    info!("Loading config file : \"{}\"", path.to_string_lossy());
+    let s = match read_file_as_string(&path) {
+        Ok(s) => s,
+        Err(e) => {
+            error!(
+                "Cannot load config file \"{}\"\n{}",
+                path.to_string_lossy(),
+                e
+            );
+            exit(1);
+        }
+    };
+
+    match toml::de::from_str(&s) {
+        Ok(config) => config,
+        Err(e) => {
+            error!(
+                "Cannot load config file \"{}\"\n{}",
+                path.to_string_lossy(),
+                e
+            );
+            exit(1);
+        }
+    }
+}
+
+/// Initialize lazy static
+pub fn init() {
+    use lazy_static::initialize;
+    initialize(&ASSETS_DIR);
+    initialize(&USER_DIR);
+    initialize(&CONFIG);
+    initialize(&INPUT_CFG);
+    initialize(&PAK_DIRS);
+}
+
+lazy_static! {
+    pub static ref ASSETS_DIR: PathBuf = get_assets_dir().expect("Cannot get data directory path");
+    pub static ref USER_DIR: PathBuf = get_user_dir();
+    pub static ref ADDON_DIR: Option<PathBuf> = get_addon_dir();
+    pub static ref CONFIG: Config = {
+        let config: Config = load_config_file("config.toml");
+        // Arguments are given to the test harness in tests
+        if cfg!(test) {
+            config
+        } else {
+            args::modify_config_by_args(config)
+        }
+    };
+    pub static ref INPUT_CFG: input::InputConfig = load_config_file("input.toml");
+    pub static ref PAK_DIRS: Vec<PathBuf> = {
+        let mut v = Vec::new();
+        v.push(abs_path("paks"));
+        v
+    };
+}
+
+/// Get application directory
+fn get_assets_dir() -> Option<PathBuf> {
+    if let Some(e) = env::var_os("RUSTED_RUINS_ASSETS_DIR") {
+        return Some(PathBuf::from(e));
+    }
+
+    if let Ok(mut exe_file) = env::current_exe() {
+        exe_file.pop();
+        exe_file.push("assets");
+        return Some(exe_file);
+    }
+
+    if let Ok(mut cdir) = env::current_dir() {
+        cdir.push("assets");
+        return Some(cdir);
+    }
+    None
+}
+
+fn get_user_dir() -> PathBuf {
+    let mut path = dirs::data_dir().expect("Failed to get user data diractory");
+    path.push(basic::APP_DIR_NAME);
+    path
+}
+
+/// Get addon directory
+fn get_addon_dir() -> Option<PathBuf> {
+    if let Some(e) = env::var_os("RUSTED_RUINS_ADDON_DIR") {
+        return Some(PathBuf::from(e));
+    }
+    None
+}
+
+/// Get application and each addon's directories
+/// They will be the root path for searching pak or text, and other data files.
+pub fn get_data_dirs() -> Vec<PathBuf> {
+    let mut v = Vec::new();
+    v.push(ASSETS_DIR.clone());
+
+    if ADDON_DIR.is_some() {
+        v.push(ADDON_DIR.clone().unwrap());
+    }
+
+    v
+}
+
+/// Create absolute path from relative path which root is application directory
+pub fn abs_path(s: &str) -> PathBuf {
+    let mut path = ASSETS_DIR.clone();
+    path.push(s);
+    path
+}
+
+/// Create absolute path from config directory
+pub fn cfg_path(s: &str) -> PathBuf {
+    let mut path = ASSETS_DIR.clone();
+    path.push(basic::CFG_FILES_DIR);
+    path.push(s);
+    path
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Config {
+    pub lang: String,
+    pub second_lang: String,
+    pub screen_config: String,
+    pub hardware_acceleration: bool,
+    #[serde(default)]
+    pub double_scale_mode: bool,
+    #[serde(default)]
+    pub fix_rand: bool,
+    /// If true, runs the game without window and audio
+    #[serde(default)]
+    pub headless: bool,
+    /// The number of turns to run in headless mode
+    #[serde(default)]
+    pub headless_turns: Option<u64>,
+    /// If set, the played session is recorded to this replay
+    #[serde(default)]
+    pub record: Option<PathBuf>,
+    /// If set, the game starts from this replay and its commands are processed
+    #[serde(default)]
+    pub replay: Option<PathBuf>,
+}
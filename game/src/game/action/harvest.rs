@@ -1,3 +1,4 @@
+use crate::game::item::ItemEx;
 use crate::game::InfoGetter;
 use common::gamedata::*;
 use common::gobj;
@@ -54,12 +55,24 @@ pub fn harvest_by_tool(gd: &mut GameData, chara_id: CharaId, pos: Vec2d) {
             HarvestType::Chop => {
                 if tool_obj.tool_effect == ToolEffect::Chop {
                     game_log_i!("harvest-chop"; chara=gd.chara.get(chara_id), item=&target_item, n=n_yield);
-                    audio::play_sound("chop-tree");
+                    crate::audio::play_sound("chop-tree");
                     gd.add_item_on_tile(gd.player_pos(), target_item.clone(), n_yield);
                     gd.remove_item(*il, 1);
                     return;
                 }
             }
+            HarvestType::Crop => {
+                // Crops can be harvested by any tool after they have grown
+                let item = gd.get_item(*il).0;
+                if item.growth() < harvest.growing_hours * 100 {
+                    game_log_i!("harvest-crop-not-grown"; item=item);
+                    return;
+                }
+                game_log_i!("harvest-crop"; chara=gd.chara.get(chara_id), item=&target_item, n=n_yield);
+                gd.add_item_on_tile(gd.player_pos(), target_item.clone(), n_yield);
+                gd.remove_item(*il, 1);
+                return;
+            }
             _ => (),
         }
     }
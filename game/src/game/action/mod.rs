@@ -1,6 +1,8 @@
 //! Process characters action
 
 pub mod harvest;
+pub mod spell;
+pub mod throw;
 pub mod use_item;
 
 use super::combat;
@@ -8,13 +10,14 @@ use super::extrait::*;
 use super::Game;
 use common::gamedata::*;
 use geom::*;
-use rng::dice;
+use rng::{dice, get_rng, Rng, SliceRandom};
 use rules::RULES;
 
 pub fn try_move(game: &mut Game, chara_id: CharaId, dir: Direction) -> bool {
     if dir.as_vec() == (0, 0) {
         return true;
     } // Move to current tile always success
+    let dir = confused_dir(game.gd.chara.get(chara_id), dir);
     let dest_tile = game.gd.get_current_map().chara_pos(chara_id).unwrap() + dir.as_vec();
 
     if !game
@@ -30,6 +33,7 @@ pub fn try_move(game: &mut Game, chara_id: CharaId, dir: Direction) -> bool {
         game.gd.get_current_map_mut().move_chara(chara_id, dir);
         if chara_id == CharaId::Player {
             game.anim_queue.push_player_move(dir);
+            super::event::trigger_event(game, EventKind::StepOn(dest_tile));
         }
     } else {
         let rel = {
@@ -45,6 +49,7 @@ pub fn try_move(game: &mut Game, chara_id: CharaId, dir: Direction) -> bool {
                 }
                 if chara_id == CharaId::Player {
                     game.anim_queue.push_player_move(dir);
+                    super::event::trigger_event(game, EventKind::StepOn(dest_tile));
                 }
             }
             Relationship::HOSTILE => {
@@ -57,7 +62,7 @@ pub fn try_move(game: &mut Game, chara_id: CharaId, dir: Direction) -> bool {
 
 /// Shot target
 pub fn shot_target(game: &mut Game, cid: CharaId, target: CharaId) -> bool {
-    combat::shot_target(game, cid, target)
+    combat::shot_target(game, cid, target).is_some()
 }
 
 /// Drink one item
@@ -85,7 +90,7 @@ pub fn eat_item(gd: &mut GameData, il: ItemLocation, cid: CharaId) {
     apply_medical_effect(chara, item_obj.medical_effect, eff);
 }
 
-pub fn release_item(game: &mut Game, il: ItemLocation, cid: CharaId) {
+pub fn release_item(game: &mut Game, il: ItemLocation, cid: CharaId, target: Option<Vec2d>) {
     let mut item = game.gd.remove_item_and_get(il, 1);
     let item_obj = item.obj();
     let item_dice: f64 = dice(item_obj.dice_n, item_obj.dice_x).into();
@@ -101,7 +106,14 @@ pub fn release_item(game: &mut Game, il: ItemLocation, cid: CharaId) {
                 .into();
             let power =
                 (skill_level / 10.0 + 1.0) * item_dice * RULES.magic.magic_device_base_power;
-            super::magic::do_magic(game, cid, item_obj.magical_effect, power);
+            super::magic::do_magic(
+                game,
+                cid,
+                target,
+                &item_obj.magical_effect,
+                item_obj.element,
+                power,
+            );
             *item.charge_mut().unwrap() = n - 1;
         }
         _ => (),
@@ -110,6 +122,21 @@ pub fn release_item(game: &mut Game, il: ItemLocation, cid: CharaId) {
     game.gd.get_item_list_mut(il.0).append(item, 1);
 }
 
+/// Change the moving direction randomly by status
+fn confused_dir(chara: &Chara, dir: Direction) -> Direction {
+    let p = chara
+        .status
+        .iter()
+        .filter_map(|s| s.effect())
+        .map(|e| e.random_move)
+        .fold(0.0f32, f32::max);
+    if p > 0.0 && get_rng().gen_bool(p.min(1.0).into()) {
+        *Direction::EIGHT_DIRS.choose(&mut get_rng()).unwrap()
+    } else {
+        dir
+    }
+}
+
 fn apply_medical_effect(chara: &mut Chara, me: MedicalEffect, eff: i32) {
     match me {
         MedicalEffect::None => (),
@@ -119,14 +146,10 @@ fn apply_medical_effect(chara: &mut Chara, me: MedicalEffect, eff: i32) {
             game_log!("heal-hp"; chara=chara, value=eff);
         }
         MedicalEffect::Sleep => {
-            chara.add_status(CharaStatus::Asleep {
-                turn_left: eff as u16,
-            });
-            game_log!("fall-asleep"; chara=chara);
+            chara.add_status(CharaStatus::new("asleep", Some(eff as u16)));
         }
         MedicalEffect::Poison => {
-            chara.add_status(CharaStatus::Poisoned);
-            game_log!("poisoned"; chara=chara);
+            chara.add_status(CharaStatus::new("poisoned", None));
         }
     }
 }
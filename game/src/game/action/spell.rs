@@ -0,0 +1,56 @@
+//! Learning and casting spells
+
+use crate::game::extrait::*;
+use crate::game::Game;
+use crate::text::spell_txt;
+use common::gamedata::*;
+use geom::Vec2d;
+use rules::RULES;
+
+/// Cast a learned spell. Returns false if the spell cannot be cast.
+pub fn cast_spell(game: &mut Game, cid: CharaId, spell_id: &str, target: Option<Vec2d>) -> bool {
+    let spell = if let Some(spell) = RULES.spell.get(spell_id) {
+        spell
+    } else {
+        return false;
+    };
+    let chara = game.gd.chara.get_mut(cid);
+    if !chara.spells.iter().any(|s| s == spell_id) {
+        return false;
+    }
+
+    let needs_target = !matches!(
+        spell.effect,
+        MagicalEffect::None | MagicalEffect::SelfBuff { .. }
+    );
+    if needs_target && target.is_none() {
+        game_log_i!("no-target"; chara=chara);
+        return false;
+    }
+    if chara.sp < spell.cost {
+        game_log_i!("not-enough-sp"; chara=chara);
+        return false;
+    }
+
+    let name = spell_txt(spell_id);
+    game_log!("cast-spell"; chara=chara, spell=name);
+    chara.sub_sp(spell.cost, cid);
+    let skill_level: f64 = chara.skills.get(SkillKind::Magic(spell.school)).into();
+    chara.add_cast_exp(spell.school);
+
+    let power = (skill_level / 10.0 + 1.0) * spell.power * RULES.magic.spell_base_power;
+    crate::game::magic::do_magic(game, cid, target, &spell.effect, spell.element, power);
+    true
+}
+
+/// Learn a spell. Returns false if the character already knows it.
+pub fn learn_spell(chara: &mut Chara, spell_id: &str) -> bool {
+    let name = spell_txt(spell_id);
+    if chara.spells.iter().any(|s| s == spell_id) {
+        game_log_i!("already-learned-spell"; chara=chara, spell=name);
+        return false;
+    }
+    game_log_i!("learn-spell"; chara=chara, spell=name);
+    chara.spells.push(spell_id.to_owned());
+    true
+}
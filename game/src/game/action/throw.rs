@@ -0,0 +1,91 @@
+//! Throwing items
+
+use super::apply_medical_effect;
+use crate::game::combat;
+use crate::game::{Game, InfoGetter};
+use common::gamedata::*;
+use geom::*;
+use rules::RULES;
+
+/// Throw one item to the target tile.
+/// The item flies until it hits a character or a wall.
+pub fn throw_item(game: &mut Game, il: ItemLocation, cid: CharaId, target: Vec2d) {
+    let item = game.gd.remove_item_and_get(il, 1);
+    let start = game.gd.chara_pos(cid).unwrap();
+    let (landing, target_id) = throw_path(game.gd.get_current_map(), start, target);
+
+    let chara = game.gd.chara.get(cid);
+    game_log!("throw-item"; chara=chara, item=item);
+    game.anim_queue.push_shot(start, landing);
+
+    let hit = if let Some(target_id) = target_id {
+        combat::throw_item_to(game, &item, cid, target_id).hit
+    } else {
+        false
+    };
+
+    let item_obj = item.obj();
+    if item_obj.kind == ItemKind::Potion {
+        // Potions are broken, and affect the character who is hit
+        game_log!("potion-shatter"; item=item);
+        if let (true, Some(target_id)) = (hit, target_id) {
+            let target = game.gd.chara.get_mut(target_id);
+            game_log!("potion-hit"; chara=target, item=item);
+            apply_medical_effect(target, item_obj.medical_effect, item_obj.eff.into());
+        }
+    } else {
+        let ill = ItemListLocation::OnMap {
+            mid: game.gd.get_current_mapid(),
+            pos: landing,
+        };
+        game.gd.get_item_list_mut(ill).append(item, 1);
+    }
+}
+
+/// Returns the tile where the thrown item lands, and the character on it
+fn throw_path(map: &Map, start: Vec2d, target: Vec2d) -> (Vec2d, Option<CharaId>) {
+    let mut landing = start;
+
+    for p in LineIter::new(start, target)
+        .skip(1)
+        .take(RULES.combat.throw_range as usize)
+    {
+        if !map.is_inside(p) || !map.tile[p].wall.is_empty() {
+            break;
+        }
+        landing = p;
+        if let Some(cid) = map.get_chara(p) {
+            return (landing, Some(cid));
+        }
+    }
+
+    (landing, None)
+}
+
+#[test]
+fn throw_path_test() {
+    use crate::game::testutil;
+
+    let _lock = testutil::init();
+    let mut gd = testutil::gen_gd();
+    testutil::set_walls(&mut gd, &[Vec2d(5, 1)]);
+    let npc = testutil::add_npc(&mut gd, Vec2d(3, 3), Relationship::HOSTILE);
+    let map = gd.get_current_map();
+    let start = Vec2d(1, 1);
+
+    // Empty tile
+    assert_eq!(throw_path(map, start, Vec2d(3, 1)), (Vec2d(3, 1), None));
+    // Stopped by the wall
+    assert_eq!(throw_path(map, start, Vec2d(10, 1)), (Vec2d(4, 1), None));
+    // Hits the character on the path
+    assert_eq!(
+        throw_path(map, start, Vec2d(6, 6)),
+        (Vec2d(3, 3), Some(npc))
+    );
+    // Limited by the range
+    let range = RULES.combat.throw_range as i32;
+    assert_eq!(
+        throw_path(map, start, Vec2d(1, 11)),
+        (Vec2d(1, 1 + range.min(10)), None)
+    );
+}
@@ -6,7 +6,7 @@ pub fn use_item(gd: &mut GameData, il: ItemLocation, cid: CharaId) {
     let item = gd.get_item(il);
     let item_obj = gobj::get_obj(item.0.idx);
 
-    match item_obj.use_effect {
+    match &item_obj.use_effect {
         UseEffect::None => panic!("use invalid item"),
         UseEffect::Deed => {
             assert_eq!(cid, CharaId::Player);
@@ -41,5 +41,17 @@ pub fn use_item(gd: &mut GameData, il: ItemLocation, cid: CharaId) {
             game_log_i!("use_item-deed-succeed");
             gd.remove_item(il, 1);
         }
+        UseEffect::LearnSpell { spell } => {
+            let chara = gd.chara.get_mut(cid);
+            if super::spell::learn_spell(chara, spell) {
+                gd.remove_item(il, 1);
+            }
+        }
+        UseEffect::SkillBook { skill } => {
+            let chara = gd.chara.get_mut(cid);
+            if crate::game::training::read_skill_book(chara, *skill) {
+                gd.remove_item(il, 1);
+            }
+        }
     }
 }
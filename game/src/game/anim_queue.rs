@@ -40,6 +40,26 @@ impl AnimQueue {
         self.push(Animation::shot(idx, start, target));
     }
 
+    pub fn push_magic_ball(&mut self, tiles: Vec<Vec2d>) {
+        let idx: AnimImgIdx = gobj::id_to_idx("!magic-ball");
+        self.push(Animation::img_tiles(idx, tiles));
+    }
+
+    pub fn push_magic_beam(&mut self, tiles: Vec<Vec2d>) {
+        let idx: AnimImgIdx = gobj::id_to_idx("!magic-beam");
+        self.push(Animation::img_tiles(idx, tiles));
+    }
+
+    pub fn push_magic_cone(&mut self, tiles: Vec<Vec2d>) {
+        let idx: AnimImgIdx = gobj::id_to_idx("!magic-cone");
+        self.push(Animation::img_tiles(idx, tiles));
+    }
+
+    pub fn push_magic_buff(&mut self, tile: Vec2d) {
+        let idx: AnimImgIdx = gobj::id_to_idx("!magic-buff");
+        self.push(Animation::img_onetile(idx, tile));
+    }
+
     pub fn push_destroy(&mut self, tile: Vec2d) {
         self.push(Animation::destroy(vec![tile]));
     }
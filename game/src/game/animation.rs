@@ -26,6 +26,12 @@ pub enum Animation {
         idx: AnimImgIdx,
         tiles: Vec<Vec2d>,
     },
+    /// Image animation on multiple tiles
+    Tiles {
+        n_frame: u32,
+        idx: AnimImgIdx,
+        tiles: Vec<Vec2d>,
+    },
 }
 
 impl Animation {
@@ -35,6 +41,7 @@ impl Animation {
             &Animation::Img { n_frame, .. } => n_frame,
             &Animation::Shot { n_frame, .. } => n_frame,
             &Animation::Destroy { n_frame, .. } => n_frame,
+            &Animation::Tiles { n_frame, .. } => n_frame,
         }
     }
 
@@ -74,6 +81,14 @@ impl Animation {
         }
     }
 
+    pub fn img_tiles(idx: AnimImgIdx, tiles: Vec<Vec2d>) -> Animation {
+        Animation::Tiles {
+            n_frame: gobj::get_obj(idx).img.n_frame,
+            idx,
+            tiles,
+        }
+    }
+
     pub fn destroy(tiles: Vec<Vec2d>) -> Animation {
         let idx: AnimImgIdx = gobj::id_to_idx("!destroy-blood");
         let animobj = gobj::get_obj(idx);
@@ -1,4 +1,5 @@
 use crate::game::extrait::*;
+use crate::game::item::gen::gen_item_by_level_checked;
 use common::basic::WAIT_TIME_NUMERATOR;
 use common::gamedata::*;
 use common::gobj;
@@ -28,6 +29,9 @@ pub fn create_chara(chara_template_idx: CharaTemplateIdx, lv: u32) -> Chara {
         skills: gen_skill_list(ct, lv),
         rel: Relationship::NEUTRAL,
         trigger_talk: None,
+        spells: Vec::new(),
+        exp: 0,
+        vars: LocalVars::default(),
     };
 
     chara.update();
@@ -37,7 +41,8 @@ pub fn create_chara(chara_template_idx: CharaTemplateIdx, lv: u32) -> Chara {
 }
 
 /// Create npc character from the race
-pub fn create_npc_chara(dungeon: DungeonKind, floor_level: u32) -> Chara {
+/// Races in `RULES.params.night_races` are chosen only if `night` is true.
+pub fn create_npc_chara(dungeon: DungeonKind, floor_level: u32, night: bool) -> Chara {
     let idx = choose_npc_chara_template(
         &RULES
             .dungeon_gen
@@ -45,17 +50,30 @@ pub fn create_npc_chara(dungeon: DungeonKind, floor_level: u32) -> Chara {
             .expect("No rule for npc generation")
             .npc_race_probability,
         floor_level,
+        night,
     );
     let ct = gobj::get_obj(idx);
     let mut chara = create_chara(idx, ct.gen_level);
     set_skill(&mut chara);
+    set_ai_items(&mut chara, floor_level);
     chara.rel = Relationship::HOSTILE;
     chara
 }
 
 /// Choose one chara_template by race, gen_level and gen_weight
-pub fn choose_npc_chara_template(nrp: &HashMap<Race, f32>, floor_level: u32) -> CharaTemplateIdx {
+pub fn choose_npc_chara_template(
+    nrp: &HashMap<Race, f32>,
+    floor_level: u32,
+    night: bool,
+) -> CharaTemplateIdx {
     let chara_templates = &gobj::get_objholder().chara_template;
+    let race_weight = |race: &Race| {
+        if !night && RULES.params.night_races.contains(race) {
+            None
+        } else {
+            nrp.get(race)
+        }
+    };
 
     // Sum up gen_weight * weight_dist * dungeon_adjustment
     let weight_dist = CalcLevelWeightDist::new(floor_level);
@@ -63,7 +81,7 @@ pub fn choose_npc_chara_template(nrp: &HashMap<Race, f32>, floor_level: u32) ->
     let mut first_available_ct_idx = None;
 
     for (i, ct) in chara_templates.iter().enumerate() {
-        if let Some(da) = nrp.get(&ct.race) {
+        if let Some(da) = race_weight(&ct.race) {
             sum += weight_dist.calc(ct.gen_level) * ct.gen_weight as f64 * *da as f64;
             if first_available_ct_idx.is_none() {
                 first_available_ct_idx = Some(i);
@@ -80,7 +98,7 @@ pub fn choose_npc_chara_template(nrp: &HashMap<Race, f32>, floor_level: u32) ->
     let r = gen_range(0.0, sum);
     let mut sum = 0.0;
     for (i, ct) in chara_templates.iter().enumerate() {
-        if let Some(da) = nrp.get(&ct.race) {
+        if let Some(da) = race_weight(&ct.race) {
             sum += weight_dist.calc(ct.gen_level) * ct.gen_weight as f64 * *da as f64;
             if r < sum {
                 return CharaTemplateIdx::from_usize(i);
@@ -121,7 +139,10 @@ impl CalcLevelWeightDist {
 
 /// Create AI parameters
 pub fn create_ai(ai_kind: NpcAIKind) -> CharaAI {
-    CharaAI { kind: ai_kind }
+    CharaAI {
+        kind: ai_kind,
+        ..CharaAI::default()
+    }
 }
 
 /// Set skills to npc
@@ -136,6 +157,74 @@ fn set_skill(chara: &mut Chara) {
     }
 }
 
+/// The number of ammunition given to ranged attackers
+const NPC_AMMO_NUM: u32 = 30;
+
+/// Give items that are needed for npc's AI kind
+fn set_ai_items(chara: &mut Chara, floor_level: u32) {
+    match chara.ai.kind {
+        NpcAIKind::Ranged => {
+            let weapon = gen_item_by_level_checked(
+                floor_level,
+                |item| match item.kind {
+                    ItemKind::Weapon(kind) if !kind.is_melee() => 1.0,
+                    _ => 0.0,
+                },
+                false,
+            );
+            if let Some(weapon) = weapon {
+                let weapon_kind = match weapon.kind {
+                    ItemKind::Weapon(kind) => kind,
+                    _ => unreachable!(),
+                };
+                if chara.equip.slot_num(EquipSlotKind::RangedWeapon) == 0 {
+                    chara.equip = EquipItemList::new(&[(EquipSlotKind::RangedWeapon, 1)]);
+                }
+                chara.equip.equip(EquipSlotKind::RangedWeapon, 0, weapon);
+                if let Some(ammo_kind) = weapon_kind.ammo_kind() {
+                    let ammo = gen_item_by_level_checked(
+                        floor_level,
+                        |item| {
+                            if item.kind == ItemKind::Ammo(ammo_kind) {
+                                1.0
+                            } else {
+                                0.0
+                            }
+                        },
+                        false,
+                    );
+                    if let Some(ammo) = ammo {
+                        chara.item_list.append(ammo, NPC_AMMO_NUM);
+                    }
+                }
+                let lv = chara.level;
+                chara
+                    .skills
+                    .set_skill_level(SkillKind::Weapon(weapon_kind), lv);
+            }
+        }
+        NpcAIKind::Caster => {
+            let device = gen_item_by_level_checked(
+                floor_level,
+                |item| {
+                    if item.kind == ItemKind::MagicDevice {
+                        1.0
+                    } else {
+                        0.0
+                    }
+                },
+                false,
+            );
+            if let Some(device) = device {
+                chara.item_list.append(device, 1);
+                let lv = chara.level;
+                chara.skills.set_skill_level(SkillKind::MagicDevice, lv);
+            }
+        }
+        _ => (),
+    }
+}
+
 /// Generate skill list based on floor level and CharaTemplateObject
 fn gen_skill_list(_ct: &CharaTemplateObject, lv: u32) -> SkillList {
     let mut skill_list = SkillList::default();
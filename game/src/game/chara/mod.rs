@@ -24,6 +24,14 @@ pub trait CharaEx {
     fn add_evasion_exp(&mut self, attacker_level: u32);
     /// Add exp when regeneration
     fn add_healing_exp(&mut self);
+    /// Add exp when this character casts a spell.
+    fn add_cast_exp(&mut self, school: MagicSchool);
+    /// Add exp when this character reads a skill book.
+    fn add_reading_exp(&mut self, kind: SkillKind);
+    /// Add character exp. The level increases when the exp reaches the threshold in rules.
+    fn add_chara_exp(&mut self, exp: u32);
+    /// Add character exp when this character kills another character.
+    fn add_kill_exp(&mut self, target_level: u32);
     /// sp increase/decrease.
     fn add_sp(&mut self, v: f32, cid: CharaId);
     fn sub_sp(&mut self, v: f32, cid: CharaId);
@@ -39,6 +47,11 @@ pub trait CharaEx {
 
 impl CharaEx for Chara {
     fn add_skill_exp(&mut self, kind: SkillKind, add_exp: u32, base_level: u32) {
+        let (cap, aptitude) = RULES.chara.skill_aptitude(self.class, kind);
+        if self.skills.get(kind) >= cap {
+            return;
+        }
+        let add_exp = (add_exp as f32 * aptitude) as u32;
         let result = self.skills.add_exp(kind, add_exp, base_level);
         trace!("{} gains {} exp for {:?}", self.to_text(), result.1, kind);
         if result.0 {
@@ -70,6 +83,38 @@ impl CharaEx for Chara {
         }
     }
 
+    fn add_cast_exp(&mut self, school: MagicSchool) {
+        let kind = SkillKind::Magic(school);
+        let lv = self.skills.get(kind);
+        self.add_skill_exp(kind, RULES.exp.cast, lv);
+    }
+
+    fn add_reading_exp(&mut self, kind: SkillKind) {
+        let lv = self.skills.get(kind);
+        self.add_skill_exp(kind, RULES.exp.skill_book, lv);
+    }
+
+    fn add_chara_exp(&mut self, exp: u32) {
+        let exp = (exp as f32 * RULES.exp.base_factor) as u32;
+        trace!("{} gains {} character exp", self.to_text(), exp);
+        self.exp += exp;
+
+        let mut level_up = false;
+        while self.exp >= RULES.exp.level_up_exp(self.level) {
+            self.exp -= RULES.exp.level_up_exp(self.level);
+            self.level += 1;
+            level_up = true;
+            game_log!("chara-level-up"; chara=self, level=self.level);
+        }
+        if level_up {
+            self.update();
+        }
+    }
+
+    fn add_kill_exp(&mut self, target_level: u32) {
+        self.add_chara_exp(RULES.exp.kill * std::cmp::max(target_level, 1));
+    }
+
     fn add_sp(&mut self, v: f32, cid: CharaId) {
         let r = &RULES.chara;
         let old_sp = self.sp;
@@ -88,20 +133,20 @@ impl CharaEx for Chara {
             CharaId::Player => {
                 if v < 0.0 {
                     if new_sp <= r.sp_hungry && old_sp > r.sp_hungry {
-                        self.add_status(CharaStatus::Hungry);
+                        self.add_status(CharaStatus::new("hungry", None));
                     }
                     if new_sp <= r.sp_weak && old_sp > r.sp_weak {
-                        self.add_status(CharaStatus::Weak);
+                        self.add_status(CharaStatus::new("weak", None));
                     }
                     if new_sp <= r.sp_starving && old_sp > r.sp_starving {
-                        self.add_status(CharaStatus::Starving);
+                        self.add_status(CharaStatus::new("starving", None));
                     }
                 } else if v > 0.0 {
                     if new_sp > r.sp_hungry && old_sp <= r.sp_hungry {
-                        self.remove_sp_status();
+                        self.remove_status_group("sp");
                     }
                     if new_sp > r.sp_weak && old_sp <= r.sp_weak {
-                        self.add_status(CharaStatus::Weak);
+                        self.add_status(CharaStatus::new("weak", None));
                     }
                 }
             }
@@ -130,9 +175,7 @@ impl CharaEx for Chara {
                 DamageKind::RangedAttack => {
                     game_log!("killed-by-ranged-attack"; chara=self);
                 }
-                DamageKind::Poison => {
-                    game_log!("killed-by-poison-damage"; chara=self);
-                }
+                DamageKind::Status => (),
                 DamageKind::Starve => {
                     game_log!("killed-by-starve-damage"; chara=self);
                 }
@@ -153,3 +196,23 @@ impl CharaEx for Chara {
         self.wait_time = WAIT_TIME_NUMERATOR / self.attr.spd as u32;
     }
 }
+
+#[test]
+fn add_chara_exp_test() {
+    use super::testutil;
+    use common::gobj;
+
+    let _lock = testutil::init();
+    let mut chara = gen::create_chara(gobj::id_to_idx(testutil::CHARA_TEMPLATE_ID), 1);
+    let max_hp = chara.attr.max_hp;
+
+    // Gains two levels at once, and the rest is kept
+    let exp = RULES.exp.level_up_exp(1) + RULES.exp.level_up_exp(2) + 10;
+    chara.add_chara_exp((exp as f32 / RULES.exp.base_factor) as u32);
+    assert_eq!(chara.level, 3);
+    assert_eq!(chara.exp, 10);
+    assert!(chara.attr.max_hp > max_hp);
+
+    chara.add_chara_exp(0);
+    assert_eq!(chara.level, 3);
+}
@@ -1,15 +1,14 @@
 use super::Game;
-use crate::game::combat::DamageKind;
+use crate::game::combat::{element_resistance, DamageKind};
 use crate::game::extrait::*;
 use common::gamedata::*;
 use rng::{dice, get_rng, Rng};
+use rules::status::PerTurnEffect;
 use rules::RULES;
 
 /// This function will be called before the character's turn
 ///
 pub fn preturn(game: &mut Game, cid: CharaId) -> bool {
-    let mut is_poisoned = false;
-
     let chara = game.gd.chara.get_mut(cid);
 
     // Process character status
@@ -27,27 +26,13 @@ pub fn preturn(game: &mut Game, cid: CharaId) -> bool {
                 chara.status.push(s);
             }
         }
+        chara.update();
     }
     for s in expired_status.into_iter() {
         s.expire(&mut game.gd, cid);
     }
 
-    let chara = game.gd.chara.get_mut(cid);
-    for s in chara.status.iter() {
-        match *s {
-            CharaStatus::Poisoned => {
-                is_poisoned = true;
-            }
-            _ => (),
-        }
-    }
-
-    if is_poisoned {
-        let chara = game.gd.chara.get_mut(cid);
-        let damage = chara.attr.max_hp / 20;
-        game_log!("poison-damage"; chara=chara, damage=damage);
-        chara.damage(damage, DamageKind::Poison);
-    }
+    process_per_turn_effects(game, cid);
 
     let chara = game.gd.chara.get_mut(cid);
 
@@ -68,6 +53,45 @@ pub fn preturn(game: &mut Game, cid: CharaId) -> bool {
     can_act(chara)
 }
 
+/// Process per turn effects of status defined in rules
+fn process_per_turn_effects(game: &mut Game, cid: CharaId) {
+    let chara = game.gd.chara.get_mut(cid);
+    let effects: Vec<(&PerTurnEffect, u16)> = chara
+        .status
+        .iter()
+        .filter_map(|s| s.effect().map(|e| (&e.per_turn, s.stacks)))
+        .collect();
+
+    for (per_turn, stacks) in effects {
+        let stacks = stacks as f32;
+        let hp = (per_turn.hp_ratio * chara.attr.max_hp as f32 + per_turn.hp as f32) * stacks;
+        let hp = hp as i32;
+        if hp < 0 {
+            let mut damage = -hp;
+            if let Some(element) = per_turn.element {
+                let resistance = element_resistance(chara, element);
+                damage = (damage as f32 * (1.0 - resistance)) as i32;
+            }
+            if let Some(damage_log) = per_turn.damage_log.as_ref() {
+                game_log!(damage_log; chara=chara, damage=damage);
+            }
+            chara.damage(damage, DamageKind::Status);
+            if chara.hp < 0 {
+                if let Some(killed_log) = per_turn.killed_log.as_ref() {
+                    game_log!(killed_log; chara=chara);
+                }
+                return;
+            }
+        } else if hp > 0 {
+            chara.heal(hp);
+        }
+
+        if per_turn.sp != 0.0 {
+            chara.add_sp(per_turn.sp * stacks, cid);
+        }
+    }
+}
+
 /// Judges this character can act or not
 fn can_act(chara: &Chara) -> bool {
     if chara.hp < 0 {
@@ -75,15 +99,13 @@ fn can_act(chara: &Chara) -> bool {
     }
 
     for s in chara.status.iter() {
-        match *s {
-            CharaStatus::Asleep { .. } => {
-                game_log_i!("asleep"; chara=chara);
-                return false;
-            }
-            CharaStatus::Creation { .. } => {
+        if let Some(effect) = s.effect() {
+            if effect.prevent_action {
+                if let Some(log) = effect.prevent_action_log.as_ref() {
+                    game_log_i!(log; chara=chara);
+                }
                 return false;
             }
-            _ => (),
         }
     }
     true
@@ -0,0 +1,137 @@
+//! Functions for character status operation
+
+use super::CharaEx;
+use common::gamedata::*;
+use rules::status::{ExpireHook, Stacking, StatusEffect};
+use rules::RULES;
+
+pub trait CharaStatusOperation {
+    /// Add status. If turn_left of the new status is None, the duration defined in rules is used.
+    fn add_status(&mut self, new_status: CharaStatus);
+    /// Remove statuses that belong to the given group
+    fn remove_status_group(&mut self, group: &str);
+}
+
+impl CharaStatusOperation for Chara {
+    fn add_status(&mut self, mut new_status: CharaStatus) {
+        let effect = if let Some(effect) = RULES.status.get(&new_status.id) {
+            effect
+        } else {
+            return;
+        };
+
+        if let Some(group) = effect.group.as_ref() {
+            let id = &new_status.id;
+            self.status.retain(|s| {
+                s.id == *id || s.effect().and_then(|e| e.group.as_ref()) != Some(group)
+            });
+        }
+
+        if new_status.turn_left.is_none() {
+            new_status.turn_left = effect.duration;
+        }
+
+        if let Some(s) = self.status.iter_mut().find(|s| s.id == new_status.id) {
+            match effect.stacking {
+                Stacking::Ignore => {
+                    return;
+                }
+                Stacking::Refresh => {
+                    s.turn_left = longer_turn(s.turn_left, new_status.turn_left);
+                }
+                Stacking::Extend => {
+                    s.turn_left = match (s.turn_left, new_status.turn_left) {
+                        (Some(a), Some(b)) => Some(a.saturating_add(b)),
+                        _ => None,
+                    };
+                }
+                Stacking::Stack => {
+                    s.stacks = std::cmp::min(s.stacks + new_status.stacks, effect.max_stacks);
+                    s.turn_left = longer_turn(s.turn_left, new_status.turn_left);
+                }
+            }
+        } else {
+            new_status.stacks = std::cmp::min(new_status.stacks, effect.max_stacks);
+            self.status.push(new_status);
+        }
+
+        if let Some(add_log) = effect.add_log.as_ref() {
+            game_log!(add_log; chara=self);
+        }
+        self.update();
+    }
+
+    fn remove_status_group(&mut self, group: &str) {
+        let len = self.status.len();
+        self.status
+            .retain(|s| s.effect().and_then(|e| e.group.as_deref()) != Some(group));
+        if self.status.len() != len {
+            self.update();
+        }
+    }
+}
+
+/// None means infinite duration
+fn longer_turn(a: Option<u16>, b: Option<u16>) -> Option<u16> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(std::cmp::max(a, b)),
+        _ => None,
+    }
+}
+
+pub trait CharaStatusEx {
+    /// Get the effect definition in rules
+    fn effect(&self) -> Option<&'static StatusEffect>;
+    fn advance_turn(&mut self, n: u16);
+    /// If this status is expired, returns true.
+    /// Expired status will be removed from character.
+    fn is_expired(&self) -> bool;
+    fn expire(self, gd: &mut GameData, cid: CharaId);
+}
+
+impl CharaStatusEx for CharaStatus {
+    fn effect(&self) -> Option<&'static StatusEffect> {
+        RULES.status.get(&self.id)
+    }
+
+    fn advance_turn(&mut self, n: u16) {
+        if let Some(turn_left) = self.turn_left.as_mut() {
+            *turn_left = turn_left.saturating_sub(n);
+        }
+    }
+
+    fn is_expired(&self) -> bool {
+        self.turn_left == Some(0)
+    }
+
+    fn expire(self, gd: &mut GameData, cid: CharaId) {
+        let effect = if let Some(effect) = self.effect() {
+            effect
+        } else {
+            return;
+        };
+        let mut data = self.data;
+
+        for hook in &effect.on_expire {
+            match hook {
+                ExpireHook::AddStatus { id } => {
+                    gd.chara.get_mut(cid).add_status(CharaStatus::new(id, None));
+                }
+                ExpireHook::Log { id } => {
+                    let chara = gd.chara.get(cid);
+                    game_log!(id; chara=chara);
+                }
+                ExpireHook::FinishCreation => {
+                    if let Some(CharaStatusData::Creation {
+                        recipe,
+                        ingredients,
+                    }) = data.take()
+                    {
+                        assert!(cid == CharaId::Player);
+                        crate::game::creation::finish_creation(gd, &recipe, ingredients);
+                    }
+                }
+            }
+        }
+    }
+}
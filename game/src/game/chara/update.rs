@@ -0,0 +1,65 @@
+use crate::game::extrait::CharaStatusEx;
+use common::gamedata::*;
+use common::gobj;
+use rules::status::AttrModifier;
+use rules::RULES;
+
+/// Update character attributes by its status
+pub fn update_attributes(chara: &mut Chara) {
+    let ct = gobj::get_obj(chara.template);
+
+    let class_revision = RULES.chara.class_revision.get(&chara.class);
+    let base_attr = if let Some(&r) = class_revision {
+        ct.base_attr.revise(r)
+    } else {
+        ct.base_attr
+    };
+    let growth = RULES
+        .chara
+        .level_growth
+        .growth(class_revision, chara.level.saturating_sub(ct.gen_level));
+    let base_attr = base_attr.revise(growth);
+
+    let m = status_modifier(chara);
+
+    chara.attr.max_hp = std::cmp::max(calc_max_hp(chara, base_attr.base_hp) + m.max_hp, 1);
+    chara.attr.str = modified_attr(base_attr.str, m.str);
+    chara.attr.vit = modified_attr(base_attr.vit, m.vit);
+    chara.attr.dex = modified_attr(base_attr.dex, m.dex);
+    chara.attr.int = modified_attr(base_attr.int, m.int);
+    chara.attr.wil = modified_attr(base_attr.wil, m.wil);
+    chara.attr.cha = modified_attr(base_attr.cha, m.cha);
+    chara.attr.spd = modified_attr(base_attr.spd, m.spd);
+    chara.attr.view_range = std::cmp::max(RULES.chara.default_view_range + m.view_range, 1);
+}
+
+/// Sum of attribute modifiers by status
+fn status_modifier(chara: &Chara) -> AttrModifier {
+    let mut sum = AttrModifier::default();
+    for s in &chara.status {
+        let m = if let Some(effect) = s.effect() {
+            &effect.modifier
+        } else {
+            continue;
+        };
+        let stacks = s.stacks as i16;
+        sum.max_hp += m.max_hp * stacks as i32;
+        sum.str += m.str * stacks;
+        sum.vit += m.vit * stacks;
+        sum.dex += m.dex * stacks;
+        sum.int += m.int * stacks;
+        sum.wil += m.wil * stacks;
+        sum.cha += m.cha * stacks;
+        sum.spd += m.spd * stacks;
+        sum.view_range += m.view_range * stacks as i32;
+    }
+    sum
+}
+
+fn modified_attr(base: i16, modifier: i16) -> u16 {
+    std::cmp::max(base.saturating_add(modifier), 1) as u16
+}
+
+fn calc_max_hp(chara: &mut Chara, base_hp: i32) -> i32 {
+    (chara.skills.get(SkillKind::Endurance) as i32 + 8) * base_hp / 8
+}
@@ -0,0 +1,585 @@
+use super::chara::CharaEx;
+use super::extrait::CharaStatusEx;
+use super::{Game, InfoGetter};
+use crate::rng;
+use common::gamedata::*;
+use common::gobj;
+use rng::{Rng, RngStream};
+use rules::combat::WeaponParams;
+use rules::RULES;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DamageKind {
+    MeleeAttack,
+    RangedAttack,
+    /// Damage by character status. Logging is done by the caller
+    Status,
+    Starve,
+}
+
+pub struct AttackParams {
+    pub attacker_id: Option<CharaId>,
+    pub kind: DamageKind,
+    pub element: Element,
+    pub attack_power: f64,
+    /// If None, this attack always hits
+    pub accuracy_power: Option<f64>,
+    /// Probability of critical hits
+    pub critical: f64,
+    /// Ratio of the target's armour defence that is ignored
+    pub penetration: f64,
+}
+
+/// The result of an attack to one target
+#[derive(Clone, Copy, Debug)]
+pub struct CombatResult {
+    pub hit: bool,
+    pub critical: bool,
+    /// Damage actually given to the target
+    pub damage: i32,
+    pub element: Element,
+    /// Resistance of the target to the element
+    pub resistance: f32,
+    pub killed: bool,
+}
+
+impl CombatResult {
+    fn miss(element: Element) -> CombatResult {
+        CombatResult {
+            hit: false,
+            critical: false,
+            damage: 0,
+            element,
+            resistance: 0.0,
+            killed: false,
+        }
+    }
+}
+
+/// Attack neighbor enemy by short range weapon or bare hands
+pub fn attack_neighbor(game: &mut Game, attacker_id: CharaId, target_id: CharaId) -> CombatResult {
+    rng::with_stream(RngStream::Combat, || {
+        attack_neighbor_on_stream(game, attacker_id, target_id)
+    })
+}
+
+fn attack_neighbor_on_stream(
+    game: &mut Game,
+    attacker_id: CharaId,
+    target_id: CharaId,
+) -> CombatResult {
+    // Logging
+    {
+        let attacker = game.gd.chara.get(attacker_id);
+        let target = game.gd.chara.get(target_id);
+        game_log!("attack"; attacker=attacker, target=target);
+    }
+    // Animation pushing
+    game.anim_queue
+        .push_attack(game.gd.get_current_map().chara_pos(target_id).unwrap());
+
+    let skill_kind;
+    let attacker = game.gd.chara.get(attacker_id);
+
+    // Damage calculation
+    let attack_params = if let Some(weapon) = attacker.equip.item(EquipSlotKind::MeleeWeapon, 0) {
+        let weapon_obj = gobj::get_obj(weapon.idx);
+        let weapon_kind = get_weapon_kind(weapon_obj);
+        skill_kind = SkillKind::Weapon(weapon_kind);
+
+        let dice_result = rng::dice(weapon_obj.dice_n as i32, weapon_obj.dice_x as i32);
+        let weapon_skill_level = attacker.skills.get(skill_kind);
+        let attack_power = calc_attack_power(dice_result, attacker.attr.str, weapon_skill_level);
+        weapon_attack_params(
+            attacker,
+            attacker_id,
+            DamageKind::MeleeAttack,
+            RULES.combat.weapon(weapon_kind),
+            weapon_skill_level,
+            weapon_obj.element,
+            attack_power,
+        )
+    } else {
+        // Attack by bare hands
+        skill_kind = SkillKind::BareHands;
+        let weapon_skill_level = attacker.skills.get(skill_kind);
+        let dice_result = rng::dice(1, weapon_skill_level as i32 / 3 + 1);
+        let attack_power = calc_attack_power(dice_result, attacker.attr.str, weapon_skill_level);
+        weapon_attack_params(
+            attacker,
+            attacker_id,
+            DamageKind::MeleeAttack,
+            &RULES.combat.bare_hands,
+            weapon_skill_level,
+            Element::Physical,
+            attack_power,
+        )
+    };
+
+    // Damage target
+    let result = attack_target(game, attack_params, target_id);
+
+    if result.hit {
+        // Exp processing
+        let target_level = game.gd.chara.get(target_id).level;
+        let attacker = game.gd.chara.get_mut(attacker_id);
+        attacker.add_attack_exp(skill_kind, target_level);
+        // Sound effect
+        crate::audio::play_sound("punch");
+    } else {
+        crate::audio::play_sound("attack-miss");
+    }
+    result
+}
+
+/// Shot target by long range weapons.
+/// If attacker actually do actions, returns the result.
+pub fn shot_target(
+    game: &mut Game,
+    attacker_id: CharaId,
+    target_id: CharaId,
+) -> Option<CombatResult> {
+    rng::with_stream(RngStream::Combat, || {
+        shot_target_on_stream(game, attacker_id, target_id)
+    })
+}
+
+fn shot_target_on_stream(
+    game: &mut Game,
+    attacker_id: CharaId,
+    target_id: CharaId,
+) -> Option<CombatResult> {
+    let attacker = game.gd.chara.get(attacker_id);
+    let weapon = if let Some(weapon) = attacker.equip.item(EquipSlotKind::RangedWeapon, 0) {
+        weapon
+    } else {
+        // If this chara doesn't equip long range weapon
+        game_log_i!("no-ranged-weapon-equipped");
+        return None;
+    };
+    let weapon_obj = gobj::get_obj(weapon.idx);
+    let weapon_kind = get_weapon_kind(weapon_obj);
+
+    // Ammunition for this weapon
+    let ammo = if let Some(ammo_kind) = weapon_kind.ammo_kind() {
+        if let Some(ammo) = find_ammo(attacker, ammo_kind) {
+            Some(ammo)
+        } else {
+            if attacker_id == CharaId::Player {
+                game_log_i!("no-ammo"; ammo=ammo_kind);
+            }
+            return None;
+        }
+    } else {
+        None
+    };
+
+    let attacker_pos = game.gd.get_current_map().chara_pos(attacker_id).unwrap();
+    let target_pos = game.gd.get_current_map().chara_pos(target_id).unwrap();
+
+    // Animation pushing
+    game.anim_queue.push_shot(attacker_pos, target_pos);
+
+    // Damage calculation
+    let attack_params = {
+        let mut dice_result = rng::dice(weapon_obj.dice_n as i32, weapon_obj.dice_x as i32);
+        let mut element = weapon_obj.element;
+        // Ammunition adds its damage and element
+        if let Some((_, ammo_obj)) = ammo {
+            if ammo_obj.dice_n > 0 {
+                dice_result += rng::dice(ammo_obj.dice_n as i32, ammo_obj.dice_x as i32);
+            }
+            if ammo_obj.element != Element::Physical {
+                element = ammo_obj.element;
+            }
+        }
+
+        let weapon_skill_level = attacker.skills.get(SkillKind::Weapon(weapon_kind));
+        let attack_power = calc_attack_power(dice_result, attacker.attr.dex, weapon_skill_level);
+
+        weapon_attack_params(
+            attacker,
+            attacker_id,
+            DamageKind::RangedAttack,
+            RULES.combat.weapon(weapon_kind),
+            weapon_skill_level,
+            element,
+            attack_power,
+        )
+    };
+    // Logging
+    {
+        let target = game.gd.chara.get(target_id);
+        game_log!("shot-target"; attacker=attacker, target=target);
+    }
+    // Consume one ammunition per shot
+    if let Some((i, _)) = ammo {
+        game.gd.chara.get_mut(attacker_id).item_list.remove(i, 1);
+    }
+    // Damage target
+    let result = attack_target(game, attack_params, target_id);
+
+    if result.hit {
+        // Exp processing
+        let target_level = game.gd.chara.get(target_id).level;
+        let attacker = game.gd.chara.get_mut(attacker_id);
+        attacker.add_attack_exp(SkillKind::Weapon(weapon_kind), target_level);
+        // Sound effect
+        crate::audio::play_sound("arrow");
+    } else {
+        crate::audio::play_sound("attack-miss");
+    }
+    Some(result)
+}
+
+/// Throw an item to the target character.
+/// Potions give no damage. Their effects are applied by the caller if they hit.
+pub fn throw_item_to(
+    game: &mut Game,
+    item: &Item,
+    attacker_id: CharaId,
+    target_id: CharaId,
+) -> CombatResult {
+    rng::with_stream(RngStream::Combat, || {
+        throw_item_to_on_stream(game, item, attacker_id, target_id)
+    })
+}
+
+fn throw_item_to_on_stream(
+    game: &mut Game,
+    item: &Item,
+    attacker_id: CharaId,
+    target_id: CharaId,
+) -> CombatResult {
+    let attacker = game.gd.chara.get(attacker_id);
+    let item_obj = item.obj();
+    let skill_level = attacker.skills.get(SkillKind::Throwing);
+    let dice_x = item_obj.w / RULES.combat.throw_weight_per_dice + 1;
+    let dice_result = rng::dice(1, dice_x as i32);
+    let attack_power = calc_attack_power(dice_result, attacker.attr.str, skill_level);
+    let attack_params = weapon_attack_params(
+        attacker,
+        attacker_id,
+        DamageKind::RangedAttack,
+        &RULES.combat.throwing,
+        skill_level,
+        Element::Physical,
+        attack_power,
+    );
+
+    let result = if item_obj.kind == ItemKind::Potion {
+        let accuracy_power = attack_params.accuracy_power.unwrap();
+        if hit_judge(&game.gd, accuracy_power, target_id, attack_params.kind) {
+            CombatResult {
+                hit: true,
+                ..CombatResult::miss(Element::None)
+            }
+        } else {
+            let result = CombatResult::miss(Element::None);
+            process_combat_result(game, &attack_params, target_id, &result);
+            result
+        }
+    } else {
+        attack_target(game, attack_params, target_id)
+    };
+
+    if result.hit {
+        let target_level = game.gd.chara.get(target_id).level;
+        let attacker = game.gd.chara.get_mut(attacker_id);
+        attacker.add_attack_exp(SkillKind::Throwing, target_level);
+    }
+    result
+}
+
+/// Find ammunition of the given kind in the character's inventory.
+/// Returns the index in the item list and the object.
+fn find_ammo(chara: &Chara, ammo_kind: AmmoKind) -> Option<(u32, &'static ItemObject)> {
+    chara
+        .item_list
+        .iter()
+        .enumerate()
+        .find(|(_, (item, _))| item.kind == ItemKind::Ammo(ammo_kind))
+        .map(|(i, (item, _))| (i as u32, gobj::get_obj(item.idx)))
+}
+
+fn weapon_attack_params(
+    attacker: &Chara,
+    attacker_id: CharaId,
+    kind: DamageKind,
+    weapon: &WeaponParams,
+    skill_level: u32,
+    element: Element,
+    attack_power: f64,
+) -> AttackParams {
+    let accuracy_power = calc_accuracy_power(weapon.accuracy, skill_level, attacker.attr.dex);
+    let critical = RULES.combat.critical_base
+        + RULES.combat.critical_dex_factor * attacker.attr.dex as f32
+        + weapon.critical;
+
+    AttackParams {
+        attacker_id: Some(attacker_id),
+        kind,
+        element,
+        attack_power,
+        accuracy_power: Some(accuracy_power),
+        critical: critical.into(),
+        penetration: weapon.penetration.into(),
+    }
+}
+
+/// Routines for targetted character
+pub fn attack_target(
+    game: &mut Game,
+    attack_params: AttackParams,
+    target_id: CharaId,
+) -> CombatResult {
+    let mut result = calc_combat_result(&game.gd, &attack_params, target_id);
+
+    if result.hit {
+        let target = game.gd.chara.get_mut(target_id);
+        target.damage(result.damage, attack_params.kind);
+        result.killed = target.hp < 0;
+    }
+    process_combat_result(game, &attack_params, target_id, &result);
+
+    result
+}
+
+/// Judge hit and critical, and calculate damage
+fn calc_combat_result(
+    gd: &GameData,
+    attack_params: &AttackParams,
+    target_id: CharaId,
+) -> CombatResult {
+    let element = attack_params.element;
+
+    // Judges hit or miss
+    if let Some(accuracy_power) = attack_params.accuracy_power {
+        if !hit_judge(gd, accuracy_power, target_id, attack_params.kind) {
+            return CombatResult::miss(element);
+        }
+    }
+    let critical = rng::get_rng().gen_bool(attack_params.critical.clamp(0.0, 1.0));
+
+    let target = gd.chara.get(target_id);
+    let equip_def = if element != Element::None {
+        calc_equip_defence(gd, target_id)[element] as f64
+    } else {
+        0.0
+    };
+    let equip_def = equip_def * (1.0 - attack_params.penetration.clamp(0.0, 1.0));
+    let defence_skill_level = target.skills.get(SkillKind::Defence);
+    let resistance = element_resistance(target, element);
+
+    let mut attack_power = attack_params.attack_power;
+    if critical {
+        attack_power *= RULES.combat.critical_multiplier as f64;
+    }
+    let defence_power = calc_defence_power(equip_def, target.attr.vit, defence_skill_level);
+    let damage = attack_power / defence_power * (1.0 - resistance) as f64;
+    let damage = damage.floor() as i32;
+
+    CombatResult {
+        hit: true,
+        critical,
+        damage,
+        element,
+        resistance,
+        // Set after the damage is given
+        killed: false,
+    }
+}
+
+/// Logging, animation, quest and exp processing by the combat result
+fn process_combat_result(
+    game: &mut Game,
+    attack_params: &AttackParams,
+    target_id: CharaId,
+    result: &CombatResult,
+) {
+    let attacker_level = attack_params
+        .attacker_id
+        .map(|attacker_id| game.gd.chara.get(attacker_id).level);
+    let target = game.gd.chara.get_mut(target_id);
+
+    if !result.hit {
+        game_log!("attack-evade"; chara=target);
+        if let Some(attacker_level) = attacker_level {
+            target.add_evasion_exp(attacker_level);
+        }
+        return;
+    }
+
+    // Damage log
+    if result.critical {
+        game_log!("critical-hit"; chara=target);
+    }
+    let element = result.element;
+    let damage = result.damage;
+    if result.resistance != 0.0 {
+        let resistance = (result.resistance * 100.0) as i32;
+        game_log!("damaged-chara-resisted"; chara=target, element=element, resistance=resistance, damage=damage);
+    } else if element != Element::Physical && element != Element::None {
+        game_log!("damaged-chara-element"; chara=target, element=element, damage=damage);
+    } else {
+        game_log!("damaged-chara"; chara=target, damage=damage);
+    }
+
+    if !result.killed {
+        // Exp for targetted character
+        if let Some(attacker_level) = attacker_level {
+            target.add_damage_exp(damage, attacker_level);
+        }
+    } else {
+        let idx = target.template;
+        let target_level = target.level;
+        super::quest::count_slayed_monster(&mut game.gd, idx);
+        let template_id = gobj::idx_to_id(idx).to_owned();
+        super::event::trigger_event(game, EventKind::Kill(template_id));
+        if let Some(attacker_id) = attack_params.attacker_id {
+            game.gd
+                .chara
+                .get_mut(attacker_id)
+                .add_kill_exp(target_level);
+        }
+        game.anim_queue
+            .push_destroy(game.gd.chara_pos(target_id).unwrap());
+    }
+}
+
+fn get_weapon_kind(item: &ItemObject) -> WeaponKind {
+    match item.kind {
+        ItemKind::Weapon(kind) => kind,
+        _ => unreachable!(),
+    }
+}
+
+/// Calculate character's defence for each elements
+fn calc_equip_defence(gd: &GameData, cid: CharaId) -> ElementArray<u16> {
+    let mut def: ElementArray<u16> = ElementArray::default();
+
+    for (_, _, item) in gd.get_equip_list(cid).item_iter() {
+        let item_obj: &ItemObject = gobj::get_obj(item.idx);
+        for e in &ELEMENTS {
+            def[*e] = def[*e].saturating_add(item_obj.def[*e]);
+        }
+    }
+
+    def
+}
+
+/// Damage reduction ratio by race and status.
+/// Negative values mean weakness.
+pub fn element_resistance(chara: &Chara, element: Element) -> f32 {
+    if element == Element::None {
+        return 0.0;
+    }
+    let race = gobj::get_obj(chara.template).race;
+    let mut resistance = RULES
+        .chara
+        .race_resistance
+        .get(&race)
+        .and_then(|r| r.get(&element))
+        .copied()
+        .unwrap_or(0.0);
+
+    for s in &chara.status {
+        if let Some(r) = s.effect().and_then(|e| e.resistance.get(&element)) {
+            resistance += r * s.stacks as f32;
+        }
+    }
+
+    // Damage is not reduced to zero, and not increased over double
+    resistance.clamp(-1.0, 0.9)
+}
+
+/// Calculate attack power
+fn calc_attack_power(dice: i32, chara_param: u16, skill_level: u32) -> f64 {
+    assert!(dice > 0);
+    assert!(chara_param > 0);
+    let dice = dice as f64;
+    let chara_param = chara_param as f64;
+    let skill_level = skill_level as f64;
+
+    dice * chara_param * chara_param * (skill_level + 8.0).powf(1.5)
+}
+
+/// Calculate defence power
+fn calc_defence_power(equip_def: f64, chara_param: u16, skill_level: u32) -> f64 {
+    assert!(chara_param > 0);
+    let chara_param = chara_param as f64;
+    let skill_level = skill_level as f64;
+
+    (equip_def + 16.0) * chara_param * (skill_level + 8.0)
+}
+
+/// Calculate accuracy power
+fn calc_accuracy_power(weapon: u32, skill_level: u32, chara_param: u16) -> f64 {
+    let weapon = weapon as f64;
+    let skill_level = skill_level as f64;
+    let chara_param = chara_param as f64 * 0.25;
+
+    weapon + skill_level + chara_param * 0.5
+}
+
+/// Calculate evasion power
+fn calc_evasion_power(equip: u32, skill_level: u32, chara_param: u16) -> f64 {
+    let equip = equip as f64;
+    let skill_level = skill_level as f64;
+    let chara_param = chara_param as f64 * 0.25;
+
+    equip + skill_level + chara_param * 0.5
+}
+
+fn hit_judge(gd: &GameData, accuracy_power: f64, target_id: CharaId, kind: DamageKind) -> bool {
+    let evasion_power = {
+        let equip = match kind {
+            DamageKind::MeleeAttack => 1,
+            DamageKind::RangedAttack => 1,
+            _ => {
+                return true;
+            } // Some kind damage always hits
+        };
+
+        let target = gd.chara.get(target_id);
+        calc_evasion_power(
+            equip,
+            target.skills.get(SkillKind::Evasion),
+            target.attr.dex,
+        )
+    };
+
+    let d = accuracy_power - evasion_power;
+    let p = 1.0 / (1.0 + (-d * 0.125).exp());
+    rng::get_rng().gen_bool(p)
+}
+
+#[test]
+fn shot_ammo_test() {
+    use super::item::gen::gen_item_from_idx;
+    use super::testutil;
+    use geom::Vec2d;
+
+    let _lock = testutil::init();
+    let mut gd = testutil::gen_gd();
+    let npc = testutil::add_npc(&mut gd, Vec2d(3, 1), Relationship::HOSTILE);
+    let player = gd.chara.get_mut(CharaId::Player);
+    let bow = gen_item_from_idx(gobj::id_to_idx(testutil::BOW_ID));
+    player.equip = EquipItemList::new(&[(EquipSlotKind::RangedWeapon, 1)]);
+    player.equip.equip(EquipSlotKind::RangedWeapon, 0, bow);
+    let arrow = gen_item_from_idx(gobj::id_to_idx(testutil::ARROW_ID));
+    player.item_list.append(arrow, 3);
+    let mut game = Game::new_headless(gd);
+
+    assert!(shot_target(&mut game, CharaId::Player, npc).is_some());
+    let player = game.gd.chara.get(CharaId::Player);
+    assert_eq!(player.item_list.items[0].1, 2);
+
+    // No shot without ammunition
+    game.gd
+        .chara
+        .get_mut(CharaId::Player)
+        .item_list
+        .remove(0, 2);
+    assert!(shot_target(&mut game, CharaId::Player, npc).is_none());
+}
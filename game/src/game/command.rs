@@ -35,6 +35,8 @@ pub enum Command {
     DrinkItem,
     EatItem,
     ReleaseItem,
+    ThrowItem,
+    CastSpell,
     TargetingMode,
     TextInput {
         text: String,
@@ -29,11 +29,15 @@ pub fn start_creation(gd: &mut GameData, recipe: &Recipe, il: Vec<ItemLocation>)
     }
 
     let player = gd.chara.get_mut(CharaId::Player);
-    player.add_status(CharaStatus::Creation {
-        turn_left: RULES.creation.required_time[&recipe.required_time],
+    let mut status = CharaStatus::new(
+        "creation",
+        Some(RULES.creation.required_time[&recipe.required_time]),
+    );
+    status.data = Some(CharaStatusData::Creation {
         recipe: recipe.clone(),
         ingredients,
     });
+    player.add_status(status);
 
     let player = gd.chara.get(CharaId::Player);
     let product = obj_txt(&recipe.product);
@@ -6,7 +6,7 @@ use common::gamedata::*;
 use common::gobj;
 use common::objholder::*;
 use geom::*;
-use rng::{self, GameRng, SliceRandom};
+use rng::{self, GameRng, RngStream, SliceRandom};
 use rules::RULES;
 
 /// Add a new dungeon
@@ -18,9 +18,18 @@ pub fn add_dungeon_site(gd: &mut GameData, dungeon_kind: DungeonKind, pos: Vec2d
         .unwrap()
 }
 
-/// Extend dungion site by one floor
+/// Extend dungion site by one floor.
+/// The same floor is generated for the same world seed.
 pub fn extend_site_floor(gd: &mut GameData, sid: SiteId) {
     let floor = gd.region.get_site(sid).floor_num();
+    let key = MapId::SiteMap { sid, floor }.as_u64();
+    rng::reseed_stream(RngStream::MapGen, key);
+    rng::reseed_stream(RngStream::ItemGen, key);
+
+    rng::with_stream(RngStream::MapGen, || gen_site_floor(gd, sid, floor));
+}
+
+fn gen_site_floor(gd: &mut GameData, sid: SiteId, floor: u32) {
     let is_deepest_floor = floor >= gd.region.get_site(sid).max_floor() - 1;
     let map = match gd.region.get_site(sid).content {
         SiteContent::AutoGenDungeon { dungeon_kind } => {
@@ -2,34 +2,31 @@ use common::gamedata::*;
 use common::gobj;
 use common::script::{Expr, ExprErrorKind, Operator, Value};
 
+use crate::game::InfoGetter;
+
 pub trait EvalExpr {
-    fn eval(&self, gd: &GameData) -> Value;
+    /// Evaluate with the character who executes the script and variables of the script execution
+    fn eval(&self, gd: &GameData, cid: Option<CharaId>, local: &LocalVars) -> Value;
 }
 
 impl EvalExpr for Expr {
-    fn eval(&self, gd: &GameData) -> Value {
+    fn eval(&self, gd: &GameData, cid: Option<CharaId>, local: &LocalVars) -> Value {
         match self {
             Expr::Value(value) => value.clone(),
             Expr::Term(v) => {
-                let mut a = v[0].1.eval(gd);
+                let mut a = v[0].1.eval(gd, cid, local);
                 assert_eq!(v[0].0, Operator::None);
 
                 for (o, b) in v.iter().skip(1) {
-                    let b = b.eval(gd);
+                    let b = b.eval(gd, cid, local);
                     a = binary_operation(*o, a, b);
                 }
                 a
             }
-            Expr::GVar(var_name) => {
-                if let Some(v) = gd.vars.global_var(var_name) {
-                    v.clone()
-                } else {
-                    Value::RefUnknownVar
-                }
-            }
+            Expr::GVar(var_name) => var_value(gd.vars.global_var(var_name)),
             Expr::IsGVarEmpty(var_name) => Value::Bool(gd.vars.global_var(var_name).is_some()),
             Expr::CurrentTime => Value::Time(gd.time.current_time()),
-            Expr::DurationHour(a, b) => match (a.eval(gd), b.eval(gd)) {
+            Expr::DurationHour(a, b) => match (a.eval(gd, cid, local), b.eval(gd, cid, local)) {
                 (Value::Time(a), Value::Time(b)) => Value::Int(a.duration_from(b).as_hours()),
                 _ => Value::Error(ExprErrorKind::InvalidType),
             },
@@ -49,10 +46,82 @@ impl EvalExpr for Expr {
                     Value::Error(ExprErrorKind::UnknownIdRef)
                 }
             }
+            Expr::PlayerLevel => Value::Int(gd.chara.get(CharaId::Player).level as i32),
+            Expr::SkillLevel(kind) => {
+                Value::Int(gd.chara.get(CharaId::Player).skills.get(*kind) as i32)
+            }
+            Expr::Money => Value::Int(gd.player.money().min(i32::MAX as i64) as i32),
+            Expr::IsQuestActive(quest_id) => {
+                Value::Bool(gd.quest.custom_quest_state(quest_id) == Some(QuestState::Active))
+            }
+            Expr::IsQuestCompleted(quest_id) => Value::Bool(matches!(
+                gd.quest.custom_quest_state(quest_id),
+                Some(QuestState::Completed) | Some(QuestState::RewardReceived)
+            )),
+            Expr::Relationship(rel) => {
+                if let Some(cid) = cid {
+                    Value::Bool(gd.chara.get(cid).rel == *rel)
+                } else {
+                    warn!("script error: relationship() needs talking character");
+                    Value::Error(ExprErrorKind::Other)
+                }
+            }
+            Expr::CurrentFloor => {
+                let mid = gd.get_current_mapid();
+                if mid.is_region_map() {
+                    Value::Int(-1)
+                } else {
+                    Value::Int(mid.floor() as i32)
+                }
+            }
+            Expr::InTown(town_id) => {
+                let mid = gd.get_current_mapid();
+                if mid.is_region_map() {
+                    return Value::Bool(false);
+                }
+                match &gd.region.get_site(mid.sid()).content {
+                    SiteContent::Town { town } => Value::Bool(town.id() == town_id),
+                    _ => Value::Bool(false),
+                }
+            }
+            Expr::ItemCount(item_id) => {
+                if let Some(idx) = gobj::id_to_idx_checked(item_id) {
+                    Value::Int(gd.has_item(idx) as i32)
+                } else {
+                    warn!("script error: unknown id {}", item_id);
+                    Value::Error(ExprErrorKind::UnknownIdRef)
+                }
+            }
+            Expr::CVar(var_name) => {
+                if let Some(cid) = cid {
+                    var_value(gd.chara.get(cid).vars.get(var_name))
+                } else {
+                    warn!("script error: chara variable needs executing character");
+                    Value::Error(ExprErrorKind::Other)
+                }
+            }
+            Expr::SVar(var_name) => {
+                let mid = gd.get_current_mapid();
+                if mid.is_region_map() {
+                    warn!("script error: site variable is used on region map");
+                    Value::Error(ExprErrorKind::Other)
+                } else {
+                    var_value(gd.region.get_site(mid.sid()).vars.get(var_name))
+                }
+            }
+            Expr::LVar(var_name) => var_value(local.get(var_name)),
         }
     }
 }
 
+fn var_value(v: Option<&Value>) -> Value {
+    if let Some(v) = v {
+        v.clone()
+    } else {
+        Value::RefUnknownVar
+    }
+}
+
 fn binary_operation(o: Operator, a: Value, b: Value) -> Value {
     use self::Value::*;
 
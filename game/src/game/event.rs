@@ -0,0 +1,52 @@
+//! Start scripts by event triggers
+
+use super::Game;
+use common::gamedata::*;
+use std::collections::VecDeque;
+
+/// Queue scripts triggered by the event on the current map and site
+pub fn trigger_event(game: &mut Game, event: EventKind) {
+    queue_triggered_scripts(game, |t, _| t.event == event);
+}
+
+/// Queue scripts whose scheduled time has come
+pub fn check_scheduled_events(game: &mut Game) {
+    queue_triggered_scripts(game, |t, now| t.is_scheduled(now));
+}
+
+/// Start the first queued script if no script is running
+pub fn start_queued_script(game: &mut Game) {
+    if game.script.is_some() {
+        return;
+    }
+    if let Some((script_id, section)) = game.event_scripts.pop_front() {
+        game.start_script_section(&script_id, &section, None);
+    }
+}
+
+fn queue_triggered_scripts<F: Fn(&EventTrigger, Time) -> bool>(game: &mut Game, f: F) {
+    let now = game.gd.time.current_time();
+    let mid = game.gd.get_current_mapid();
+
+    let map_triggers = &mut game.gd.get_current_map_mut().triggers;
+    take_triggered(map_triggers, now, &f, &mut game.event_scripts);
+    if !mid.is_region_map() {
+        let site_triggers = &mut game.gd.region.get_site_mut(mid.sid()).triggers;
+        take_triggered(site_triggers, now, &f, &mut game.event_scripts);
+    }
+}
+
+fn take_triggered<F: Fn(&EventTrigger, Time) -> bool>(
+    triggers: &mut Vec<EventTrigger>,
+    now: Time,
+    f: &F,
+    scripts: &mut VecDeque<(String, String)>,
+) {
+    for t in triggers.iter_mut() {
+        if f(t, now) {
+            t.last_time = Some(now);
+            scripts.push_back((t.script_id.clone(), t.section.clone()));
+        }
+    }
+    triggers.retain(|t| !(t.once && t.last_time.is_some()));
+}
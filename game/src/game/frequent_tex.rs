@@ -20,6 +20,13 @@ impl FrequentTextures {
         FrequentTextures { effect_idx }
     }
 
+    /// Create without any texture. Must not be used for drawing.
+    pub fn empty() -> FrequentTextures {
+        FrequentTextures {
+            effect_idx: Vec::new(),
+        }
+    }
+
     pub fn overlay_idx(&self, o: Overlay) -> EffectIdx {
         self.effect_idx[o as usize]
     }
@@ -0,0 +1,263 @@
+//! Headless mode runs the game without window, audio and textures.
+//! The player character is moved by NPC AI instead of user input,
+//! or by the commands of a replay.
+
+use super::frequent_tex::FrequentTextures;
+use super::newgame::NewGameBuilder;
+use super::npc::process_turn_by_ai;
+use super::replay::{EndState, Replay};
+use super::{Command, DialogOpenRequest, DoPlayerAction, Game, GameState, InfoGetter};
+use common::gamedata::*;
+use geom::Direction;
+use std::error::Error;
+
+/// AI kind used for the player stand-in
+const PLAYER_STAND_IN_AI: NpcAIKind = NpcAIKind::Melee;
+
+/// The result of a headless run
+#[derive(Clone, Copy, Debug)]
+pub struct HeadlessReport {
+    /// The number of processed player turns
+    pub turns: u64,
+    pub player_dead: bool,
+}
+
+impl Game {
+    /// Create Game for headless mode.
+    /// Textures are not loaded, so the returned Game cannot be drawn.
+    pub fn new_headless(gd: GameData) -> Game {
+        let mut game = Game::new(gd);
+        game.state = GameState::WaitingForNextTurn;
+        game.frequent_tex = FrequentTextures::empty();
+        game
+    }
+}
+
+/// Advance turns until the player's n_turns turns are processed or the player dies.
+/// Animations and dialog requests except game over are discarded.
+pub fn run(game: &mut Game, n_turns: u64) -> HeadlessReport {
+    let mut report = HeadlessReport {
+        turns: 0,
+        player_dead: false,
+    };
+
+    while report.turns < n_turns {
+        if game.state == GameState::WaitingForNextTurn {
+            game.advance_turn();
+        }
+        while game.pop_animation().is_some() {}
+
+        if let Some(DialogOpenRequest::GameOver) = game.pop_dialog_open_request() {
+            report.player_dead = true;
+            break;
+        }
+
+        if game.state == GameState::PlayerTurn {
+            process_turn_by_ai(game, CharaId::Player, PLAYER_STAND_IN_AI);
+            game.finish_player_turn();
+            report.turns += 1;
+        }
+    }
+
+    report
+}
+
+/// Generate a new game and run it in headless mode.
+/// If the "audio" feature is enabled, audio must be initialized by audio::init_null() before calling this.
+pub fn run_new_game(n_turns: u64) -> HeadlessReport {
+    let mut builder = NewGameBuilder::new();
+    builder.set_player_name("headless");
+    builder.set_chara_class(CharaClass::Adventurer);
+    let mut game = Game::new_headless(builder.build());
+
+    let report = run(&mut game, n_turns);
+    info!(
+        "Headless run finished: {} turns, player dead: {}, date: {:?}",
+        report.turns,
+        report.player_dead,
+        game.gd.time.current_date()
+    );
+    report
+}
+
+/// Process the commands of a replay in headless mode, and check the end state if recorded.
+/// Only commands on the map are supported, because others need windows of a frontend.
+pub fn run_replay(replay: Replay) -> Result<Game, Box<dyn Error>> {
+    let mut game = Game::new_headless(replay.gd);
+    game.state = GameState::PlayerTurn;
+    game.update_before_player_turn();
+    if replay.header.new_game {
+        game.start_new_game();
+    }
+
+    for (i, command) in replay.commands.iter().enumerate() {
+        let result = wait_player_turn(&mut game).and_then(|player_dead| {
+            if player_dead {
+                return Err("the player is dead before the replay ends".into());
+            }
+            process_command(&mut game, command)
+        });
+        if let Err(e) = result {
+            return Err(format!("replay stopped at command {} {:?}: {}", i, command, e).into());
+        }
+    }
+    wait_player_turn(&mut game)?;
+
+    if let Some(end_state) = replay.end_state.as_ref() {
+        let replayed = EndState::new(&game.gd);
+        if *end_state != replayed {
+            return Err(format!(
+                "the end state differs from the recorded one\nrecorded: {:?}\nreplayed: {:?}",
+                end_state, replayed
+            )
+            .into());
+        }
+    } else {
+        warn!("The replay has no end state to check");
+    }
+    info!(
+        "Headless replay finished: {} commands, date: {:?}",
+        replay.commands.len(),
+        game.gd.time.current_date()
+    );
+    Ok(game)
+}
+
+/// Advance turns until the player's turn. Returns true if the player is dead.
+fn wait_player_turn(game: &mut Game) -> Result<bool, Box<dyn Error>> {
+    loop {
+        while game.pop_animation().is_some() {}
+        match game.pop_dialog_open_request() {
+            Some(DialogOpenRequest::GameOver) => return Ok(true),
+            Some(_) => return Err("a dialog is opened".into()),
+            None => (),
+        }
+        if game.state == GameState::PlayerTurn {
+            return Ok(false);
+        }
+        game.advance_turn();
+    }
+}
+
+/// Process a command in the same way as frontends do on the map.
+fn process_command(game: &mut Game, command: &Command) -> Result<(), Box<dyn Error>> {
+    let mut pa = DoPlayerAction::new(game);
+    match *command {
+        Command::Move { dir } => {
+            pa.try_move(dir);
+        }
+        Command::MoveTo { dest } => {
+            pa.move_to(dest);
+        }
+        Command::Shoot { target } => {
+            pa.shoot(target);
+        }
+        Command::UseTool { target } => {
+            pa.use_tool(target);
+        }
+        Command::Enter => {
+            if pa.gd().on_map_entrance() {
+                pa.goto_next_floor(Direction::none(), true);
+            }
+        }
+        // Only the screen is changed by these commands
+        Command::Cancel
+        | Command::RotateWindowRight
+        | Command::RotateWindowLeft
+        | Command::MouseWheel { .. }
+        | Command::MouseState { .. } => (),
+        _ => return Err("the command needs windows".into()),
+    }
+    Ok(())
+}
+
+#[test]
+fn headless_run_test() {
+    use super::testutil;
+    use geom::Vec2d;
+
+    let _lock = testutil::init();
+    let mut gd = testutil::gen_gd();
+    let npcs = [
+        testutil::add_npc(&mut gd, Vec2d(8, 6), Relationship::HOSTILE),
+        testutil::add_npc(&mut gd, Vec2d(12, 9), Relationship::NEUTRAL),
+    ];
+    let start_time = gd.time.current_time();
+    let mut game = Game::new_headless(gd);
+
+    let n_turns = 30;
+    let report = run(&mut game, n_turns);
+    assert!(report.player_dead || report.turns == n_turns);
+
+    let gd = &game.gd;
+    assert!(gd.time.current_time() > start_time);
+    let map = gd.get_current_map();
+    let mut n_charas = 0;
+    for &cid in map.iter_charaid() {
+        let pos = map
+            .chara_pos(cid)
+            .expect("chara on the map has no position");
+        assert_eq!(map.get_chara(pos), Some(cid));
+        let chara = gd.chara.get(cid);
+        assert!(chara.hp <= chara.attr.max_hp);
+        n_charas += 1;
+    }
+    let n_tiles = map
+        .tile
+        .iter_idx()
+        .filter(|&p| map.tile[p].chara.is_some())
+        .count();
+    assert_eq!(n_charas, n_tiles);
+    // Nobody is killed without a fight except by the hostile one
+    assert!(map.chara_pos(npcs[1]).is_some() || report.player_dead);
+}
+
+#[test]
+fn replay_round_trip_test() {
+    use super::replay::{self, Recorder};
+    use super::testutil;
+    use geom::Vec2d;
+
+    let _lock = testutil::init();
+    let mut gd = testutil::gen_gd();
+    testutil::add_npc(&mut gd, Vec2d(6, 4), Relationship::HOSTILE);
+    testutil::add_npc(&mut gd, Vec2d(10, 8), Relationship::NEUTRAL);
+    let replay_dir = testutil::temp_dir().join("replay");
+
+    let mut game = Game::new(gd);
+    let mut recorder = Recorder::start(&replay_dir, &mut game, false).unwrap();
+    game.update_before_player_turn();
+    let dirs = [
+        Direction::E,
+        Direction::SE,
+        Direction::S,
+        Direction::E,
+        Direction::W,
+    ];
+    for &dir in dirs.iter().cycle().take(15) {
+        if wait_player_turn(&mut game).unwrap() {
+            break;
+        }
+        let command = Command::Move { dir };
+        recorder.record(&command);
+        process_command(&mut game, &command).unwrap();
+    }
+    wait_player_turn(&mut game).unwrap();
+    recorder.finish(&game.gd);
+
+    let replay = replay::load(&replay_dir).unwrap();
+    assert!(replay.end_state.is_some());
+    let replayed = run_replay(replay).unwrap();
+
+    let (gd, replayed_gd) = (&game.gd, &replayed.gd);
+    assert_eq!(EndState::new(gd), EndState::new(replayed_gd));
+    assert_eq!(gd.player_pos(), replayed_gd.player_pos());
+    let map = gd.get_current_map();
+    for &cid in map.iter_charaid() {
+        let (chara, replayed_chara) = (gd.chara.get(cid), replayed_gd.chara.get(cid));
+        assert_eq!(chara.hp, replayed_chara.hp);
+        assert_eq!(chara.sp, replayed_chara.sp);
+        assert_eq!(chara.wait_time, replayed_chara.wait_time);
+        assert_eq!(chara.status, replayed_chara.status);
+    }
+}
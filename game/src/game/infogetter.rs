@@ -3,6 +3,8 @@ use common::gamedata::*;
 use common::gobj;
 use common::objholder::*;
 use geom::*;
+use rules::weather::factor;
+use rules::RULES;
 
 /// Helper functions to get information for event processing and drawing
 pub trait InfoGetter {
@@ -26,6 +28,10 @@ pub trait InfoGetter {
     fn is_item_on_player_tile(&self) -> bool;
     /// Judge given map is open-air or not
     fn is_open_air(&self, mid: MapId) -> bool;
+    /// Current time is night or not
+    fn is_night(&self) -> bool;
+    /// View range of the character revised by the time of day and the weather
+    fn view_range(&self, cid: CharaId) -> i32;
     /// Get the number of specified item player has
     fn has_item(&self, idx: ItemIdx) -> u32;
     /// Get the item location of specified item
@@ -105,6 +111,24 @@ impl InfoGetter for GameData {
         }
     }
 
+    fn is_night(&self) -> bool {
+        RULES.params.is_night(self.time.current_date().hour)
+    }
+
+    fn view_range(&self, cid: CharaId) -> i32 {
+        let view_range = self.chara.get(cid).attr.view_range;
+        let mid = self.get_current_mapid();
+        if !self.is_open_air(mid) {
+            return view_range;
+        }
+        let weather = self.region.get(mid.rid()).weather.kind;
+        let mut r = view_range as f32 * factor(&RULES.weather.view_range_factor, weather);
+        if self.is_night() {
+            r *= RULES.params.night_view_range_factor;
+        }
+        std::cmp::max(r as i32, 1)
+    }
+
     fn has_item(&self, idx: ItemIdx) -> u32 {
         let il = self.get_item_list(ItemListLocation::Chara {
             cid: CharaId::Player,
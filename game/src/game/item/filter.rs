@@ -1,5 +1,6 @@
 use common::gamedata::*;
 use common::gobj;
+use rules::RULES;
 
 /// Used for creating filtered list and saving filtering state
 #[derive(Clone, Copy, Debug)]
@@ -9,6 +10,7 @@ pub struct ItemFilter {
     pub flags: ItemFlags,
     pub kind_rough: Option<ItemKindRough>,
     pub usable: bool,
+    pub throwable: bool,
 }
 
 impl ItemFilter {
@@ -49,6 +51,10 @@ impl ItemFilter {
             return false;
         }
 
+        if self.throwable && !RULES.combat.throwable_kinds.contains(&o.kind.rough()) {
+            return false;
+        }
+
         true
     }
 
@@ -71,6 +77,11 @@ impl ItemFilter {
         self.usable = usable;
         self
     }
+
+    pub fn throwable(mut self, throwable: bool) -> ItemFilter {
+        self.throwable = throwable;
+        self
+    }
 }
 
 impl Default for ItemFilter {
@@ -81,6 +92,7 @@ impl Default for ItemFilter {
             flags: ItemFlags::empty(),
             kind_rough: None,
             usable: false,
+            throwable: false,
         }
     }
 }
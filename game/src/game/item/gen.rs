@@ -1,7 +1,7 @@
 use common::gamedata::*;
 use common::gobj;
 use common::objholder::ItemIdx;
-use rng;
+use rng::{self, RngStream};
 
 /// Generate new item on dungeon floor
 pub fn gen_dungeon_item(floor_level: u32) -> Item {
@@ -11,18 +11,40 @@ pub fn gen_dungeon_item(floor_level: u32) -> Item {
 /// Generate new item by level.
 /// f is weight adjustment function.
 pub fn gen_item_by_level<F: FnMut(&ItemObject) -> f64>(level: u32, f: F, is_shop: bool) -> Item {
-    let idx = choose_item_by_floor_level(level, f, is_shop);
+    let idx = choose_item_by_floor_level(level, f, is_shop).expect("No item to generate");
 
     gen_item_from_idx(idx)
 }
 
+/// Generate new item by level.
+/// Returns None if there is no item that has positive weight.
+pub fn gen_item_by_level_checked<F: FnMut(&ItemObject) -> f64>(
+    level: u32,
+    f: F,
+    is_shop: bool,
+) -> Option<Item> {
+    let idx = choose_item_by_floor_level(level, f, is_shop)?;
+
+    Some(gen_item_from_idx(idx))
+}
+
 /// Choose item by floor level.
 /// f is weight adjustment function.
 fn choose_item_by_floor_level<F: FnMut(&ItemObject) -> f64>(
+    floor_level: u32,
+    f: F,
+    is_shop: bool,
+) -> Option<ItemIdx> {
+    rng::with_stream(RngStream::ItemGen, || {
+        choose_item_by_floor_level_on_stream(floor_level, f, is_shop)
+    })
+}
+
+fn choose_item_by_floor_level_on_stream<F: FnMut(&ItemObject) -> f64>(
     floor_level: u32,
     mut f: F,
     is_shop: bool,
-) -> ItemIdx {
+) -> Option<ItemIdx> {
     let items = &gobj::get_objholder().item;
 
     // Sum up gen_weight * weight_dist * dungeon_adjustment
@@ -42,7 +64,10 @@ fn choose_item_by_floor_level<F: FnMut(&ItemObject) -> f64>(
         }
     }
 
-    assert!(sum > 0.0);
+    // NaN weights also mean no item can be chosen
+    if sum.is_nan() || sum <= 0.0 {
+        return None;
+    }
 
     // Choose one item
     let r = rng::gen_range(0.0, sum);
@@ -55,11 +80,11 @@ fn choose_item_by_floor_level<F: FnMut(&ItemObject) -> f64>(
         };
         sum += weight_dist.calc(item.gen_level) * gen_weight as f64 * f(item);
         if r < sum {
-            return ItemIdx::from_usize(i);
+            return Some(ItemIdx::from_usize(i));
         }
     }
 
-    ItemIdx::from_usize(first_available_item_idx.unwrap())
+    Some(ItemIdx::from_usize(first_available_item_idx.unwrap()))
 }
 
 struct CalcLevelWeightDist {
@@ -111,7 +136,10 @@ pub fn gen_item_from_idx(idx: ItemIdx) -> Item {
 
 /// Generate a magic device item
 fn gen_magic_device(mut item: Item, item_obj: &ItemObject) -> Item {
-    let charge_n: u32 = rng::gen_range_inclusive(item_obj.charge[0], item_obj.charge[1]).into();
+    let charge_n: u32 = rng::with_stream(RngStream::ItemGen, || {
+        rng::gen_range_inclusive(item_obj.charge[0], item_obj.charge[1])
+    })
+    .into();
     item.attributes.push(ItemAttribute::Charge { n: charge_n });
     item
 }
@@ -28,7 +28,12 @@ impl ItemInfoText {
                     desc_text.push((UI_IMG_ID_ITEM_INFO, t));
                 }
             }
-            ItemKind::MagicDevice => {}
+            ItemKind::MagicDevice => {
+                if obj.element != Element::Physical {
+                    let t = misc_txt_format!("item_info_text-element"; element=obj.element);
+                    desc_text.push((UI_IMG_ID_ITEM_INFO, t));
+                }
+            }
             ItemKind::Weapon(weapon_kind) => {
                 if weapon_kind.is_melee() {
                     let t = misc_txt_format!(
@@ -39,6 +44,10 @@ impl ItemInfoText {
                         "item_info_text-ranged_weapon"; dice_x=obj.dice_x, dice_n=obj.dice_n);
                     desc_text.push((UI_IMG_ID_ITEM_INFO, t));
                 }
+                if obj.element != Element::Physical {
+                    let t = misc_txt_format!("item_info_text-element"; element=obj.element);
+                    desc_text.push((UI_IMG_ID_ITEM_INFO, t));
+                }
             }
             ItemKind::Armor(_) => {
                 let d0 = format!("{:+}", obj.def[Element::Physical]);
@@ -57,6 +66,17 @@ impl ItemInfoText {
             ItemKind::Special => {}
             ItemKind::Material => {}
             ItemKind::Object => {}
+            ItemKind::Ammo(_) => {
+                if obj.dice_n > 0 {
+                    let t = misc_txt_format!(
+                        "item_info_text-ammo"; dice_x=obj.dice_x, dice_n=obj.dice_n);
+                    desc_text.push((UI_IMG_ID_ITEM_INFO, t));
+                }
+                if obj.element != Element::Physical {
+                    let t = misc_txt_format!("item_info_text-element"; element=obj.element);
+                    desc_text.push((UI_IMG_ID_ITEM_INFO, t));
+                }
+            }
         }
 
         ItemInfoText {
@@ -15,6 +15,8 @@ pub trait ItemEx {
     fn w(&self) -> u32;
     fn charge(&self) -> Option<u32>;
     fn charge_mut(&mut self) -> Option<&mut u32>;
+    fn growth(&self) -> u32;
+    fn add_growth(&mut self, n: u32);
 }
 
 impl ItemEx for Item {
@@ -57,6 +59,25 @@ impl ItemEx for Item {
         }
         None
     }
+
+    fn growth(&self) -> u32 {
+        for attr in &self.attributes {
+            if let ItemAttribute::Growth { n } = attr {
+                return *n;
+            }
+        }
+        0
+    }
+
+    fn add_growth(&mut self, n: u32) {
+        for attr in &mut self.attributes {
+            if let ItemAttribute::Growth { n: growth } = attr {
+                *growth += n;
+                return;
+            }
+        }
+        self.attributes.push(ItemAttribute::Growth { n });
+    }
 }
 
 pub trait ItemListEx {
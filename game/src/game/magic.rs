@@ -0,0 +1,238 @@
+use super::combat::{attack_target, AttackParams, DamageKind};
+use crate::game::extrait::*;
+use crate::game::{Game, InfoGetter};
+use common::gamedata::*;
+use geom::*;
+use rules::RULES;
+
+pub fn do_magic(
+    game: &mut Game,
+    cid: CharaId,
+    target: Option<Vec2d>,
+    me: &MagicalEffect,
+    element: Element,
+    power: f64,
+) {
+    match me {
+        MagicalEffect::None => {
+            return;
+        }
+        MagicalEffect::Arrow => {
+            arrow(game, cid, target, element, power);
+        }
+        MagicalEffect::Ball { radius } => {
+            if let Some(target_pos) = target_pos(game, cid, target) {
+                let tiles = ball_tiles(game.gd.get_current_map(), target_pos, *radius);
+                game.anim_queue.push_magic_ball(tiles.clone());
+                damage_area(game, cid, &tiles, element, power);
+            }
+        }
+        MagicalEffect::Beam { length } => {
+            if let Some(target_pos) = target_pos(game, cid, target) {
+                let start = game.gd.chara_pos(cid).unwrap();
+                let tiles = beam_tiles(game.gd.get_current_map(), start, target_pos, *length);
+                game.anim_queue.push_magic_beam(tiles.clone());
+                damage_area(game, cid, &tiles, element, power);
+            }
+        }
+        MagicalEffect::Cone { length } => {
+            if let Some(target_pos) = target_pos(game, cid, target) {
+                let start = game.gd.chara_pos(cid).unwrap();
+                let tiles = cone_tiles(game.gd.get_current_map(), start, target_pos, *length);
+                game.anim_queue.push_magic_cone(tiles.clone());
+                damage_area(game, cid, &tiles, element, power);
+            }
+        }
+        MagicalEffect::SelfBuff { status } => {
+            let pos = game.gd.chara_pos(cid).unwrap();
+            game.anim_queue.push_magic_buff(pos);
+            let chara = game.gd.chara.get_mut(cid);
+            chara.add_status(CharaStatus::new(status, None));
+        }
+    }
+}
+
+/// The arrow flies to the target tile, and hits the character on it
+pub fn arrow(game: &mut Game, cid: CharaId, target: Option<Vec2d>, element: Element, power: f64) {
+    let target_id = target.and_then(|pos| game.gd.get_current_map().get_chara(pos));
+    let (target_pos, target_id) = match (target, target_id) {
+        (Some(target_pos), Some(target_id)) => (target_pos, target_id),
+        _ => {
+            let chara = game.gd.chara.get(cid);
+            game_log_i!("no-target"; chara=chara);
+            return;
+        }
+    };
+    let start = game.gd.get_current_map().chara_pos(cid).unwrap();
+
+    game.anim_queue.push_magic_arrow(start, target_pos);
+
+    let target = game.gd.chara.get(target_id);
+    game_log!("arrow-hit"; chara=target);
+
+    attack_target(game, magic_attack_params(cid, element, power), target_id);
+}
+
+fn magic_attack_params(cid: CharaId, element: Element, power: f64) -> AttackParams {
+    AttackParams {
+        attacker_id: Some(cid),
+        kind: DamageKind::RangedAttack,
+        element,
+        attack_power: power,
+        accuracy_power: None,
+        critical: 0.0,
+        penetration: 0.0,
+    }
+}
+
+fn target_pos(game: &Game, cid: CharaId, target: Option<Vec2d>) -> Option<Vec2d> {
+    if target.is_none() {
+        let chara = game.gd.chara.get(cid);
+        game_log_i!("no-target"; chara=chara);
+    }
+    target
+}
+
+/// Damage characters in the tiles.
+/// Characters are damaged only if their relationship from the caster is listed in rules.
+fn damage_area(game: &mut Game, cid: CharaId, tiles: &[Vec2d], element: Element, power: f64) {
+    let caster_rel = game.gd.chara.get(cid).rel;
+    let targets: Vec<CharaId> = tiles
+        .iter()
+        .filter_map(|p| game.gd.get_current_map().get_chara(*p))
+        .filter(|target_id| *target_id != cid)
+        .filter(|target_id| {
+            let rel = caster_rel.relative(game.gd.chara.get(*target_id).rel);
+            RULES.magic.area_damage_relationships.contains(&rel)
+        })
+        .collect();
+
+    for target_id in targets {
+        attack_target(game, magic_attack_params(cid, element, power), target_id);
+    }
+}
+
+fn is_magic_passable(map: &Map, p: Vec2d) -> bool {
+    map.is_inside(p) && map.tile[p].wall.is_empty()
+}
+
+/// Tiles within the radius that are in sight from the center
+fn ball_tiles(map: &Map, center: Vec2d, radius: u32) -> Vec<Vec2d> {
+    let r = radius as i32;
+    let mut tiles = Vec::new();
+    // The range of shadowcasting is in manhattan distance, so it covers the circle by 2r
+    shadowcasting(
+        center,
+        2 * r,
+        |p| !is_magic_passable(map, p),
+        |p| {
+            let d = p - center;
+            if d.0 * d.0 + d.1 * d.1 <= r * r + r && is_magic_passable(map, p) {
+                tiles.push(p);
+            }
+        },
+    );
+    // Shadowcasting visits tiles on the boundaries of quadrants twice
+    tiles.sort_by_key(|p| (p.1, p.0));
+    tiles.dedup();
+    tiles
+}
+
+/// The beam goes straight until it reaches a wall or the length
+fn beam_tiles(map: &Map, start: Vec2d, target: Vec2d, length: u32) -> Vec<Vec2d> {
+    let d = target - start;
+    let n = std::cmp::max(d.0.abs(), d.1.abs());
+    if n == 0 {
+        return Vec::new();
+    }
+    let length = length as i32;
+    let end = start + (d.0 * length / n, d.1 * length / n);
+
+    LineIter::new(start, end)
+        .skip(1)
+        .take_while(|p| is_magic_passable(map, *p))
+        .collect()
+}
+
+/// Tiles within the length and 45 degrees from the direction to the target
+fn cone_tiles(map: &Map, start: Vec2d, target: Vec2d, length: u32) -> Vec<Vec2d> {
+    let d = target - start;
+    if d == Vec2d(0, 0) {
+        return Vec::new();
+    }
+    let dir = (d.0 as f32, d.1 as f32);
+    let dir_len = (dir.0 * dir.0 + dir.1 * dir.1).sqrt();
+    let l = length as i32;
+
+    RectIter::new(start - (l, l), start + (l, l))
+        .filter(|p| {
+            let v = *p - start;
+            let v = (v.0 as f32, v.1 as f32);
+            let v_len = (v.0 * v.0 + v.1 * v.1).sqrt();
+            if v_len == 0.0 || v_len > length as f32 + 0.5 {
+                return false;
+            }
+            let cos = (v.0 * dir.0 + v.1 * dir.1) / (v_len * dir_len);
+            cos >= std::f32::consts::FRAC_1_SQRT_2 - 0.01 && is_magic_passable(map, *p)
+        })
+        .collect()
+}
+
+#[test]
+fn ball_tiles_test() {
+    use super::testutil;
+
+    let _lock = testutil::init();
+    let mut gd = testutil::gen_gd();
+    let center = Vec2d(8, 6);
+    let tiles = ball_tiles(gd.get_current_map(), center, 2);
+    assert_eq!(tiles.len(), 21);
+    assert!(tiles.contains(&center));
+    assert!(!tiles.contains(&Vec2d(10, 8)));
+
+    // Tiles behind the wall are not in sight from the center
+    let walls: Vec<Vec2d> = (3..=9).map(|y| Vec2d(9, y)).collect();
+    testutil::set_walls(&mut gd, &walls);
+    let tiles = ball_tiles(gd.get_current_map(), center, 2);
+    assert_eq!(tiles.len(), 21 - 5 - 3);
+    assert!(tiles.iter().all(|p| p.0 < 9));
+}
+
+#[test]
+fn beam_tiles_test() {
+    use super::testutil;
+
+    let _lock = testutil::init();
+    let mut gd = testutil::gen_gd();
+    let start = Vec2d(1, 1);
+    let tiles = beam_tiles(gd.get_current_map(), start, Vec2d(3, 1), 5);
+    assert_eq!(tiles, (2..=6).map(|x| Vec2d(x, 1)).collect::<Vec<_>>());
+    assert!(beam_tiles(gd.get_current_map(), start, start, 5).is_empty());
+
+    testutil::set_walls(&mut gd, &[Vec2d(5, 1)]);
+    let tiles = beam_tiles(gd.get_current_map(), start, Vec2d(3, 1), 5);
+    assert_eq!(tiles, (2..=4).map(|x| Vec2d(x, 1)).collect::<Vec<_>>());
+}
+
+#[test]
+fn cone_tiles_test() {
+    use super::testutil;
+
+    let _lock = testutil::init();
+    let mut gd = testutil::gen_gd();
+    let start = Vec2d(1, 6);
+    let tiles = cone_tiles(gd.get_current_map(), start, Vec2d(5, 6), 3);
+    assert!(tiles.contains(&Vec2d(4, 6)));
+    assert!(tiles.contains(&Vec2d(3, 8)));
+    assert!(!tiles.contains(&Vec2d(5, 6)));
+    assert!(!tiles.contains(&Vec2d(2, 8)));
+    assert!(tiles.iter().all(|p| {
+        let v = *p - start;
+        v.0 >= v.1.abs()
+    }));
+    assert!(cone_tiles(gd.get_current_map(), start, start, 3).is_empty());
+
+    testutil::set_walls(&mut gd, &[Vec2d(2, 6)]);
+    let tiles = cone_tiles(gd.get_current_map(), start, Vec2d(5, 6), 3);
+    assert!(!tiles.contains(&Vec2d(2, 6)));
+}
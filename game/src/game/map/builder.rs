@@ -3,6 +3,7 @@ use common::gamedata::map::*;
 use common::gobj;
 use common::objholder::*;
 use geom::*;
+use rng::{get_rng, Rng};
 use rules::floor_gen::*;
 use rules::RULES;
 
@@ -45,13 +46,31 @@ impl MapBuilder {
         } else {
             MapGenerator::new((self.w, self.h)).flat().generate()
         };
-        generated_map_to_map(
+        let rooms = generated_map.rooms.clone();
+        let mut map = generated_map_to_map(
             generated_map,
             self.tile,
             self.wall,
             self.floor,
             self.is_deepest_floor,
-        )
+        );
+
+        if let Some(floor_gen_params) = self.floor_gen_params {
+            if floor_gen_params.dark {
+                map.dark = true;
+                for (top_left, right_bottom) in rooms {
+                    if get_rng().gen_bool(floor_gen_params.lit_room_probability) {
+                        // Surrounding walls are also lit
+                        let top_left = top_left - (1, 1);
+                        let right_bottom = right_bottom + (1, 1);
+                        for p in RectIter::new(top_left, right_bottom) {
+                            map.tile[p].lit = true;
+                        }
+                    }
+                }
+            }
+        }
+        map
     }
 
     pub fn floor(mut self, floor: u32) -> MapBuilder {
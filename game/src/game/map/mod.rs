@@ -1,11 +1,12 @@
 pub mod builder;
 pub mod from_template;
+pub mod path;
 pub mod search;
 pub mod tile_info;
 
 use super::chara::gen::create_npc_chara;
 use super::item::gen::gen_dungeon_item;
-use super::Game;
+use super::{Game, InfoGetter};
 use crate::text::ToText;
 use common::basic::MAX_ITEM_FOR_DRAW;
 use common::gamedata::*;
@@ -80,8 +81,54 @@ pub fn switch_map(game: &mut Game, mid: MapId) {
         gd.get_current_map_mut()
             .locate_chara(CharaId::Player, new_player_pos);
     }
+    game.target_tile = None;
     crate::audio::play_sound("floor-change");
     super::view::update_view_map(game);
+    super::event::trigger_event(game, EventKind::EnterMap);
+}
+
+/// Move player to the position. If floor is given, move to the floor of the current site.
+/// Returns false if the floor does not exist or the position is not available.
+pub fn teleport_player(game: &mut Game, floor: Option<u32>, pos: Vec2d) -> bool {
+    let mid = game.gd.get_current_mapid();
+    let dest_mid = if let Some(floor) = floor {
+        if mid.is_region_map() || !game.gd.region.map_exist(mid.set_floor(floor)) {
+            warn!("teleport to unknown floor {}", floor);
+            return false;
+        }
+        mid.set_floor(floor)
+    } else {
+        mid
+    };
+
+    // Check the position before leaving the current map
+    if dest_mid != mid {
+        let save_dir = game.save_dir.as_ref().unwrap();
+        game.gd.region.preload_map(dest_mid, save_dir.join("maps"));
+    }
+    let player = game.gd.chara.get(CharaId::Player);
+    let dest_map = game.gd.region.get_map(dest_mid);
+    if !dest_map.is_passable(player, pos)
+        || dest_map
+            .get_chara(pos)
+            .is_some_and(|cid| cid != CharaId::Player)
+    {
+        warn!("teleport to invalid position {:?}", pos);
+        return false;
+    }
+
+    if dest_mid != mid {
+        switch_map(game, dest_mid);
+    }
+    if !game
+        .gd
+        .get_current_map_mut()
+        .locate_chara(CharaId::Player, pos)
+    {
+        return false;
+    }
+    super::view::update_view_map(game);
+    true
 }
 
 pub fn gen_npcs(gd: &mut GameData, mid: MapId, n: u32, floor_level: u32) {
@@ -89,10 +136,12 @@ pub fn gen_npcs(gd: &mut GameData, mid: MapId, n: u32, floor_level: u32) {
         SiteContent::AutoGenDungeon { dungeon_kind } => dungeon_kind,
         _ => DungeonKind::Ruin,
     };
+    // Night-only monsters always appear on underground floors
+    let night = gd.is_night() || !gd.is_open_air(mid);
 
     for _ in 0..n {
         if let Some(p) = choose_empty_tile(gd.region.get_map(mid)) {
-            let chara = create_npc_chara(dungeon_kind, floor_level);
+            let chara = create_npc_chara(dungeon_kind, floor_level, night);
             trace!("Generate new npc {}", chara.to_text());
             let cid = gd.add_chara_to_map(chara, mid);
             let map = gd.region.get_map_mut(mid);
@@ -209,3 +258,37 @@ pub fn update_observed_map(game: &mut Game) {
         }
     }
 }
+
+#[test]
+fn teleport_player_test() {
+    use super::testutil;
+
+    let _lock = testutil::init();
+    let mut gd = testutil::gen_gd();
+    let first_mid = gd.get_current_mapid();
+    let (w, h) = testutil::MAP_SIZE;
+    let second_mid = gd.add_map(Map::new(w, h), first_mid.sid(), 2);
+    gd.region.get_map_mut(second_mid).tile[Vec2d(3, 3)].wall =
+        WallIdxPP::new(gobj::id_to_idx(testutil::WALL_ID));
+    let mut game = Game::new_headless(gd);
+
+    // The player stays on the current map if the position is not available
+    assert!(!teleport_player(
+        &mut game,
+        Some(second_mid.floor()),
+        Vec2d(3, 3)
+    ));
+    assert_eq!(game.gd.get_current_mapid(), first_mid);
+    assert!(!teleport_player(&mut game, Some(99), Vec2d(4, 4)));
+    assert_eq!(game.gd.get_current_mapid(), first_mid);
+
+    assert!(teleport_player(
+        &mut game,
+        Some(second_mid.floor()),
+        Vec2d(4, 4)
+    ));
+    assert_eq!(game.gd.get_current_mapid(), second_mid);
+    assert_eq!(game.gd.player_pos(), Vec2d(4, 4));
+    assert!(teleport_player(&mut game, None, Vec2d(5, 4)));
+    assert_eq!(game.gd.player_pos(), Vec2d(5, 4));
+}
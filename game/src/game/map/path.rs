@@ -0,0 +1,326 @@
+//! Path finding on maps by A* algorithm
+
+use super::MapEx;
+use common::gamedata::*;
+use geom::*;
+use rules::RULES;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// Additional cost to pass through a tile another character stands on.
+/// Characters are not treated as walls because they may move or be swapped.
+const CHARA_TILE_COST: u32 = 4;
+
+/// Scratch buffers and the search budget for path finding.
+/// It is kept during playing to avoid allocating map-sized buffers at every search.
+pub struct PathFinder {
+    g_score: Array2d<Option<u32>>,
+    came_from: Array2d<Option<Vec2d>>,
+    /// Tiles whose scores are set in the last search
+    touched: Vec<Vec2d>,
+    open: BinaryHeap<Node>,
+    /// The number of tiles that can be searched until the next turn
+    budget: u32,
+}
+
+/// The reason why no path is returned
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PathError {
+    /// The budget of this turn is used up. The goal may be reachable in later turns.
+    BudgetExhausted,
+    /// The search is finished without finding any tile nearer to the goal
+    NotFound,
+}
+
+impl Default for PathFinder {
+    fn default() -> PathFinder {
+        PathFinder::new()
+    }
+}
+
+impl PathFinder {
+    pub fn new() -> PathFinder {
+        PathFinder {
+            g_score: Array2d::new(0, 0, None),
+            came_from: Array2d::new(0, 0, None),
+            touched: Vec::new(),
+            open: BinaryHeap::new(),
+            budget: 0,
+        }
+    }
+
+    /// Restore the budget shared by all searches in one turn
+    pub fn reset_budget(&mut self) {
+        self.budget = RULES.params.path_search_budget;
+    }
+
+    /// Search a path from start to goal on the map, and returns the direction for the first step.
+    /// If the goal is not reachable in the rest of the budget,
+    /// returns the direction to the nearest searched tile.
+    pub fn first_step_dir(
+        &mut self,
+        map: &Map,
+        chara: &Chara,
+        start: Vec2d,
+        goal: Vec2d,
+    ) -> Result<Direction, PathError> {
+        let path = self.find_path(map, chara, start, goal)?;
+        Ok(to_dir(path[0] - start))
+    }
+
+    /// Search a path from start to goal on the map.
+    /// The returned path does not include start, and is not empty.
+    pub fn find_path(
+        &mut self,
+        map: &Map,
+        chara: &Chara,
+        start: Vec2d,
+        goal: Vec2d,
+    ) -> Result<Vec<Vec2d>, PathError> {
+        let size = Vec2d(map.w as i32, map.h as i32);
+        self.astar(size, start, goal, |p| {
+            if p == goal {
+                // The goal is often the tile a target character stands on
+                if map.is_inside(p) {
+                    Some(1)
+                } else {
+                    None
+                }
+            } else if !map.is_passable(chara, p) {
+                None
+            } else if map.tile[p].chara.is_some() {
+                Some(1 + CHARA_TILE_COST)
+            } else {
+                Some(1)
+            }
+        })
+    }
+
+    /// A* search on a grid of given size with 8 directions movement.
+    /// cost() returns the cost to enter the tile, or None if the tile is impassable.
+    fn astar<F>(
+        &mut self,
+        size: Vec2d,
+        start: Vec2d,
+        goal: Vec2d,
+        mut cost: F,
+    ) -> Result<Vec<Vec2d>, PathError>
+    where
+        F: FnMut(Vec2d) -> Option<u32>,
+    {
+        if start == goal || size.0 <= 0 || size.1 <= 0 {
+            return Err(PathError::NotFound);
+        }
+        let in_range = |p: Vec2d| 0 <= p.0 && p.0 < size.0 && 0 <= p.1 && p.1 < size.1;
+        if !in_range(start) {
+            return Err(PathError::NotFound);
+        }
+        if self.budget == 0 {
+            return Err(PathError::BudgetExhausted);
+        }
+        let heuristic = |p: Vec2d| std::cmp::max((p.0 - goal.0).abs(), (p.1 - goal.1).abs()) as u32;
+        self.clear(size);
+
+        let mut nearest = (heuristic(start), start);
+        let mut exhausted = false;
+        self.g_score[start] = Some(0);
+        self.touched.push(start);
+        self.open.push(Node {
+            f: heuristic(start),
+            g: 0,
+            pos: start,
+        });
+
+        while let Some(Node { g, pos, .. }) = self.open.pop() {
+            if self.g_score[pos].is_some_and(|s| s < g) {
+                continue; // Already visited by a cheaper path
+            }
+            if pos == goal {
+                nearest = (0, pos);
+                break;
+            }
+            if self.budget == 0 {
+                exhausted = true;
+                break;
+            }
+            self.budget -= 1;
+
+            for dir in &Direction::EIGHT_DIRS {
+                let next = pos + dir.as_vec();
+                if !in_range(next) {
+                    continue;
+                }
+                let c = if let Some(c) = cost(next) {
+                    c
+                } else {
+                    continue;
+                };
+                let next_g = g + c;
+                if self.g_score[next].map_or(true, |s| next_g < s) {
+                    if self.g_score[next].is_none() {
+                        self.touched.push(next);
+                    }
+                    self.g_score[next] = Some(next_g);
+                    self.came_from[next] = Some(pos);
+                    let h = heuristic(next);
+                    if h < nearest.0 {
+                        nearest = (h, next);
+                    }
+                    self.open.push(Node {
+                        f: next_g + h,
+                        g: next_g,
+                        pos: next,
+                    });
+                }
+            }
+        }
+
+        // Reconstruct the path from the goal or the nearest tile to the goal
+        let mut path = Vec::new();
+        let mut p = nearest.1;
+        while p != start {
+            path.push(p);
+            p = self.came_from[p].expect("searched tile has no previous tile");
+        }
+        if path.is_empty() {
+            return Err(if exhausted {
+                PathError::BudgetExhausted
+            } else {
+                PathError::NotFound
+            });
+        }
+        path.reverse();
+        Ok(path)
+    }
+
+    /// Clear the scores of the last search. Buffers are reallocated only if the size is changed.
+    fn clear(&mut self, size: Vec2d) {
+        let (w, h) = (size.0 as u32, size.1 as u32);
+        if self.g_score.size() != (w, h) {
+            self.g_score = Array2d::new(w, h, None);
+            self.came_from = Array2d::new(w, h, None);
+        } else {
+            for p in self.touched.iter() {
+                self.g_score[*p] = None;
+                self.came_from[*p] = None;
+            }
+        }
+        self.touched.clear();
+        self.open.clear();
+    }
+}
+
+#[derive(PartialEq, Eq)]
+struct Node {
+    /// Estimated total cost
+    f: u32,
+    g: u32,
+    pos: Vec2d,
+}
+
+impl Ord for Node {
+    fn cmp(&self, other: &Node) -> Ordering {
+        // Reversed to pop the lowest cost node from BinaryHeap
+        other.f.cmp(&self.f).then_with(|| other.g.cmp(&self.g))
+    }
+}
+
+impl PartialOrd for Node {
+    fn partial_cmp(&self, other: &Node) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn to_dir(v: Vec2d) -> Direction {
+    let hdir = match v.0.signum() {
+        -1 => HDirection::Left,
+        1 => HDirection::Right,
+        _ => HDirection::None,
+    };
+    let vdir = match v.1.signum() {
+        -1 => VDirection::Up,
+        1 => VDirection::Down,
+        _ => VDirection::None,
+    };
+    Direction::new(hdir, vdir)
+}
+
+#[test]
+fn astar_test() {
+    // 0 is floor, 1 is wall
+    let grid = [
+        [0, 0, 0, 0, 0],
+        [1, 1, 1, 1, 0],
+        [0, 0, 0, 0, 0],
+        [0, 1, 1, 1, 1],
+        [0, 0, 0, 0, 0],
+    ];
+    let cost = |p: Vec2d| {
+        if grid[p.1 as usize][p.0 as usize] == 0 {
+            Some(1)
+        } else {
+            None
+        }
+    };
+    let mut path_finder = PathFinder::new();
+    path_finder.budget = 100;
+    let path = path_finder
+        .astar(Vec2d(5, 5), Vec2d(0, 0), Vec2d(4, 4), cost)
+        .unwrap();
+    assert_eq!(path.len(), 12);
+    assert_eq!(path[0], Vec2d(1, 0));
+    assert_eq!(*path.last().unwrap(), Vec2d(4, 4));
+
+    // Buffers are reused, and the budget is shared by searches in one turn
+    let used = 100 - path_finder.budget;
+    path_finder.budget = used + 5;
+    let path = path_finder
+        .astar(Vec2d(5, 5), Vec2d(0, 0), Vec2d(4, 4), cost)
+        .unwrap();
+    assert_eq!(path.len(), 12);
+    let path = path_finder
+        .astar(Vec2d(5, 5), Vec2d(0, 0), Vec2d(4, 4), cost)
+        .unwrap();
+    assert!(*path.last().unwrap() != Vec2d(4, 4));
+    assert_eq!(path_finder.budget, 0);
+    assert_eq!(
+        path_finder.astar(Vec2d(5, 5), Vec2d(0, 0), Vec2d(4, 4), cost),
+        Err(PathError::BudgetExhausted)
+    );
+
+    // The goal is surrounded by walls
+    path_finder.budget = 100;
+    let cost = |p: Vec2d| if p.0 == 2 { None } else { Some(1) };
+    let path = path_finder
+        .astar(Vec2d(5, 5), Vec2d(0, 2), Vec2d(4, 2), cost)
+        .unwrap();
+    assert_eq!(*path.last().unwrap(), Vec2d(1, 2));
+
+    // No tile nearer to the goal is reachable
+    let cost = |p: Vec2d| if p.0 == 1 { None } else { Some(1) };
+    assert_eq!(
+        path_finder.astar(Vec2d(5, 5), Vec2d(0, 2), Vec2d(4, 2), cost),
+        Err(PathError::NotFound)
+    );
+}
+
+#[test]
+fn find_path_test() {
+    use crate::game::testutil;
+
+    let _lock = testutil::init();
+    let mut gd = testutil::gen_gd();
+    // A wall which has a gap at the bottom
+    let walls: Vec<Vec2d> = (0..10).map(|y| Vec2d(5, y)).collect();
+    testutil::set_walls(&mut gd, &walls);
+
+    let map = gd.get_current_map();
+    let chara = gd.chara.get(CharaId::Player);
+    let mut path_finder = PathFinder::new();
+    path_finder.reset_budget();
+    let path = path_finder
+        .find_path(map, chara, Vec2d(1, 1), Vec2d(9, 1))
+        .unwrap();
+    assert_eq!(*path.last().unwrap(), Vec2d(9, 1));
+    assert!(path.iter().all(|p| p.0 != 5 || p.1 >= 10));
+}
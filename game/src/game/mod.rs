@@ -9,7 +9,9 @@ pub mod creation;
 mod debug_command;
 mod dungeon_gen;
 mod eval_expr;
+mod event;
 pub mod frequent_tex;
+pub mod headless;
 mod infogetter;
 pub mod item;
 mod magic;
@@ -19,14 +21,19 @@ mod npc;
 pub mod playeract;
 pub mod quest;
 mod region;
+pub mod replay;
 pub mod saveload;
 mod script;
 pub mod shop;
 pub mod site;
 mod skill;
+#[cfg(test)]
+mod testutil;
 mod town;
+pub mod training;
 mod turnloop;
 pub mod view;
+mod weather;
 
 pub use self::animation::Animation;
 pub use self::command::Command;
@@ -38,7 +45,9 @@ use common::gamedata::*;
 use common::gobj;
 use common::objholder::ScriptIdx;
 use geom::Vec2d;
+use std::collections::VecDeque;
 use std::path::PathBuf;
+use std::time::Instant;
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum GameState {
@@ -55,18 +64,25 @@ pub struct Game {
     anim_queue: anim_queue::AnimQueue,
     dialog_open_request: Option<DialogOpenRequest>,
     script: Option<ScriptEngine>,
+    /// Scripts started by event triggers, waiting for the running script to finish
+    event_scripts: VecDeque<(String, String)>,
     /// Player's current target of shot and similer actions
     target_chara: Option<CharaId>,
+    /// Player's current target tile, used when no character is targeted
+    target_tile: Option<Vec2d>,
     save_dir: Option<PathBuf>,
+    /// Used to count real play time
+    play_start: Instant,
     pub view_map: view::ViewMap,
     pub frequent_tex: self::frequent_tex::FrequentTextures,
+    path_finder: map::path::PathFinder,
 }
 
 impl Game {
     pub fn new(gd: GameData) -> Game {
         let save_dir = self::saveload::get_each_save_dir(&gd);
 
-        rng::reseed(crate::config::CONFIG.fix_rand);
+        init_rng(&gd);
 
         Game {
             gd,
@@ -74,10 +90,14 @@ impl Game {
             anim_queue: anim_queue::AnimQueue::default(),
             dialog_open_request: None,
             script: None,
+            event_scripts: VecDeque::new(),
             target_chara: None,
+            target_tile: None,
             save_dir: Some(save_dir),
+            play_start: Instant::now(),
             view_map: view::ViewMap::new(),
             frequent_tex: self::frequent_tex::FrequentTextures::new(),
+            path_finder: map::path::PathFinder::new(),
         }
     }
 
@@ -89,10 +109,14 @@ impl Game {
             anim_queue: anim_queue::AnimQueue::default(),
             dialog_open_request: None,
             script: None,
+            event_scripts: VecDeque::new(),
             target_chara: None,
+            target_tile: None,
             save_dir: None,
+            play_start: Instant::now(),
             view_map: view::ViewMap::new(),
             frequent_tex: self::frequent_tex::FrequentTextures::new(),
+            path_finder: map::path::PathFinder::new(),
         }
     }
 
@@ -112,7 +136,11 @@ impl Game {
 
     /// Update some parameters before starting player's turn
     pub fn update_before_player_turn(&mut self) {
+        self.path_finder.reset_budget();
+        weather::update_weather(&mut self.gd);
         view::update_view_map(self);
+        event::check_scheduled_events(self);
+        event::start_queued_script(self);
     }
 
     pub fn finish_player_turn(&mut self) {
@@ -137,7 +165,11 @@ impl Game {
     }
 
     pub fn start_script(&mut self, id: &str, cid: Option<CharaId>) {
-        self.script = Some(ScriptEngine::new(id, cid));
+        self.start_script_section(id, "start", cid);
+    }
+
+    pub fn start_script_section(&mut self, id: &str, section: &str, cid: Option<CharaId>) {
+        self.script = Some(ScriptEngine::new(id, section, cid));
         self.advance_script(None);
     }
 
@@ -177,6 +209,14 @@ impl Game {
                 self.request_dialog_open(DialogOpenRequest::Quest);
                 AdvanceScriptResult::Continue
             }
+            ExecResult::Train(cid) => {
+                self.request_dialog_open(DialogOpenRequest::Train { cid });
+                AdvanceScriptResult::Continue
+            }
+            ExecResult::Teleport(floor, pos) => {
+                map::teleport_player(self, floor, pos);
+                self.advance_script(None)
+            }
         }
     }
 
@@ -184,17 +224,32 @@ impl Game {
     /// If given tile position is empty, returns false.
     pub fn set_target(&mut self, pos: Vec2d) -> bool {
         let map = self.gd.get_current_map();
+        let player = self.gd.chara.get(CharaId::Player);
         if let Some(cid) = map.get_chara(pos) {
-            let player = self.gd.chara.get(CharaId::Player);
             let target = self.gd.chara.get(cid);
             game_log_i!("target-chara"; chara=player, target=target);
             self.target_chara = Some(cid);
+            self.target_tile = None;
+            true
+        } else if map.is_inside(pos) && self.view_map.get_tile_visible(pos) {
+            game_log_i!("target-tile"; chara=player);
+            self.target_chara = None;
+            self.target_tile = Some(pos);
             true
         } else {
             false
         }
     }
 
+    /// Position of the current target character, or the target tile
+    pub fn target_pos(&self) -> Option<Vec2d> {
+        if let Some(cid) = self.target_chara {
+            self.gd.chara_pos(cid)
+        } else {
+            self.target_tile
+        }
+    }
+
     /// Start new generated game
     pub fn start_new_game(&mut self) {
         const START_SCRIPT_ID: &str = "!start";
@@ -208,6 +263,19 @@ impl Game {
     }
 }
 
+/// Initialize random number streams by the world seed.
+/// Streams used during playing are also keyed by the current time,
+/// so that they are reproducible from the same save.
+fn init_rng(gd: &GameData) {
+    use rng::RngStream;
+
+    rng::set_world_seed(gd.world_seed);
+    let time_key = gd.time.current_time().as_secs();
+    for stream in &[RngStream::Default, RngStream::Combat, RngStream::Ai] {
+        rng::reseed_stream(*stream, time_key);
+    }
+}
+
 pub enum DialogOpenRequest {
     YesNo {
         callback: Box<dyn FnMut(&mut DoPlayerAction, bool)>,
@@ -226,6 +294,9 @@ pub enum DialogOpenRequest {
     ShopSell,
     PickUpItem,
     Quest,
+    Train {
+        cid: CharaId,
+    },
     GameOver,
 }
 
@@ -29,13 +29,17 @@ impl NewGameBuilder {
     }
 
     pub fn build(mut self) -> GameData {
-        rng::reseed(crate::config::CONFIG.fix_rand);
+        let world_seed = rng::gen_world_seed(crate::config::CONFIG.fix_rand);
+        rng::set_world_seed(world_seed);
+        self.gd.world_seed = world_seed;
         {
-            let mut gd = &mut self.gd;
+            let gd = &mut self.gd;
 
             gd.meta.set_save_name(self.player_name.as_ref().unwrap());
 
-            super::region::add_region(&mut gd, &RULES.newgame.start_region);
+            rng::with_stream(rng::RngStream::MapGen, || {
+                super::region::add_region(gd, &RULES.newgame.start_region);
+            });
 
             let mid = MapId::RegionMap {
                 rid: RegionId::default(),
@@ -43,7 +47,7 @@ impl NewGameBuilder {
             gd.set_initial_mapid(mid);
             let start_pos = RULES.newgame.start_pos;
 
-            super::region::gen_dungeon(&mut gd, mid.rid());
+            super::region::gen_dungeon(gd, mid.rid());
 
             let chara_template_id = &RULES.newgame.chara_template_table[&self.chara_class.unwrap()];
             let mut chara = super::chara::gen::create_chara(gobj::id_to_idx(chara_template_id), 1);
@@ -0,0 +1,315 @@
+//! Functions for NPC's AI and actions
+
+pub mod map_search;
+mod state;
+
+use super::action;
+use super::combat;
+use super::extrait::*;
+use super::map::path::PathError;
+use super::view::calc_visual_distance;
+use super::{Game, InfoGetter};
+use common::gamedata::*;
+use geom::*;
+use rng::*;
+use rules::RULES;
+
+pub fn process_npc_turn(game: &mut Game, cid: CharaId) {
+    let ai_kind = game.gd.chara.get(cid).ai.kind;
+    process_turn_by_ai(game, cid, ai_kind);
+}
+
+/// Process one turn of given character by given AI kind.
+/// The player character can be moved by this function in headless mode.
+pub fn process_turn_by_ai(game: &mut Game, cid: CharaId, ai_kind: NpcAIKind) {
+    with_stream(RngStream::Ai, || process_ai(game, cid, ai_kind));
+}
+
+fn process_ai(game: &mut Game, cid: CharaId, ai_kind: NpcAIKind) {
+    state::update_ai_state(game, cid);
+    if state::process_by_state(game, cid) {
+        return;
+    }
+
+    match ai_kind {
+        NpcAIKind::None => {
+            return;
+        }
+        NpcAIKind::NoMove => {
+            return;
+        }
+        _ => (),
+    }
+
+    if attack_by_ai_kind(game, cid, ai_kind) {
+        return;
+    }
+
+    if gen_range(0, 3) == 0 {
+        move_to_nearest_enemy(game, cid);
+    } else {
+        random_walk(game, cid);
+    }
+}
+
+/// Attack by ranged weapons or magic devices if the AI kind uses them.
+/// Returns true if the npc acted.
+fn attack_by_ai_kind(game: &mut Game, cid: CharaId, ai_kind: NpcAIKind) -> bool {
+    match ai_kind {
+        NpcAIKind::Ranged => ranged_attack(game, cid),
+        NpcAIKind::Caster => cast_learned_spell(game, cid) || release_magic_device(game, cid),
+        _ => false,
+    }
+}
+
+/// Move npc at random
+fn random_walk(game: &mut Game, cid: CharaId) {
+    let dir = Direction::new(
+        *[HDirection::Left, HDirection::None, HDirection::Right]
+            .choose(&mut get_rng())
+            .unwrap(),
+        *[VDirection::Up, VDirection::None, VDirection::Down]
+            .choose(&mut get_rng())
+            .unwrap(),
+    );
+    action::try_move(game, cid, dir);
+}
+
+/// Move npc to nearest enemy
+fn move_to_nearest_enemy(game: &mut Game, cid: CharaId) {
+    if let Some(target) = map_search::search_nearest_enemy(&game.gd, cid) {
+        if let (Some(pos), Some(target_pos)) = (game.gd.chara_pos(cid), game.gd.chara_pos(target)) {
+            let map = game.gd.get_current_map();
+            let chara = game.gd.chara.get(cid);
+            let dir = game
+                .path_finder
+                .first_step_dir(map, chara, pos, target_pos)
+                .unwrap_or_else(|_| map_search::dir_to_chara(&game.gd, target, pos));
+            action::try_move(game, cid, dir);
+        }
+    }
+}
+
+/// Move npc one step toward the destination.
+/// Returns false if there is no way to the destination.
+/// If the path search budget of this turn is used up, steps straight toward the destination.
+fn move_toward(game: &mut Game, cid: CharaId, dest: Vec2d) -> bool {
+    let pos = if let Some(pos) = game.gd.chara_pos(cid) {
+        pos
+    } else {
+        return false;
+    };
+    let map = game.gd.get_current_map();
+    let chara = game.gd.chara.get(cid);
+    match game.path_finder.first_step_dir(map, chara, pos, dest) {
+        Ok(dir) => action::try_move(game, cid, dir),
+        Err(PathError::BudgetExhausted) => {
+            action::try_move(game, cid, map_search::dir_2pos(pos, dest));
+            true
+        }
+        Err(PathError::NotFound) => false,
+    }
+}
+
+/// Search the nearest enemy that is in sight.
+/// On dark maps, enemies must be on lit tiles or adjacent.
+/// Returns the enemy's id and the distance to it.
+fn search_target_in_sight(game: &Game, cid: CharaId) -> Option<(CharaId, i32)> {
+    let target = map_search::search_nearest_enemy(&game.gd, cid)?;
+    let pos = game.gd.chara_pos(cid)?;
+    let target_pos = game.gd.chara_pos(target)?;
+    let map = game.gd.get_current_map();
+    let distance = calc_visual_distance(map, pos, target_pos, game.gd.view_range(cid))?;
+
+    if map.dark && distance > 1 && !game.view_map.is_lit(target_pos) {
+        return None;
+    }
+    Some((target, distance))
+}
+
+/// Shoot the nearest enemy, or step back if it is too close.
+/// Returns false if this npc cannot do ranged attacks now.
+fn ranged_attack(game: &mut Game, cid: CharaId) -> bool {
+    /// Ranged attackers try to keep this distance from the target
+    const KEEP_DISTANCE: i32 = 2;
+
+    if game
+        .gd
+        .chara
+        .get(cid)
+        .equip
+        .item(EquipSlotKind::RangedWeapon, 0)
+        .is_none()
+    {
+        return false;
+    }
+    let (target, distance) = if let Some(target) = search_target_in_sight(game, cid) {
+        target
+    } else {
+        return false;
+    };
+
+    if distance < KEEP_DISTANCE {
+        let pos = game.gd.chara_pos(cid).unwrap();
+        let target_pos = game.gd.chara_pos(target).unwrap();
+        let dir = map_search::dir_2pos(target_pos, pos);
+        let dest = pos + dir.as_vec();
+        let map = game.gd.get_current_map();
+        if map.is_passable(game.gd.chara.get(cid), dest) && map.get_chara(dest).is_none() {
+            return action::try_move(game, cid, dir);
+        }
+    }
+
+    combat::shot_target(game, cid, target).is_some()
+}
+
+/// Cast one of the learned attack spells to the nearest enemy.
+/// Returns false if this npc has no spell to cast now or no target.
+fn cast_learned_spell(game: &mut Game, cid: CharaId) -> bool {
+    let chara = game.gd.chara.get(cid);
+    let spells: Vec<&String> = chara
+        .spells
+        .iter()
+        .filter(|spell_id| {
+            RULES.spell.get(spell_id).is_some_and(|spell| {
+                !matches!(
+                    spell.effect,
+                    MagicalEffect::None | MagicalEffect::SelfBuff { .. }
+                ) && spell.cost <= chara.sp
+            })
+        })
+        .collect();
+    let spell_id = if let Some(spell_id) = spells.choose(&mut get_rng()) {
+        (*spell_id).clone()
+    } else {
+        return false;
+    };
+    let target = if let Some((target, _)) = search_target_in_sight(game, cid) {
+        target
+    } else {
+        return false;
+    };
+
+    let target_pos = game.gd.chara_pos(target);
+    action::spell::cast_spell(game, cid, &spell_id, target_pos)
+}
+
+/// Release a magic device to the nearest enemy.
+/// Returns false if this npc has no usable magic device or no target.
+fn release_magic_device(game: &mut Game, cid: CharaId) -> bool {
+    let item_location = {
+        let item_list = game.gd.get_item_list(ItemListLocation::Chara { cid });
+        item_list.iter().enumerate().find_map(|(i, (item, _))| {
+            let item_obj = item.obj();
+            if item_obj.kind == ItemKind::MagicDevice
+                && item_obj.magical_effect != MagicalEffect::None
+                && item.charge().is_some_and(|n| n > 0)
+            {
+                Some((ItemListLocation::Chara { cid }, i as u32))
+            } else {
+                None
+            }
+        })
+    };
+    let il = if let Some(il) = item_location {
+        il
+    } else {
+        return false;
+    };
+    let target = if let Some((target, _)) = search_target_in_sight(game, cid) {
+        target
+    } else {
+        return false;
+    };
+
+    let target_pos = game.gd.chara_pos(target);
+    action::release_item(game, il, cid, target_pos);
+    true
+}
+
+#[test]
+fn no_move_state_test() {
+    use super::testutil;
+
+    let _lock = testutil::init();
+    let mut gd = testutil::gen_gd();
+    let guard = testutil::add_npc(&mut gd, Vec2d(8, 6), Relationship::NEUTRAL);
+    let returning = testutil::add_npc(&mut gd, Vec2d(8, 9), Relationship::NEUTRAL);
+    for (cid, state, home) in [
+        (guard, AIState::Guard { range: 3 }, Vec2d(12, 6)),
+        (returning, AIState::ReturnHome, Vec2d(4, 9)),
+    ] {
+        let ai = &mut gd.chara.get_mut(cid).ai;
+        ai.kind = NpcAIKind::NoMove;
+        ai.state = state;
+        ai.default_state = state;
+        ai.home = Some(home);
+    }
+    let mut game = Game::new_headless(gd);
+    game.path_finder.reset_budget();
+
+    process_npc_turn(&mut game, guard);
+    process_npc_turn(&mut game, returning);
+    assert_eq!(game.gd.chara_pos(guard).unwrap().0, 9);
+    assert_eq!(game.gd.chara_pos(returning).unwrap().0, 7);
+}
+
+#[test]
+fn caster_spell_test() {
+    use super::testutil;
+
+    let _lock = testutil::init();
+    let mut gd = testutil::gen_gd();
+    let caster = testutil::add_npc(&mut gd, Vec2d(4, 1), Relationship::HOSTILE);
+    let chara = gd.chara.get_mut(caster);
+    chara.ai.kind = NpcAIKind::Caster;
+    chara.spells.push("magic_arrow".to_owned());
+    chara.sp = 100.0;
+    let mut game = Game::new_headless(gd);
+
+    process_npc_turn(&mut game, caster);
+    let cost = RULES.spell.get("magic_arrow").unwrap().cost;
+    assert_eq!(game.gd.chara.get(caster).sp, 100.0 - cost);
+    assert_eq!(game.gd.chara_pos(caster), Some(Vec2d(4, 1)));
+}
+
+#[test]
+fn return_home_budget_test() {
+    use super::testutil;
+
+    let _lock = testutil::init();
+    let mut gd = testutil::gen_gd();
+    let returning = testutil::add_npc(&mut gd, Vec2d(8, 9), Relationship::NEUTRAL);
+    let ai = &mut gd.chara.get_mut(returning).ai;
+    ai.kind = NpcAIKind::NoMove;
+    ai.state = AIState::ReturnHome;
+    ai.home = Some(Vec2d(4, 9));
+    let mut game = Game::new_headless(gd);
+
+    // Use up the budget by searching an unreachable goal
+    game.path_finder.reset_budget();
+    let map = game.gd.get_current_map();
+    let chara = game.gd.chara.get(CharaId::Player);
+    let path_finder = &mut game.path_finder;
+    let exhausted = (0..100).any(|_| {
+        path_finder.find_path(map, chara, Vec2d(1, 1), Vec2d(-1, -1))
+            == Err(PathError::BudgetExhausted)
+    });
+    assert!(exhausted);
+
+    // The npc steps toward home without the search, and keeps returning
+    process_npc_turn(&mut game, returning);
+    assert_eq!(game.gd.chara_pos(returning), Some(Vec2d(7, 9)));
+    assert_eq!(game.gd.chara.get(returning).ai.state, AIState::ReturnHome);
+
+    // Gives up if the search is finished without reaching home
+    game.path_finder.reset_budget();
+    let walls: Vec<Vec2d> = Direction::EIGHT_DIRS
+        .iter()
+        .map(|dir| Vec2d(7, 9) + dir.as_vec())
+        .collect();
+    testutil::set_walls(&mut game.gd, &walls);
+    process_npc_turn(&mut game, returning);
+    assert_eq!(game.gd.chara_pos(returning), Some(Vec2d(7, 9)));
+    assert_eq!(game.gd.chara.get(returning).ai.state, AIState::Normal);
+}
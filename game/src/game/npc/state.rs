@@ -0,0 +1,180 @@
+//! Processes NPC's AI states, such as patrolling, guarding and fleeing.
+
+use super::{move_to_nearest_enemy, move_toward, search_target_in_sight};
+use crate::game::action;
+use crate::game::extrait::*;
+use crate::game::{Game, InfoGetter};
+use common::gamedata::*;
+use geom::*;
+use rules::RULES;
+
+/// Change the AI state by the situation around the npc
+pub fn update_ai_state(game: &mut Game, cid: CharaId) {
+    let enemy_in_sight = search_target_in_sight(game, cid).is_some();
+    let resting_time = is_resting_time(game, cid);
+    let pos = game.gd.chara_pos(cid);
+    let chara = game.gd.chara.get_mut(cid);
+    let hp_ratio = chara.hp as f32 / chara.attr.max_hp as f32;
+    let ai = &mut chara.ai;
+
+    let next_state = match ai.state {
+        AIState::Flee => {
+            if !enemy_in_sight || hp_ratio >= RULES.npc_ai.flee_end_hp_ratio {
+                if ai.home.is_some() {
+                    AIState::ReturnHome
+                } else {
+                    ai.default_state
+                }
+            } else {
+                AIState::Flee
+            }
+        }
+        _ if enemy_in_sight && hp_ratio < RULES.npc_ai.flee_hp_ratio => AIState::Flee,
+        AIState::Rest if !resting_time => ai.default_state,
+        AIState::Normal | AIState::Patrol { .. } | AIState::ReturnHome if resting_time => {
+            AIState::Rest
+        }
+        AIState::ReturnHome => match (pos, ai.home) {
+            (Some(pos), Some(home)) if pos != home => AIState::ReturnHome,
+            _ => ai.default_state,
+        },
+        AIState::Normal => match (pos, ai.home) {
+            (Some(pos), Some(home)) if pos.mdistance(home) > RULES.npc_ai.home_range => {
+                AIState::ReturnHome
+            }
+            _ => AIState::Normal,
+        },
+        state => state,
+    };
+
+    ai.state = next_state;
+}
+
+/// Process the npc's action by the current AI state.
+/// Returns false if the npc should act by its AI kind.
+pub fn process_by_state(game: &mut Game, cid: CharaId) -> bool {
+    match game.gd.chara.get(cid).ai.state {
+        AIState::Normal => false,
+        AIState::Patrol { next } => patrol(game, cid, next),
+        AIState::ReturnHome => {
+            let home = game.gd.chara.get(cid).ai.home;
+            if !home.is_some_and(|home| move_toward(game, cid, home)) {
+                // Give up returning if there is no way to home
+                let ai = &mut game.gd.chara.get_mut(cid).ai;
+                ai.state = ai.default_state;
+            }
+            true
+        }
+        AIState::Guard { range } => guard(game, cid, range),
+        AIState::Flee => flee(game, cid),
+        AIState::Rest => rest(game, cid),
+    }
+}
+
+/// Citizens that have home go back there at night on open-air maps
+fn is_resting_time(game: &Game, cid: CharaId) -> bool {
+    let chara = game.gd.chara.get(cid);
+    chara.ai.home.is_some()
+        && chara.rel != Relationship::HOSTILE
+        && game.gd.is_night()
+        && game.gd.is_open_air(game.gd.get_current_mapid())
+}
+
+/// Move to the next waypoint. If enemies are in sight, returns false to fight them.
+fn patrol(game: &mut Game, cid: CharaId, next: u32) -> bool {
+    if search_target_in_sight(game, cid).is_some() {
+        return false;
+    }
+    let pos = if let Some(pos) = game.gd.chara_pos(cid) {
+        pos
+    } else {
+        return true;
+    };
+
+    let ai = &mut game.gd.chara.get_mut(cid).ai;
+    if ai.waypoints.is_empty() {
+        ai.state = AIState::Normal;
+        return false;
+    }
+    let mut next = next as usize % ai.waypoints.len();
+    if ai.waypoints[next] == pos {
+        next = (next + 1) % ai.waypoints.len();
+        ai.state = AIState::Patrol { next: next as u32 };
+    }
+    let dest = ai.waypoints[next];
+
+    move_toward(game, cid, dest);
+    true
+}
+
+/// Attack enemies in the guarding area, or stay at home.
+fn guard(game: &mut Game, cid: CharaId, range: u32) -> bool {
+    let home = if let Some(home) = game.gd.chara.get(cid).ai.home {
+        home
+    } else {
+        return false;
+    };
+
+    if let Some((target, _)) = search_target_in_sight(game, cid) {
+        if let Some(target_pos) = game.gd.chara_pos(target) {
+            if target_pos.mdistance(home) <= range as i32 {
+                let ai_kind = game.gd.chara.get(cid).ai.kind;
+                if super::attack_by_ai_kind(game, cid, ai_kind) {
+                    return true;
+                }
+                move_to_nearest_enemy(game, cid);
+                return true;
+            }
+        }
+    }
+
+    if game.gd.chara_pos(cid) != Some(home) {
+        move_toward(game, cid, home);
+    }
+    true
+}
+
+/// Go back to home and wait there until the morning.
+fn rest(game: &mut Game, cid: CharaId) -> bool {
+    let home = if let Some(home) = game.gd.chara.get(cid).ai.home {
+        home
+    } else {
+        return false;
+    };
+
+    if game.gd.chara_pos(cid) != Some(home) {
+        move_toward(game, cid, home);
+    }
+    true
+}
+
+/// Move away from the nearest enemy.
+/// If there is no tile to run away, returns false to fight back.
+fn flee(game: &mut Game, cid: CharaId) -> bool {
+    let target = if let Some((target, _)) = search_target_in_sight(game, cid) {
+        target
+    } else {
+        return false;
+    };
+    let pos = game.gd.chara_pos(cid).unwrap();
+    let target_pos = game.gd.chara_pos(target).unwrap();
+    let map = game.gd.get_current_map();
+    let chara = game.gd.chara.get(cid);
+
+    let current_distance = pos.mdistance(target_pos);
+    let dir = Direction::EIGHT_DIRS
+        .iter()
+        .filter(|dir| {
+            let p = pos + dir.as_vec();
+            map.is_passable(chara, p) && map.get_chara(p).is_none()
+        })
+        .max_by_key(|dir| (pos + dir.as_vec()).mdistance(target_pos))
+        .copied();
+
+    match dir {
+        Some(dir) if (pos + dir.as_vec()).mdistance(target_pos) > current_distance => {
+            action::try_move(game, cid, dir)
+        }
+        _ => false,
+    }
+}
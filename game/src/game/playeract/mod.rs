@@ -4,6 +4,7 @@ mod use_tool;
 use super::Game;
 use crate::game::{AdvanceScriptResult, DialogOpenRequest, InfoGetter};
 use common::gamedata::*;
+use common::gobj;
 use geom::*;
 
 /// Player actions are processed through this.
@@ -27,6 +28,10 @@ impl<'a> DoPlayerAction<'a> {
         &mut self.0.gd
     }
 
+    pub fn save_file(&mut self) {
+        self.0.save_file();
+    }
+
     pub fn shoot(&mut self, target: Vec2d) {
         let map = self.gd().get_current_map();
         if let Some(target_id) = map.get_chara(target) {
@@ -43,7 +48,9 @@ impl<'a> DoPlayerAction<'a> {
             cid: CharaId::Player,
         };
         game_log_i!("item-pickup"; chara=gd.chara.get(CharaId::Player), item=gd.get_item(il).0);
+        let item_id = gobj::idx_to_id(gd.get_item(il).0.idx).to_owned();
         gd.move_item(il, player_item_list_location, n);
+        crate::game::event::trigger_event(self.0, EventKind::PickUpItem(item_id));
         true
     }
 
@@ -79,10 +86,36 @@ impl<'a> DoPlayerAction<'a> {
 
     /// Release one magic device item
     pub fn release_item(&mut self, il: ItemLocation) {
-        super::action::release_item(self.0, il, CharaId::Player);
+        let target = self.0.target_pos();
+        super::action::release_item(self.0, il, CharaId::Player, target);
         self.0.finish_player_turn();
     }
 
+    /// Throw one item to the target character or tile
+    pub fn throw_item(&mut self, il: ItemLocation) {
+        let target = self.0.target_pos();
+        if let Some(target) = target {
+            super::action::throw::throw_item(self.0, il, CharaId::Player, target);
+            self.0.finish_player_turn();
+        } else {
+            let player = self.gd().chara.get(CharaId::Player);
+            game_log_i!("no-target"; chara=player);
+        }
+    }
+
+    /// Cast a learned spell to the target character or tile
+    pub fn cast_spell(&mut self, spell_id: &str) {
+        let target = self.0.target_pos();
+        if super::action::spell::cast_spell(self.0, CharaId::Player, spell_id, target) {
+            self.0.finish_player_turn();
+        }
+    }
+
+    /// Train a skill at the trainer
+    pub fn train_skill(&mut self, trainer: CharaId, kind: SkillKind) {
+        super::training::train_skill(self.gd_mut(), trainer, kind);
+    }
+
     /// Buy item
     pub fn buy_item(&mut self, il: ItemLocation) {
         super::shop::buy_item(self.gd_mut(), il);
@@ -1,5 +1,4 @@
 use super::DoPlayerAction;
-use crate::game::map::MapEx;
 use crate::game::{action, DialogOpenRequest, InfoGetter};
 use crate::text::ToText;
 use common::gamedata::*;
@@ -49,38 +48,15 @@ impl<'a> DoPlayerAction<'a> {
     }
 
     pub fn move_to(&mut self, dest: Vec2d) {
-        let d = dest - self.gd().player_pos();
-        let hdir = if d.0 < 0 {
-            HDirection::Left
-        } else if d.0 > 0 {
-            HDirection::Right
-        } else {
-            HDirection::None
-        };
-        let vdir = if d.1 < 0 {
-            VDirection::Up
-        } else if d.1 > 0 {
-            VDirection::Down
-        } else {
-            VDirection::None
-        };
-        let dir = Direction::new(hdir, vdir);
-        if dir.is_none() {
+        let start = self.gd().player_pos();
+        if start == dest {
             return;
         }
-        let map = self.gd().get_current_map();
-        let player = self.gd().chara.get(CharaId::Player);
-        let is_passable = |dir: Direction| {
-            let dest_tile = map.chara_pos(CharaId::Player).unwrap() + dir.as_vec();
-            map.is_passable(&player, dest_tile)
-        };
-
-        let dir = if is_passable(dir) {
+        let game = &mut *self.0;
+        let map = game.gd.get_current_map();
+        let player = game.gd.chara.get(CharaId::Player);
+        let dir = if let Ok(dir) = game.path_finder.first_step_dir(map, player, start, dest) {
             dir
-        } else if is_passable(Direction::new(dir.hdir, VDirection::None)) {
-            Direction::new(dir.hdir, VDirection::None)
-        } else if is_passable(Direction::new(HDirection::None, dir.vdir)) {
-            Direction::new(HDirection::None, dir.vdir)
         } else {
             return;
         };
@@ -1,6 +1,7 @@
 //! Quest handlings
 
 use super::chara::gen::choose_npc_chara_template;
+use super::extrait::*;
 use super::Game;
 use common::gamedata::*;
 use common::objholder::CharaTemplateIdx;
@@ -47,11 +48,12 @@ fn gen_quest() -> Quest {
     let reward = Reward {
         money: 1000,
         item: Vec::new(),
+        exp: RULES.exp.quest_reward,
     };
 
     Quest::SlayMonsters {
         reward,
-        idx: choose_npc_chara_template(&RULES.quest.slay_race_probability, 1),
+        idx: choose_npc_chara_template(&RULES.quest.slay_race_probability, 1, true),
         goal: 10,
         killed: 0,
     }
@@ -72,19 +74,26 @@ pub fn count_slayed_monster(gd: &mut GameData, t: CharaTemplateIdx) {
                     }
                 }
             }
+            Quest::Custom { .. } => (),
         }
     }
 }
 
 pub fn receive_rewards(gd: &mut GameData) -> bool {
     let mut money = 0;
+    let mut exp = 0;
     let mut exist_completed_quest = false;
 
     for (state, quest) in gd.quest.iter_mut() {
+        // Quests by scripts are kept to be referred by scripts later
+        if let Quest::Custom { .. } = quest {
+            continue;
+        }
         if *state == QuestState::Completed {
             exist_completed_quest = true;
             let reward = quest.reward();
             money += reward.money;
+            exp += reward.exp;
             *state = QuestState::RewardReceived;
         }
     }
@@ -93,6 +102,9 @@ pub fn receive_rewards(gd: &mut GameData) -> bool {
         gd.quest.remove_reward_received();
         gd.player.add_money(money);
         game_log_i!("quest-reward-receive-money"; money=money);
+        if exp > 0 {
+            gd.chara.get_mut(CharaId::Player).add_chara_exp(exp);
+        }
     }
     exist_completed_quest
 }
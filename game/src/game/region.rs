@@ -36,6 +36,10 @@ pub fn gen_dungeon_max(gd: &mut GameData, rid: RegionId) {
 
 /// Generate one dungeon and add it to the region
 pub fn gen_dungeon(gd: &mut GameData, rid: RegionId) {
+    with_stream(RngStream::MapGen, || gen_dungeon_on_stream(gd, rid));
+}
+
+fn gen_dungeon_on_stream(gd: &mut GameData, rid: RegionId) {
     if MAX_AUTO_GEN_DUNGEONS <= gd.region.get(rid).get_site_n(SiteKind::AutoGenDungeon) {
         return;
     }
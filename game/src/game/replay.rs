@@ -0,0 +1,201 @@
+//! Records player's commands with the save at the start of playing, and replays them.
+//! A replay is a directory like save files, containing the header, the starting save
+//! and processed commands in JSON lines.
+
+use super::saveload::get_map_dir;
+use super::{Command, Game};
+use common::gamedata::*;
+use geom::Vec2d;
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+const HEADER_FILE: &str = "header";
+const SAVE_DIR: &str = "save";
+const COMMANDS_FILE: &str = "commands";
+const END_STATE_FILE: &str = "end_state";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReplayHeader {
+    /// The game version used for recording
+    pub version: String,
+    /// If true, the opening script of a new game is started after loading
+    pub new_game: bool,
+}
+
+/// A summary of the game data at the end of recording, used to check that a replay is reproduced
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct EndState {
+    pub time: Time,
+    pub mid: MapId,
+    pub money: i64,
+    /// Position, hp and exp of charas on the current map, in the map's order
+    pub charas: Vec<(CharaId, Vec2d, i32, u32)>,
+}
+
+impl EndState {
+    pub fn new(gd: &GameData) -> EndState {
+        let map = gd.get_current_map();
+        let charas = map
+            .iter_charaid()
+            .filter_map(|&cid| {
+                let chara = gd.chara.get(cid);
+                Some((cid, map.chara_pos(cid)?, chara.hp, chara.exp))
+            })
+            .collect();
+        EndState {
+            time: gd.time.current_time(),
+            mid: gd.get_current_mapid(),
+            money: gd.player.money(),
+            charas,
+        }
+    }
+}
+
+pub struct Recorder {
+    dir: PathBuf,
+
+    file: BufWriter<File>,
+}
+
+impl Recorder {
+    /// Start recording to the given directory. This must be called just after Game::new(),
+    /// because the current game data is saved as the starting point.
+    /// An old replay in the directory is overwritten.
+    pub fn start<P: AsRef<Path>>(
+        path: P,
+        game: &mut Game,
+        new_game: bool,
+    ) -> Result<Recorder, Box<dyn Error>> {
+        let dir = path.as_ref();
+        if dir.exists() {
+            if !dir.join(HEADER_FILE).exists() {
+                return Err(format!("{} exists and is not a replay", dir.to_string_lossy()).into());
+            }
+            fs::remove_dir_all(dir)?;
+        }
+        fs::create_dir_all(dir)?;
+
+        // Maps not loaded yet are in the player's save, so they are included in the replay
+        let map_dir = get_map_dir(&game.gd);
+        preload_all_maps(&mut game.gd, &map_dir);
+        game.gd.save(dir.join(SAVE_DIR))?;
+
+        let header = ReplayHeader {
+            version: env!("CARGO_PKG_VERSION").to_owned(),
+            new_game,
+        };
+        let mut file = BufWriter::new(File::create(dir.join(HEADER_FILE))?);
+        serde_json::to_writer_pretty(&mut file, &header)?;
+        file.flush()?;
+
+        let file = BufWriter::new(File::create(dir.join(COMMANDS_FILE))?);
+        info!("Start recording to {}", dir.to_string_lossy());
+        Ok(Recorder {
+            dir: dir.to_owned(),
+            file,
+        })
+    }
+
+    /// Append a processed command.
+    /// Commands are flushed at once, so that a replay is available even if the game crashes.
+    pub fn record(&mut self, command: &Command) {
+        // Mouse states are sent at every frame only to update the cursor
+        if let Command::MouseState { .. } = command {
+            return;
+        }
+
+        let result = serde_json::to_writer(&mut self.file, command)
+            .map_err(std::io::Error::from)
+            .and_then(|_| writeln!(self.file))
+            .and_then(|_| self.file.flush());
+        if let Err(e) = result {
+            warn!("Failed to record a command: {}", e);
+        }
+    }
+
+    /// Finish recording and write the end state, which is checked after a headless replay.
+    pub fn finish(self, gd: &GameData) {
+        if let Err(e) = self.write_end_state(gd) {
+            warn!("Failed to write the end state of the replay: {}", e);
+        }
+    }
+
+    fn write_end_state(&self, gd: &GameData) -> Result<(), Box<dyn Error>> {
+        let mut file = BufWriter::new(File::create(self.dir.join(END_STATE_FILE))?);
+        serde_json::to_writer_pretty(&mut file, &EndState::new(gd))?;
+        file.flush()?;
+        Ok(())
+    }
+}
+
+pub struct Replay {
+    pub header: ReplayHeader,
+    pub gd: GameData,
+    pub commands: Vec<Command>,
+    /// None if the recording was not finished properly
+    pub end_state: Option<EndState>,
+}
+
+/// Load a replay. Its commands should be processed in the same way as player's inputs
+/// after the game is started by the loaded data.
+pub fn load<P: AsRef<Path>>(path: P) -> Result<Replay, Box<dyn Error>> {
+    let dir = path.as_ref();
+
+    let header: ReplayHeader =
+        serde_json::from_reader(BufReader::new(File::open(dir.join(HEADER_FILE))?))?;
+    if header.version != env!("CARGO_PKG_VERSION") {
+        warn!(
+            "The replay was recorded by version {}, so it may not be reproduced",
+            header.version
+        );
+    }
+
+    let save_dir = dir.join(SAVE_DIR);
+    let mut gd = GameData::load_dir(&save_dir)?;
+    preload_all_maps(&mut gd, &save_dir.join("maps"));
+    // Saving during a replay must not overwrite the player's save
+    let save_name = format!("replay-{}", gd.meta.save_name());
+    gd.meta.set_save_name(&save_name);
+
+    let mut commands = Vec::new();
+    for line in BufReader::new(File::open(dir.join(COMMANDS_FILE))?).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        commands.push(serde_json::from_str(&line)?);
+    }
+    let end_state_path = dir.join(END_STATE_FILE);
+    let end_state = if end_state_path.exists() {
+        Some(serde_json::from_reader(BufReader::new(File::open(
+            end_state_path,
+        )?))?)
+    } else {
+        None
+    };
+
+    info!(
+        "Loaded replay {} with {} commands",
+        dir.to_string_lossy(),
+        commands.len()
+    );
+
+    Ok(Replay {
+        header,
+        gd,
+        commands,
+        end_state,
+    })
+}
+
+fn preload_all_maps(gd: &mut GameData, map_dir: &Path) {
+    let mut mids = Vec::new();
+    gd.region.visit_all_maps(|mid, _map| {
+        mids.push(mid);
+    });
+    for mid in mids {
+        gd.region.preload_map(mid, map_dir);
+    }
+}
@@ -0,0 +1,385 @@
+use crate::config::USER_DIR;
+use crate::game::Game;
+use crate::text::ToText;
+use common::basic::{SAVE_DIR_NAME, SAVE_EXTENSION};
+use common::gamedata::{CharaId, GameData, MapId, MetaData};
+use common::migration::MigrationError;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+impl Game {
+    pub fn save_file(&mut self) {
+        self.update_meta();
+        let save_dir = get_save_dir();
+
+        if !save_dir.exists() {
+            match fs::create_dir_all(&save_dir) {
+                Ok(()) => (),
+                Err(e) => {
+                    warn!("Failed to create save directory : {}", e);
+                    return;
+                }
+            }
+        }
+
+        let path = self.gd.save_dir(save_dir);
+
+        match self.gd.save(&path) {
+            Ok(_) => info!("Saved to {:?}", path.to_string_lossy()),
+            Err(e) => warn!("Faild to saving to {:?}: {}", path.to_string_lossy(), e),
+        }
+    }
+
+    /// Update the summary of the game in metadata
+    fn update_meta(&mut self) {
+        let elapsed = Instant::now().duration_since(self.play_start).as_secs();
+        self.play_start += Duration::from_secs(elapsed);
+
+        let gd = &mut self.gd;
+        let mid = gd.get_current_mapid();
+        let (site_name, floor) = match mid {
+            MapId::SiteMap { sid, floor } => {
+                (gd.region.get_site(sid).to_text().into_owned(), Some(floor))
+            }
+            MapId::RegionMap { rid } => (gd.region.get(rid).name.clone(), None),
+        };
+        let date = gd.time.current_date();
+        let player = gd.chara.get(CharaId::Player);
+        let player_name = player.to_text().into_owned();
+        let chara_class = player.class;
+        let level = player.level;
+
+        let meta = &mut gd.meta;
+        meta.player_name = player_name;
+        meta.chara_class = Some(chara_class);
+        meta.level = level;
+        meta.site_name = site_name;
+        meta.floor = floor;
+        meta.date = Some(date);
+        meta.play_time += elapsed;
+        meta.timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+    }
+
+    pub fn clean_save_data(&self) {
+        let save_dir = get_save_dir();
+        let path = self.gd.save_dir(save_dir);
+        if !path.exists() {
+            return;
+        }
+        match self.gd.clean_map_dir(&path) {
+            Ok(_) => info!("Clean map dir {:?}", path.to_string_lossy()),
+            Err(e) => warn!("Faild to clean map dir {:?}: {}", path.to_string_lossy(), e),
+        }
+    }
+}
+
+/// A save directory and its metadata
+pub struct SaveFile {
+    pub path: PathBuf,
+    /// None if the metadata cannot be read
+    pub meta: Option<MetaData>,
+}
+
+/// Returns save files. The most recently saved one is the first.
+pub fn save_file_list() -> Result<Vec<SaveFile>, std::io::Error> {
+    let mut list = Vec::new();
+
+    for entry in fs::read_dir(get_save_dir())? {
+        let file = entry?;
+
+        if !file.file_type()?.is_dir() {
+            continue;
+        }
+
+        let path = file.path();
+
+        let extension = path.extension();
+
+        if extension.is_some() && extension.unwrap() == SAVE_EXTENSION {
+            let meta = match common::saveload::load_metadata(&path) {
+                Ok(meta) => Some(meta),
+                Err(e) => {
+                    warn!(
+                        "Failed to read metadata of {}: {}",
+                        path.to_string_lossy(),
+                        e
+                    );
+                    None
+                }
+            };
+            list.push(SaveFile { path, meta });
+        }
+    }
+
+    list.sort_by_key(|save_file| {
+        std::cmp::Reverse(save_file.meta.as_ref().map_or(0, |meta| meta.timestamp))
+    });
+    Ok(list)
+}
+
+/// The message shown to the player when loading a save file failed
+pub fn load_error_msg(e: &(dyn std::error::Error + 'static)) -> String {
+    match e.downcast_ref::<MigrationError>() {
+        Some(MigrationError::NewerVersion { .. }) => {
+            crate::text::ui_txt("dialog-load_newer_version")
+        }
+        _ => crate::text::ui_txt("dialog-load_failed"),
+    }
+}
+
+/// Delete the save file and its backups
+pub fn delete_save_file(path: &Path) -> Result<(), std::io::Error> {
+    common::saveload::delete_save(path)?;
+    info!("Deleted save {}", path.to_string_lossy());
+    Ok(())
+}
+
+/// Copy the save file with an unused name such as "name-2"
+pub fn duplicate_save_file(path: &Path) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let name = path
+        .file_stem()
+        .ok_or("invalid save file path")?
+        .to_string_lossy()
+        .into_owned();
+
+    for n in 2.. {
+        let new_name = format!("{}-{}", name, n);
+        if !get_save_dir()
+            .join(format!("{}.{}", new_name, SAVE_EXTENSION))
+            .exists()
+        {
+            let new_path = common::saveload::duplicate_save(path, &new_name)?;
+            info!("Duplicated save {} to {}", name, new_name);
+            return Ok(new_path);
+        }
+    }
+    unreachable!()
+}
+
+/// Generate random id for FileBox
+pub fn gen_box_id(gd: &GameData) -> u64 {
+    use rng::*;
+
+    loop {
+        let s = thread_rng().gen::<u64>();
+
+        // Check generated name is not used
+        let mut path = get_each_save_dir(gd);
+        path.push("maps");
+        path.push(format!("{:016x}", s));
+        if !path.exists() {
+            return s;
+        }
+    }
+}
+
+fn get_save_dir() -> PathBuf {
+    USER_DIR.clone().join(SAVE_DIR_NAME)
+}
+
+/// Get each save directory path "save_dir/save_name"
+pub fn get_each_save_dir(gd: &GameData) -> PathBuf {
+    get_save_dir().join(format!("{}.{}", gd.meta.save_name(), SAVE_EXTENSION))
+}
+
+/// Get map save directory
+pub fn get_map_dir(gd: &GameData) -> PathBuf {
+    get_each_save_dir(gd).join("maps")
+}
+
+#[test]
+fn migration_test() {
+    use super::testutil;
+    use common::basic::SAVE_VERSION;
+    use common::gamedata::AIState;
+
+    let _lock = testutil::init();
+    for &(name, world_seed) in &[
+        ("v0.rrsve", None),
+        ("v1.rrsve", Some(12345)),
+        ("v2.rrsve", Some(12345)),
+    ] {
+        let path = testutil::copy_save_fixture(name);
+        let gd = GameData::load(&path).unwrap();
+        assert_eq!(gd.meta.version(), SAVE_VERSION);
+        let meta = common::saveload::load_metadata(&path).unwrap();
+        assert_eq!(meta.version(), SAVE_VERSION);
+        if let Some(world_seed) = world_seed {
+            assert_eq!(gd.world_seed, world_seed);
+        }
+
+        let player = gd.chara.get(CharaId::Player);
+        let status: Vec<_> = player
+            .status
+            .iter()
+            .map(|s| (s.id.as_str(), s.turn_left))
+            .collect();
+        assert_eq!(status, [("hungry", None), ("asleep", Some(5))]);
+
+        let npc = *gd
+            .get_current_map()
+            .iter_charaid()
+            .find(|&&cid| cid != CharaId::Player)
+            .unwrap();
+        let npc = gd.chara.get(npc);
+        assert_eq!(npc.status[0].id, "poisoned");
+        assert_eq!(npc.ai.state, AIState::Normal);
+    }
+}
+
+#[test]
+fn newer_version_test() {
+    use super::testutil;
+
+    let _lock = testutil::init();
+    let path = testutil::copy_save_fixture("newer.rrsve");
+    let e = match GameData::load(&path) {
+        Ok(_) => panic!("loaded a save of a newer version"),
+        Err(e) => e,
+    };
+    assert!(matches!(
+        e.downcast_ref::<MigrationError>(),
+        Some(MigrationError::NewerVersion { save: 99, .. })
+    ));
+    assert_eq!(
+        common::saveload::load_metadata(&path).unwrap().version(),
+        99
+    );
+    assert_eq!(
+        load_error_msg(&*e),
+        crate::text::ui_txt("dialog-load_newer_version")
+    );
+}
+
+#[test]
+fn local_vars_test() {
+    use super::testutil;
+    use common::script::Value;
+    use geom::Vec2d;
+
+    let _lock = testutil::init();
+    let path = testutil::temp_dir()
+        .join("saves")
+        .join(format!("vars.{}", SAVE_EXTENSION));
+    common::saveload::delete_save(&path).unwrap();
+    let mut gd = testutil::gen_gd();
+    gd.meta.set_save_name("vars");
+    let npc = testutil::add_npc(
+        &mut gd,
+        Vec2d(3, 3),
+        common::gamedata::Relationship::NEUTRAL,
+    );
+    let sid = gd.get_current_mapid().sid();
+    gd.chara
+        .get_mut(CharaId::Player)
+        .vars
+        .set("n", Value::Int(1));
+    gd.chara.get_mut(npc).vars.set("n", Value::Bool(true));
+    gd.region.get_site_mut(sid).vars.set("n", Value::Int(3));
+    gd.save(&path).unwrap();
+
+    let gd = GameData::load(&path).unwrap();
+    let get_var = |cid| gd.chara.get(cid).vars.get("n");
+    assert_eq!(get_var(CharaId::Player), Some(&Value::Int(1)));
+    assert_eq!(get_var(npc), Some(&Value::Bool(true)));
+    assert_eq!(gd.region.get_site(sid).vars.get("n"), Some(&Value::Int(3)));
+}
+
+/// Save the test game data twice with different money, and returns the path.
+/// The first save is kept as a backup.
+#[cfg(test)]
+fn save_with_backup(name: &str) -> PathBuf {
+    use super::testutil;
+
+    let path = testutil::temp_dir()
+        .join("saves")
+        .join(format!("{}.{}", name, SAVE_EXTENSION));
+    common::saveload::delete_save(&path).unwrap();
+    let mut gd = testutil::gen_gd();
+    gd.meta.set_save_name(name);
+    gd.player.set_money(100);
+    gd.save(&path).unwrap();
+    gd.player.set_money(200);
+    gd.save(&path).unwrap();
+    path
+}
+
+#[test]
+fn restore_backup_test() {
+    use super::testutil;
+
+    let _lock = testutil::init();
+    let path = save_with_backup("corrupt");
+    let broken = |n: u32| PathBuf::from(format!("{}.broken.{}", path.to_string_lossy(), n));
+    fs::create_dir_all(broken(1)).unwrap();
+    fs::write(broken(1).join("gamedata"), "old broken save").unwrap();
+    fs::write(path.join("gamedata"), "corrupt").unwrap();
+
+    let gd = GameData::load(&path).unwrap();
+    assert_eq!(gd.player.money(), 100);
+    // The existing broken save and the live save are kept
+    assert_eq!(
+        fs::read_to_string(broken(1).join("gamedata")).unwrap(),
+        "old broken save"
+    );
+    assert_eq!(
+        fs::read_to_string(broken(2).join("gamedata")).unwrap(),
+        "corrupt"
+    );
+
+    common::saveload::delete_save(&path).unwrap();
+    assert!(!broken(1).exists() && !broken(2).exists());
+}
+
+#[test]
+fn interrupted_swap_test() {
+    use super::testutil;
+
+    let _lock = testutil::init();
+    let path = save_with_backup("swap");
+    // Stopped after the old save is moved to the backup
+    let tmp = PathBuf::from(format!("{}.tmp", path.to_string_lossy()));
+    fs::rename(&path, &tmp).unwrap();
+
+    let gd = GameData::load(&path).unwrap();
+    assert_eq!(gd.player.money(), 200);
+    assert!(path.exists() && !tmp.exists());
+}
+
+#[test]
+fn newer_version_backup_test() {
+    use super::testutil;
+
+    let _lock = testutil::init();
+    let path = save_with_backup("newer");
+    let metadata = fs::read_to_string(path.join("metadata")).unwrap();
+    let metadata = metadata.replace(
+        &format!("\"version\": {}", common::basic::SAVE_VERSION),
+        "\"version\": 99",
+    );
+    fs::write(path.join("metadata"), metadata).unwrap();
+
+    assert!(GameData::load(&path).is_err());
+    // The backup is not restored
+    let meta = common::saveload::load_metadata(&path).unwrap();
+    assert_eq!(meta.version(), 99);
+    assert!(!PathBuf::from(format!("{}.broken.1", path.to_string_lossy())).exists());
+    assert!(common::saveload::backup_dir(&path, 1).exists());
+}
+
+#[test]
+fn load_dir_test() {
+    use super::testutil;
+
+    let _lock = testutil::init();
+    let path = testutil::copy_save_fixture("v0.rrsve");
+    let gd = GameData::load_dir(&path).unwrap();
+    assert_eq!(gd.meta.version(), common::basic::SAVE_VERSION);
+    // The save is converted in a temporary copy
+    let meta = common::saveload::load_metadata(&path).unwrap();
+    assert_eq!(meta.version(), 0);
+}
@@ -0,0 +1,325 @@
+//! Script engine implementation
+
+use common::gamedata::*;
+use common::gobj;
+use common::objholder::ItemIdx;
+use common::script::*;
+use geom::Vec2d;
+
+use crate::game::eval_expr::EvalExpr;
+use crate::game::InfoGetter;
+
+pub struct ScriptEngine {
+    script: &'static Script,
+    pos: ScriptPos,
+    cid: Option<CharaId>,
+    talking: bool,
+    /// Variables valid during this script execution
+    vars: LocalVars,
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum ExecResult {
+    Talk(Option<CharaId>, TalkText, bool),
+    ShopBuy(CharaId),
+    ShopSell,
+    Quest,
+    Train(CharaId),
+    Teleport(Option<u32>, Vec2d),
+    Quit,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TalkText {
+    pub text_id: &'static str,
+    pub choices: Option<&'static [(String, String)]>,
+}
+
+/// Unwrap Value as bool
+macro_rules! as_bool {
+    ($v:expr) => {{
+        match $v {
+            Value::Bool(v) => v,
+            Value::RefUnknownVar => false,
+            _ => {
+                return ExecResult::Quit;
+            }
+        }
+    }};
+}
+
+/// Unwrap Value as int
+macro_rules! as_int {
+    ($v:expr) => {{
+        match $v {
+            Value::Int(v) => v,
+            _ => {
+                return ExecResult::Quit;
+            }
+        }
+    }};
+}
+
+/// Return if the evaluation failed, as as_bool and as_int do
+macro_rules! no_error {
+    ($v:expr) => {{
+        match $v {
+            Value::Error(_) => {
+                return ExecResult::Quit;
+            }
+            v => v,
+        }
+    }};
+}
+
+/// Jump to the given section and continue loop.
+macro_rules! jump {
+    ($s:expr, $section:expr) => {{
+        match $section.as_ref() {
+            QUIT_SECTION => return ExecResult::Quit,
+            CONTINUE_SECTION => {
+                $s.pos.advance();
+                continue;
+            }
+            _ => (),
+        }
+        $s.pos.set_section($section);
+        continue;
+    }};
+}
+
+/// Unwrap or return with warning message.
+macro_rules! ur {
+    ($a:expr, $e:expr) => {{
+        if let Some(a) = $a {
+            a
+        } else {
+            warn!(concat!("script error: ", $e));
+            return ExecResult::Quit;
+        }
+    }};
+}
+
+impl ScriptEngine {
+    pub fn new(id: &str, section: &str, cid: Option<CharaId>) -> ScriptEngine {
+        let script_obj: &ScriptObject = gobj::get_by_id(id);
+        ScriptEngine {
+            script: &script_obj.script,
+            pos: ScriptPos {
+                section: section.to_owned(),
+                i: 0,
+            },
+            cid,
+            talking: false,
+            vars: LocalVars::default(),
+        }
+    }
+
+    pub fn exec(&mut self, gd: &mut GameData) -> ExecResult {
+        let result = loop {
+            let instruction = if let Some(instruction) = self.script.get(&self.pos) {
+                instruction
+            } else {
+                break ExecResult::Quit;
+            };
+
+            match instruction {
+                Instruction::Jump(section) => {
+                    jump!(self, section);
+                }
+                Instruction::JumpIf(section, expr) => {
+                    if as_bool!(expr.eval(gd, self.cid, &self.vars)) {
+                        jump!(self, section);
+                    }
+                }
+                Instruction::Talk(text_id, choices) => {
+                    let need_open_talk_dialog = if self.talking {
+                        false
+                    } else {
+                        self.talking = true;
+                        true
+                    };
+
+                    let choices = if choices.is_empty() {
+                        None
+                    } else {
+                        Some(choices.as_ref())
+                    };
+                    return ExecResult::Talk(
+                        self.cid,
+                        TalkText { text_id, choices },
+                        need_open_talk_dialog,
+                    );
+                }
+                Instruction::GSet(name, v) => {
+                    let v = no_error!(v.eval(gd, self.cid, &self.vars));
+                    gd.vars.set_global_var(name, v);
+                }
+                Instruction::ReceiveMoney(v) => {
+                    let v = v.eval(gd, self.cid, &self.vars);
+                    gd.player.add_money(as_int!(v) as i64);
+                }
+                Instruction::RemoveItem(item_id) => {
+                    let il = ur!(gd.player_item_location(item_id), "cannot find item");
+                    gd.remove_item(il, 1);
+                }
+                Instruction::Special(SpecialInstruction::ShopBuy) => {
+                    break ExecResult::ShopBuy(ur!(self.cid, "cid is needed"));
+                }
+                Instruction::Special(SpecialInstruction::ShopSell) => {
+                    break ExecResult::ShopSell;
+                }
+                Instruction::Special(SpecialInstruction::GetDungeonLocation) => {
+                    let mid = gd.get_current_mapid();
+                    super::region::gen_dungeon_max(gd, mid.rid());
+                }
+                Instruction::Special(SpecialInstruction::QuestWindow) => {
+                    super::quest::update_town_quest(gd);
+                    break ExecResult::Quest;
+                }
+                Instruction::Special(SpecialInstruction::ReceiveQuestRewards) => {
+                    let result = super::quest::receive_rewards(gd);
+                    gd.vars.set_last_result(Value::Bool(result))
+                }
+                Instruction::Special(SpecialInstruction::Train) => {
+                    break ExecResult::Train(ur!(self.cid, "cid is needed"));
+                }
+                Instruction::GiveItem(item_id, n) => {
+                    let idx: ItemIdx = ur!(gobj::id_to_idx_checked(item_id), "unknown item id");
+                    let n = as_int!(n.eval(gd, self.cid, &self.vars));
+                    if n > 0 {
+                        let item = super::item::gen::gen_item_from_idx(idx);
+                        let player = gd.chara.get(CharaId::Player);
+                        game_log_i!("receive-item"; chara=player, item=&item, n=n);
+                        gd.get_item_list_mut(ItemListLocation::Chara {
+                            cid: CharaId::Player,
+                        })
+                        .append(item, n as u32);
+                    }
+                }
+                Instruction::SetRelationship(rel) => {
+                    let cid = ur!(self.cid, "cid is needed");
+                    gd.chara.get_mut(cid).rel = *rel;
+                }
+                Instruction::StartQuest(quest_id) => {
+                    if gd.quest.custom_quest_state(quest_id).is_none() {
+                        let quest = Quest::Custom {
+                            id: quest_id.clone(),
+                        };
+                        game_log_i!("quest-start"; quest=&quest);
+                        gd.quest.start_new_quest(quest);
+                    }
+                }
+                Instruction::CompleteQuest(quest_id) => {
+                    if gd.quest.custom_quest_state(quest_id) == Some(QuestState::Active) {
+                        gd.quest
+                            .set_custom_quest_state(quest_id, QuestState::Completed);
+                        let quest = Quest::Custom {
+                            id: quest_id.clone(),
+                        };
+                        game_log_i!("quest-complete"; quest=&quest);
+                    }
+                }
+                Instruction::Teleport(floor, pos) => {
+                    break ExecResult::Teleport(*floor, *pos);
+                }
+                Instruction::CSet(name, v) => {
+                    let cid = ur!(self.cid, "chara variable needs executing character");
+                    let v = no_error!(v.eval(gd, self.cid, &self.vars));
+                    gd.chara.get_mut(cid).vars.set(name, v);
+                }
+                Instruction::SSet(name, v) => {
+                    let mid = gd.get_current_mapid();
+                    if mid.is_region_map() {
+                        warn!("script error: site variable is used on region map");
+                        return ExecResult::Quit;
+                    }
+                    let v = no_error!(v.eval(gd, self.cid, &self.vars));
+                    gd.region.get_site_mut(mid.sid()).vars.set(name, v);
+                }
+                Instruction::LSet(name, v) => {
+                    let v = no_error!(v.eval(gd, self.cid, &self.vars));
+                    self.vars.set(name, v);
+                }
+            }
+            self.pos.advance();
+        };
+
+        self.pos.advance();
+        result
+    }
+
+    pub fn continue_talk(&mut self, gd: &mut GameData, choice: Option<u32>) -> ExecResult {
+        match self.script.get(&self.pos).expect("instruction not found") {
+            Instruction::Talk(_, choices) => {
+                if let Some(c) = choice {
+                    let next_section = &choices[c as usize].1;
+                    if next_section == QUIT_SECTION {
+                        return ExecResult::Quit;
+                    }
+                    if next_section == CONTINUE_SECTION {
+                        self.pos.advance();
+                    } else {
+                        self.pos.set_section(next_section);
+                    }
+                } else {
+                    assert!(choices.is_empty());
+                    self.pos.advance();
+                }
+                self.exec(gd)
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[test]
+fn local_vars_test() {
+    use super::testutil;
+
+    let _lock = testutil::init();
+    let mut gd = testutil::gen_gd();
+    let npc = testutil::add_npc(&mut gd, Vec2d(3, 3), Relationship::NEUTRAL);
+    let mid = gd.get_current_mapid();
+    let run = |gd: &mut GameData, section: &str, cid: Option<CharaId>| {
+        ScriptEngine::new(testutil::SCRIPT_ID, section, cid).exec(gd)
+    };
+    let global = |gd: &GameData, name: &str| gd.vars.global_var(name).cloned();
+
+    assert_eq!(run(&mut gd, "set", Some(npc)), ExecResult::Quit);
+    assert_eq!(global(&gd, "local"), Some(Value::Int(1)));
+    assert_eq!(global(&gd, "chara"), Some(Value::Int(2)));
+    assert_eq!(global(&gd, "site"), Some(Value::Int(3)));
+    assert_eq!(gd.chara.get(npc).vars.get("n"), Some(&Value::Int(2)));
+    assert_eq!(
+        gd.region.get_site(mid.sid()).vars.get("n"),
+        Some(&Value::Int(3))
+    );
+
+    // Local variables are lost when the execution ends
+    run(&mut gd, "get", Some(npc));
+    assert_eq!(global(&gd, "local"), Some(Value::RefUnknownVar));
+    assert_eq!(global(&gd, "chara"), Some(Value::Int(2)));
+    assert_eq!(global(&gd, "site"), Some(Value::Int(3)));
+
+    // Characters have their own variables
+    let other = testutil::add_npc(&mut gd, Vec2d(4, 3), Relationship::NEUTRAL);
+    run(&mut gd, "get", Some(other));
+    assert_eq!(global(&gd, "chara"), Some(Value::RefUnknownVar));
+
+    // Errors stop the script in both of setting and reading
+    gd.vars.set_global_var("chara", Value::Int(0));
+    gd.vars.set_global_var("site", Value::Int(0));
+    run(&mut gd, "get", None);
+    assert_eq!(global(&gd, "chara"), Some(Value::Int(0)));
+    assert_eq!(global(&gd, "site"), Some(Value::Int(0)));
+    gd.set_current_mapid(MapId::from(mid.rid()));
+    run(&mut gd, "get", Some(CharaId::Player));
+    assert_eq!(global(&gd, "chara"), Some(Value::RefUnknownVar));
+    assert_eq!(global(&gd, "site"), Some(Value::Int(0)));
+    assert_eq!(run(&mut gd, "set_site", None), ExecResult::Quit);
+    assert_eq!(
+        gd.region.get_site(mid.sid()).vars.get("n"),
+        Some(&Value::Int(3))
+    );
+}
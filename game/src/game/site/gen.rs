@@ -0,0 +1,58 @@
+use crate::game;
+use common::gamedata::*;
+use common::gobj;
+use common::sitegen::*;
+
+/// Add unique citizens from SiteGenObject
+pub fn add_unique_citizens(gd: &mut GameData, sid: SiteId, sg: &SiteGenObject) {
+    for uc in &sg.unique_citizens {
+        let mut chara = game::chara::gen::create_chara(gobj::id_to_idx(&uc.chara_template_id), 1);
+        let mid = MapId::SiteMap {
+            sid,
+            floor: uc.floor,
+        };
+        chara.rel = common::gamedata::chara::Relationship::FRIENDLY;
+
+        if let Some(talk_script_id) = uc.talk_script_id.as_ref() {
+            // Talk script setting
+            chara.trigger_talk = Some(talk_script_id.to_owned());
+        }
+
+        // AI setting
+        chara.ai.home = Some(uc.pos);
+        chara.ai.default_state = if let Some(range) = uc.guard_range {
+            AIState::Guard { range }
+        } else if !uc.waypoints.is_empty() {
+            chara.ai.waypoints = uc.waypoints.clone();
+            AIState::Patrol { next: 0 }
+        } else {
+            AIState::Normal
+        };
+        chara.ai.state = chara.ai.default_state;
+
+        let cid = gd.add_chara_to_site(chara, sid, uc.n);
+        gd.region.get_map_mut(mid).locate_chara(cid, uc.pos);
+    }
+}
+
+/// Add event triggers from SiteGenObject
+pub fn add_event_triggers(gd: &mut GameData, sid: SiteId, sg: &SiteGenObject) {
+    for t in &sg.triggers {
+        let section = t.section.clone().unwrap_or_else(|| "start".to_owned());
+        let trigger = EventTrigger::new(t.event.clone(), t.script_id.clone(), section, t.once);
+
+        if let Some(floor) = t.floor {
+            let mid = MapId::SiteMap { sid, floor };
+            if !gd.region.map_exist(mid) {
+                warn!(
+                    "event trigger for {} is set to nonexistent floor {} of {}",
+                    t.script_id, floor, sg.id
+                );
+                continue;
+            }
+            gd.region.get_map_mut(mid).triggers.push(trigger);
+        } else {
+            gd.region.get_site_mut(sid).triggers.push(trigger);
+        }
+    }
+}
@@ -0,0 +1,293 @@
+//! Global states and small game data for tests.
+//! Objects are written to a temporary pak file, so tests do not need downloaded pak files.
+
+use super::chara::gen::create_chara;
+use common::gamedata::*;
+use common::gobj;
+use common::obj::*;
+use common::pakutil::write_object;
+use common::script::{Expr, Instruction, Script, Value};
+use common::sitegen::TrainerGenData;
+use geom::*;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, Once};
+
+pub const CHARA_TEMPLATE_ID: &str = "!test-chara";
+pub const WALL_ID: &str = "!test-wall";
+pub const BOW_ID: &str = "!test-bow";
+pub const ARROW_ID: &str = "!test-arrow";
+pub const TOWN_ID: &str = "!test-town";
+pub const CROP_ID: &str = "!test-crop";
+pub const SCRIPT_ID: &str = "!test-script";
+pub const MAP_SIZE: (u32, u32) = (16, 12);
+
+static INIT: Once = Once::new();
+
+lazy_static! {
+    static ref LOCK: Mutex<()> = Mutex::new(());
+    static ref TEMP_DIR: PathBuf =
+        std::env::temp_dir().join(format!("rusted-ruins-test-{}", std::process::id()));
+}
+
+/// Initialize global states for tests. Game log and RNG are shared,
+/// so tests that process the game must hold the returned guard.
+pub fn init() -> MutexGuard<'static, ()> {
+    INIT.call_once(|| {
+        let assets_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../assets");
+        std::env::set_var("RUSTED_RUINS_ASSETS_DIR", assets_dir);
+        std::env::set_var("XDG_DATA_HOME", temp_dir().join("user"));
+        crate::text::init();
+        crate::log::init();
+        gobj::init(vec![write_pak()]);
+        rules::init(assets_dir);
+    });
+    LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Temporary directory for this test process
+pub fn temp_dir() -> PathBuf {
+    TEMP_DIR.clone()
+}
+
+/// Copy a save in testdata/saves to the temporary directory, and returns the path of the copy
+pub fn copy_save_fixture(name: &str) -> PathBuf {
+    let src = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("testdata/saves")
+        .join(name);
+    let dest = temp_dir().join("saves").join(name);
+    if dest.exists() {
+        std::fs::remove_dir_all(&dest).unwrap();
+    }
+    for entry in walkdir::WalkDir::new(&src) {
+        let entry = entry.unwrap();
+        let path = dest.join(entry.path().strip_prefix(&src).unwrap());
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(path).unwrap();
+        } else {
+            std::fs::copy(entry.path(), path).unwrap();
+        }
+    }
+    dest
+}
+
+fn write_pak() -> PathBuf {
+    let dir = temp_dir().join("paks");
+    std::fs::create_dir_all(&dir).unwrap();
+    // Objects not found by id are substituted by the first one of each kind
+    let objects = vec![
+        Object::AnimImg(AnimImgObject {
+            id: "!test-anim".to_owned(),
+            img: empty_img(),
+        }),
+        Object::Effect(EffectObject {
+            id: "!test-effect".to_owned(),
+            img: empty_img(),
+        }),
+        Object::CharaTemplate(CharaTemplateObject {
+            id: CHARA_TEMPLATE_ID.to_owned(),
+            img: empty_img(),
+            race: Race::Human,
+            gen_weight: 1.0,
+            gen_level: 1,
+            default_ai_kind: NpcAIKind::None,
+            base_attr: CharaBaseAttr {
+                base_hp: 100,
+                str: 10,
+                vit: 10,
+                dex: 10,
+                int: 10,
+                wil: 10,
+                cha: 10,
+                spd: 100,
+            },
+        }),
+        Object::Tile(TileObject {
+            id: "!test-tile".to_owned(),
+            img: empty_img(),
+            kind: TileKind::Ground,
+            symbol_color: (0, 0, 0),
+        }),
+        Object::Wall(WallObject {
+            id: WALL_ID.to_owned(),
+            base_draw: true,
+            img: empty_img(),
+            symbol_color: (0, 0, 0),
+            build_skill: None,
+            materials: None,
+        }),
+        Object::Item(item_obj(BOW_ID, ItemKind::Weapon(WeaponKind::Bow))),
+        Object::Item(item_obj(ARROW_ID, ItemKind::Ammo(AmmoKind::Arrow))),
+        Object::Item(ItemObject {
+            harvest: Some(Harvest {
+                harvest_type: HarvestType::Crop,
+                target_item: CROP_ID.to_owned(),
+                difficulty: 0,
+                n_yield: 1,
+                growing_hours: 24,
+            }),
+            ..item_obj(CROP_ID, ItemKind::Object)
+        }),
+        Object::Script(ScriptObject {
+            id: SCRIPT_ID.to_owned(),
+            script: test_script(),
+        }),
+        Object::SiteGen(SiteGenObject {
+            id: TOWN_ID.to_owned(),
+            kind: SiteKind::Town,
+            map_template_id: Vec::new(),
+            unique_citizens: Vec::new(),
+            shops: Vec::new(),
+            trainers: vec![TrainerGenData {
+                chara_n: 0,
+                skills: vec![SkillKind::Defence],
+            }],
+            triggers: Vec::new(),
+        }),
+    ];
+
+    let mut builder = tar::Builder::new(std::fs::File::create(dir.join("test.pak")).unwrap());
+    for object in &objects {
+        let mut data = Vec::new();
+        write_object(&mut data, object).unwrap();
+        let mut header = tar::Header::new_gnu();
+        header.set_path(object.get_id()).unwrap();
+        header.set_size(data.len() as u64);
+        header.set_cksum();
+        builder.append(&header, &data[..]).unwrap();
+    }
+    builder.finish().unwrap();
+    dir
+}
+
+/// Sections set variables of each scope, and copy them to global variables to check
+fn test_script() -> Script {
+    let int = |n| Expr::Value(Value::Int(n));
+    let gset = |name: &str, expr| Instruction::GSet(name.to_owned(), expr);
+    let sections = vec![
+        (
+            "set",
+            vec![
+                Instruction::LSet("n".to_owned(), int(1)),
+                Instruction::CSet("n".to_owned(), int(2)),
+                Instruction::SSet("n".to_owned(), int(3)),
+                gset("local", Expr::LVar("n".to_owned())),
+                gset("chara", Expr::CVar("n".to_owned())),
+                gset("site", Expr::SVar("n".to_owned())),
+            ],
+        ),
+        (
+            "get",
+            vec![
+                gset("local", Expr::LVar("n".to_owned())),
+                gset("chara", Expr::CVar("n".to_owned())),
+                gset("site", Expr::SVar("n".to_owned())),
+            ],
+        ),
+        ("set_site", vec![Instruction::SSet("n".to_owned(), int(4))]),
+    ];
+    Script::from_map(
+        sections
+            .into_iter()
+            .map(|(name, instructions)| (name.to_owned(), instructions))
+            .collect(),
+    )
+}
+
+fn item_obj(id: &str, kind: ItemKind) -> ItemObject {
+    ItemObject {
+        id: id.to_owned(),
+        img: empty_img(),
+        kind,
+        group: String::new(),
+        default_flags: ItemFlags::empty(),
+        basic_price: 1,
+        w: 100,
+        gen_weight: 0.0,
+        shop_weight: 0.0,
+        gen_level: 0,
+        dice_n: 1,
+        dice_x: 4,
+        element: Element::Physical,
+        def: ElementArray::default(),
+        eff: 0,
+        magical_effect: MagicalEffect::None,
+        medical_effect: MedicalEffect::None,
+        tool_effect: ToolEffect::None,
+        use_effect: UseEffect::None,
+        nutrition: 0,
+        charge: [0, 0],
+        harvest: None,
+        facility: None,
+        light: 0,
+    }
+}
+
+fn empty_img() -> Img {
+    Img {
+        data: Vec::new(),
+        w: 1,
+        h: 1,
+        grid_nx: 1,
+        grid_ny: 1,
+        n_frame: 1,
+        n_pattern: 1,
+        n_anim_frame: 1,
+        duration: 0,
+    }
+}
+
+/// Game data with a region map and a site with one floor. Tests can add the second floor.
+/// The player is on the site map at (1, 1).
+pub fn gen_gd() -> GameData {
+    let mut gd = GameData::empty();
+    gd.meta.set_save_name("test");
+    let (w, h) = MAP_SIZE;
+    let rid = gd.region.add_region(Region::new("test", Map::new(w, h), 0));
+    let sid = gd
+        .add_site(Site::new(2), SiteKind::Other, rid, Vec2d(1, 1))
+        .unwrap();
+    let mid = gd.add_map(Map::new(w, h), sid, 1);
+    gd.set_initial_mapid(mid);
+
+    let mut player = create_chara(gobj::id_to_idx(CHARA_TEMPLATE_ID), 1);
+    player.rel = Relationship::ALLY;
+    player.name = Some("test".to_owned());
+    let cid = gd.add_chara(player, CharaKind::Player);
+    gd.region.get_map_mut(mid).locate_chara(cid, Vec2d(1, 1));
+    gd.time = GameTime::new(
+        rules::RULES.params.initial_date_year,
+        rules::RULES.params.initial_date_month,
+        rules::RULES.params.initial_date_day,
+        12,
+    );
+    gd
+}
+
+/// Add a character to the current map
+pub fn add_npc(gd: &mut GameData, pos: Vec2d, rel: Relationship) -> CharaId {
+    let mut chara = create_chara(gobj::id_to_idx(CHARA_TEMPLATE_ID), 1);
+    chara.rel = rel;
+    let mid = gd.get_current_mapid();
+    let cid = gd.add_chara_to_map(chara, mid);
+    gd.region.get_map_mut(mid).locate_chara(cid, pos);
+    cid
+}
+
+/// Put walls on the current map
+pub fn set_walls(gd: &mut GameData, walls: &[Vec2d]) {
+    let wall_idx = gobj::id_to_idx(WALL_ID);
+    let map = gd.get_current_map_mut();
+    for &p in walls {
+        map.tile[p].wall = WallIdxPP::new(wall_idx);
+    }
+}
+
+/// Add a town site to the region. The trainer of the town is the citizen 0.
+pub fn add_town(gd: &mut GameData) -> SiteId {
+    let rid = gd.get_current_mapid().rid();
+    let mut site = Site::new(1);
+    site.content = SiteContent::Town {
+        town: Box::new(Town::new(TOWN_ID)),
+    };
+    gd.add_site(site, SiteKind::Town, rid, Vec2d(2, 2)).unwrap()
+}
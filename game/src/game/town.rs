@@ -32,6 +32,7 @@ pub fn add_town(gd: &mut GameData, rid: RegionId, pos: Vec2d, town_id: &str) {
     }
 
     super::site::gen::add_unique_citizens(gd, sid, sg);
+    super::site::gen::add_event_triggers(gd, sid, sg);
 
     // Add symbol to region map
     {
@@ -0,0 +1,142 @@
+//! Skill training by trainers and skill books
+
+use super::extrait::*;
+use super::skill::SkillListEx;
+use common::gamedata::*;
+use common::gobj;
+use common::sitegen::SiteGenObject;
+use rules::RULES;
+
+/// Skills the trainer can raise. Empty if the character is not a trainer.
+pub fn trainer_skills(gd: &GameData, trainer: CharaId) -> &'static [SkillKind] {
+    let (sid, n) = match trainer {
+        CharaId::OnSite { sid, n } => (sid, n),
+        _ => return &[],
+    };
+    let town = match &gd.region.get_site(sid).content {
+        SiteContent::Town { town } => town,
+        _ => return &[],
+    };
+    let sg: &'static SiteGenObject = gobj::get_by_id(town.id());
+    sg.trainers
+        .iter()
+        .find(|t| t.chara_n == n)
+        .map_or(&[], |t| t.skills.as_slice())
+}
+
+/// Money to raise the skill by one level
+pub fn training_cost(chara: &Chara, kind: SkillKind) -> i64 {
+    let lv = chara.skills.get(kind) as i64;
+    RULES.chara.skill_training_cost * (lv + 1) * (lv + 1)
+}
+
+/// Raise the player's skill by one level at the trainer. Returns true if succeeded.
+pub fn train_skill(gd: &mut GameData, trainer: CharaId, kind: SkillKind) -> bool {
+    if !trainer_skills(gd, trainer).contains(&kind) {
+        warn!("{:?} cannot train {:?}", trainer, kind);
+        return false;
+    }
+
+    let player = gd.chara.get(CharaId::Player);
+    let (cap, _) = RULES.chara.skill_aptitude(player.class, kind);
+    if player.skills.get(kind) >= cap {
+        game_log_i!("skill-reach-cap"; chara=player, skill=kind);
+        return false;
+    }
+    let cost = training_cost(player, kind);
+    if !gd.player.has_money(cost) {
+        game_log_i!("training-lack-of-money"; chara=player);
+        return false;
+    }
+
+    gd.player.sub_money(cost);
+    let player = gd.chara.get_mut(CharaId::Player);
+    let lv = player.skills.get(kind);
+    if lv == 0 {
+        player.skills.learn_new_skill(kind);
+    } else {
+        player.skills.set_skill_level(kind, lv + 1);
+    }
+    player.update();
+    game_log_i!("training-succeed"; chara=player, skill=kind);
+    true
+}
+
+/// Read a skill book. Unknown skills are learned, and others gain exp.
+/// Returns false if the book is not consumed.
+pub fn read_skill_book(chara: &mut Chara, kind: SkillKind) -> bool {
+    let (cap, _) = RULES.chara.skill_aptitude(chara.class, kind);
+    let lv = chara.skills.get(kind);
+    if lv >= cap {
+        game_log_i!("skill-reach-cap"; chara=chara, skill=kind);
+        return false;
+    }
+
+    game_log_i!("read-skill-book"; chara=chara, skill=kind);
+    if lv == 0 {
+        chara.skills.learn_new_skill(kind);
+        game_log_i!("learn-skill"; chara=chara, skill=kind);
+        chara.update();
+    } else {
+        chara.add_reading_exp(kind);
+    }
+    true
+}
+
+#[test]
+fn train_skill_test() {
+    use super::testutil;
+
+    let _lock = testutil::init();
+    let mut gd = testutil::gen_gd();
+    let sid = testutil::add_town(&mut gd);
+    let trainer = CharaId::OnSite { sid, n: 0 };
+    let kind = SkillKind::Defence;
+    gd.chara.get_mut(CharaId::Player).class = CharaClass::Warrior;
+    let cost = RULES.chara.skill_training_cost;
+    gd.player.set_money(cost + cost * 4);
+
+    // The cost is multiplied by the square of (skill level + 1)
+    assert!(train_skill(&mut gd, trainer, kind));
+    assert_eq!(gd.player.money(), cost * 4);
+    assert!(train_skill(&mut gd, trainer, kind));
+    assert_eq!(gd.player.money(), 0);
+    assert_eq!(gd.chara.get(CharaId::Player).skills.get(kind), 2);
+    assert!(!train_skill(&mut gd, trainer, kind));
+
+    // The trainer cannot raise other skills
+    gd.player.set_money(cost);
+    assert!(!train_skill(&mut gd, trainer, SkillKind::Throwing));
+
+    // Not trained over the cap
+    let (cap, _) = RULES.chara.skill_aptitude(CharaClass::Warrior, kind);
+    let player = gd.chara.get_mut(CharaId::Player);
+    player.skills.set_skill_level(kind, cap);
+    gd.player.set_money(training_cost(player, kind));
+    assert!(!train_skill(&mut gd, trainer, kind));
+    assert_eq!(gd.chara.get(CharaId::Player).skills.get(kind), cap);
+}
+
+#[test]
+fn read_skill_book_test() {
+    use super::testutil;
+
+    let _lock = testutil::init();
+    let mut gd = testutil::gen_gd();
+    let chara = gd.chara.get_mut(CharaId::Player);
+    chara.class = CharaClass::Warrior;
+    let kind = SkillKind::Defence;
+
+    // Unknown skills are learned
+    assert!(read_skill_book(chara, kind));
+    assert_eq!(chara.skills.get(kind), 1);
+    let level_exp = chara.skills.get_level_exp(kind);
+    assert!(read_skill_book(chara, kind));
+    assert!(chara.skills.get_level_exp(kind) > level_exp);
+
+    // The book is not consumed at the cap
+    let (cap, _) = RULES.chara.skill_aptitude(CharaClass::Warrior, kind);
+    chara.skills.set_skill_level(kind, cap);
+    assert!(!read_skill_book(chara, kind));
+    assert_eq!(chara.skills.get(kind), cap);
+}
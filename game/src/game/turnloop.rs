@@ -5,6 +5,7 @@ use super::DialogOpenRequest;
 use super::{Game, GameState};
 use common::basic::WAIT_TIME_NUMERATOR;
 use common::gamedata::*;
+use rules::weather::factor;
 use rules::RULES;
 
 pub fn turn_loop(game: &mut Game) {
@@ -84,7 +85,9 @@ fn remove_dying_charas(game: &mut Game) -> bool {
 fn advance_game_time(game: &mut Game, advanced_clock: u32) {
     let mid = game.gd.get_current_mapid();
     let minutes_per_turn = if mid.is_region_map() {
-        RULES.params.minutes_per_turn_region
+        // Bad weather makes travelling on the region map slow
+        let weather = game.gd.region.get(mid.rid()).weather.kind;
+        RULES.params.minutes_per_turn_region * factor(&RULES.weather.region_move_factor, weather)
     } else {
         RULES.params.minutes_per_turn_normal
     };
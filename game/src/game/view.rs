@@ -0,0 +1,171 @@
+//! This module processes the view of characters
+
+use crate::game::Game;
+use crate::game::InfoGetter;
+use common::gamedata::*;
+use common::gobj;
+use geom::*;
+
+/// The cache for determining player's view
+pub struct ViewMap {
+    visible: Array2d<bool>,
+    /// Lit tiles on dark maps. None if the current map is not dark.
+    lit: Option<Array2d<bool>>,
+}
+
+impl ViewMap {
+    pub fn new() -> ViewMap {
+        ViewMap {
+            visible: Array2d::new(128, 128, false),
+            lit: None,
+        }
+    }
+
+    fn reserve_size(&mut self, w: u32, h: u32) {
+        let size = self.visible.size();
+        if size.0 < w || size.1 < h {
+            use std::cmp::max;
+            self.visible = Array2d::new(max(size.0, w), max(size.1, h), false);
+        }
+    }
+
+    fn fill(&mut self, w: u32, h: u32, value: bool) {
+        for ny in 0..h {
+            for nx in 0..w {
+                self.visible[(nx, ny)] = value;
+            }
+        }
+    }
+
+    pub fn get_tile_visible(&self, pos: Vec2d) -> bool {
+        if self.visible.in_range(pos) {
+            self.visible[pos]
+        } else {
+            false
+        }
+    }
+
+    /// Characters on lit tiles can be seen by others on dark maps
+    pub fn is_lit(&self, pos: Vec2d) -> bool {
+        self.lit
+            .as_ref()
+            .map_or(true, |lit| lit.in_range(pos) && lit[pos])
+    }
+}
+
+pub fn update_view_map(game: &mut Game) {
+    let map = game.gd.get_current_map();
+    let (w, h) = map.size();
+    let view_map = &mut game.view_map;
+    view_map.reserve_size(w, h);
+
+    view_map.lit = None;
+    if game.gd.get_current_mapid().is_region_map() {
+        view_map.fill(w, h, true); // Fill by true when region map
+        return;
+    }
+
+    // Fill by false
+    view_map.fill(w, h, false); // Fill by false
+
+    let player = game.gd.chara.get(CharaId::Player);
+    let player_pos = game.gd.player_pos();
+    let player_view_range = game.gd.view_range(CharaId::Player);
+    let visible = &mut view_map.visible;
+
+    if !map.dark {
+        shadowcasting(
+            player_pos,
+            player_view_range,
+            |pos| blocks_sight(map, pos),
+            |pos| {
+                if map.is_inside(pos) {
+                    visible[pos] = true;
+                }
+            },
+        );
+        return;
+    }
+
+    // On dark maps, player can see lit tiles and adjacent tiles only
+    let lit = light_map(map, player_pos, chara_light(player));
+    shadowcasting(
+        player_pos,
+        player_view_range,
+        |pos| blocks_sight(map, pos),
+        |pos| {
+            if map.is_inside(pos) && (lit[pos] || pos.mdistance(player_pos) <= 1) {
+                visible[pos] = true;
+            }
+        },
+    );
+    view_map.lit = Some(lit);
+}
+
+/// Returns the distance between two tiles if they are in sight of each other within the range
+pub fn calc_visual_distance(map: &Map, orig: Vec2d, dist: Vec2d, range: i32) -> Option<i32> {
+    let distance = dist.mdistance(orig);
+    if distance > range {
+        return None;
+    }
+    if is_visible(orig, dist, distance, |pos| blocks_sight(map, pos)) {
+        Some(distance)
+    } else {
+        None
+    }
+}
+
+fn blocks_sight(map: &Map, pos: Vec2d) -> bool {
+    !map.is_inside(pos) || !map.tile[pos].wall.is_empty()
+}
+
+/// Radius of the light carried by the character
+fn chara_light(chara: &Chara) -> u32 {
+    chara
+        .item_list
+        .iter()
+        .map(|(item, _)| item.obj().light)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Calculate tiles lit by lit rooms, light sources on the map, and the light carried by player
+fn light_map(map: &Map, player_pos: Vec2d, player_light: u32) -> Array2d<bool> {
+    let mut lit = Array2d::new(map.w, map.h, false);
+    let mut light_sources = Vec::new();
+
+    for (pos, tile) in map.tile.iter_with_idx() {
+        if tile.lit {
+            lit[pos] = true;
+        }
+        let deco_light = tile.deco.map_or(0, |deco| gobj::get_obj(deco).light);
+        let item_light = tile.item_list.as_ref().map_or(0, |item_list| {
+            item_list
+                .iter()
+                .map(|(item, _)| item.obj().light)
+                .max()
+                .unwrap_or(0)
+        });
+        let light = std::cmp::max(deco_light, item_light);
+        if light > 0 {
+            light_sources.push((pos, light));
+        }
+    }
+    if player_light > 0 {
+        light_sources.push((player_pos, player_light));
+    }
+
+    for (pos, light) in light_sources {
+        shadowcasting(
+            pos,
+            light as i32,
+            |p| blocks_sight(map, p),
+            |p| {
+                if map.is_inside(p) {
+                    lit[p] = true;
+                }
+            },
+        );
+    }
+    lit
+}
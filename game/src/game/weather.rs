@@ -0,0 +1,155 @@
+//! Changes weather of regions, and applies its effects
+
+use super::item::ItemEx;
+use super::InfoGetter;
+use common::gamedata::*;
+use common::gobj;
+use rng::gen_range;
+use rules::weather::factor;
+use rules::RULES;
+
+/// Change the weather of the current region if its time has come.
+/// Also grows crops and updates the ambient sound for the current map.
+pub fn update_weather(gd: &mut GameData) {
+    let now = gd.time.current_time();
+    let rid = gd.get_current_mapid().rid();
+    let weather = gd.region.get(rid).weather;
+
+    if weather.next_change <= now {
+        let season = now.into_date().season();
+        let kind = choose_weather(season, &gd.region.get(rid).name);
+        let [min, max] = RULES.weather.duration_hours;
+        let hours = gen_range(min, max + 1) as u64;
+        let mut next_change = now;
+        next_change.advance(hours * SECS_PER_HOUR);
+
+        let weather = &mut gd.region.get_mut(rid).weather;
+        weather.next_change = next_change;
+        if weather.kind != kind {
+            weather.kind = kind;
+            if gd.is_open_air(gd.get_current_mapid()) {
+                game_log_i!("weather-change"; weather=kind);
+            }
+        }
+    }
+
+    grow_crops(gd);
+    update_ambience(gd);
+}
+
+/// Play the ambient sound for the weather on open-air maps
+fn update_ambience(gd: &GameData) {
+    let mid = gd.get_current_mapid();
+    let ambience = if gd.is_open_air(mid) {
+        let kind = gd.region.get(mid.rid()).weather.kind;
+        RULES.weather.ambience.get(&kind)
+    } else {
+        None
+    };
+    crate::audio::play_ambience(ambience.map(|s| s.as_str()));
+}
+
+fn choose_weather(season: Season, region: &str) -> WeatherKind {
+    let weights = RULES.weather.weights(season, region);
+    let sum: f32 = weights.iter().map(|(_, w)| w).sum();
+    if sum.is_nan() || sum <= 0.0 {
+        return WeatherKind::Clear;
+    }
+
+    let r = gen_range(0.0, sum);
+    let mut sum = 0.0;
+    for (kind, w) in &weights {
+        sum += w;
+        if r < sum {
+            return *kind;
+        }
+    }
+    WeatherKind::Clear
+}
+
+/// Crops on the current open-air map grow every hour.
+/// The growth while the player was away is caught up when the player returns to the map.
+fn grow_crops(gd: &mut GameData) {
+    let now = gd.time.current_time();
+    let mid = gd.get_current_mapid();
+    if !gd.is_open_air(mid) {
+        return;
+    }
+    let kind = gd.region.get(mid.rid()).weather.kind;
+    let map = gd.get_current_map_mut();
+
+    if map.last_growth == Time::default() {
+        map.last_growth = now;
+        return;
+    }
+    let hours = now.duration_from(map.last_growth).as_hours();
+    if hours < 1 {
+        return;
+    }
+    map.last_growth.advance(hours as u64 * SECS_PER_HOUR);
+
+    let speed = RULES
+        .weather
+        .crop_growth_season
+        .get(&now.into_date().season())
+        .copied()
+        .unwrap_or(1.0)
+        * factor(&RULES.weather.crop_growth_factor, kind);
+    let n = (speed * 100.0) as u32 * hours as u32;
+
+    for p in map.tile.iter_idx() {
+        let item_list = if let Some(item_list) = map.tile[p].item_list.as_mut() {
+            item_list
+        } else {
+            continue;
+        };
+        for (item, _) in item_list.items.iter_mut() {
+            let is_crop = gobj::get_obj(item.idx)
+                .harvest
+                .as_ref()
+                .is_some_and(|h| h.harvest_type == HarvestType::Crop);
+            if is_crop {
+                item.add_growth(n);
+            }
+        }
+    }
+}
+
+#[test]
+fn grow_crops_test() {
+    use super::item::gen::gen_item_from_idx;
+    use super::testutil;
+    use geom::Vec2d;
+
+    let _lock = testutil::init();
+    let mut gd = testutil::gen_gd();
+    let sid = testutil::add_town(&mut gd);
+    let (w, h) = testutil::MAP_SIZE;
+    let mid = gd.add_map(Map::new(w, h), sid, 3);
+    gd.set_current_mapid(mid);
+    let crop = gen_item_from_idx(gobj::id_to_idx(testutil::CROP_ID));
+    gd.get_current_map_mut().locate_item(crop, Vec2d(2, 2), 1);
+    let growth = |gd: &GameData| {
+        let item_list = gd.get_current_map().tile[Vec2d(2, 2)].item_list.as_ref();
+        item_list.unwrap().items[0]
+            .0
+            .attributes
+            .iter()
+            .find_map(|attr| match attr {
+                ItemAttribute::Growth { n } => Some(*n),
+                _ => None,
+            })
+    };
+
+    grow_crops(&mut gd);
+    assert_eq!(growth(&gd), None);
+    gd.time.advance(SECS_PER_HOUR);
+    grow_crops(&mut gd);
+    let n = growth(&gd).unwrap();
+    assert!(n > 0);
+
+    // Hours while the map is not updated are caught up at once
+    gd.time.advance(5 * SECS_PER_HOUR);
+    grow_crops(&mut gd);
+    assert_eq!(growth(&gd), Some(n * 6));
+}
@@ -0,0 +1,27 @@
+//! Game logic, texts and configurations shared by the frontends.
+//! Without the "audio" feature, this crate does not depend on SDL, so it can be used without a window.
+
+#[cfg(feature = "audio")]
+extern crate rusted_ruins_audio as audio;
+extern crate rusted_ruins_common as common;
+extern crate rusted_ruins_geom as geom;
+extern crate rusted_ruins_map_generator as map_generator;
+extern crate rusted_ruins_rng as rng;
+extern crate rusted_ruins_rules as rules;
+#[macro_use]
+extern crate lazy_static;
+#[macro_use]
+extern crate serde_derive;
+#[macro_use]
+extern crate log as applog;
+
+#[macro_use]
+pub mod log;
+#[macro_use]
+pub mod util;
+#[macro_use]
+pub mod text;
+#[cfg(not(feature = "audio"))]
+mod audio;
+pub mod config;
+pub mod game;
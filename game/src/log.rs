@@ -95,41 +95,43 @@ pub fn latest_line() -> usize {
     gamelog.line_count
 }
 
+#[macro_export]
 macro_rules! game_log {
     ($id:expr) => {
         $crate::log::push($crate::text::log_txt($id));
     };
     ($id:expr; $($target:ident = $value:expr),*) => {{
-        use crate::text::ToText;
-        let mut table: std::collections::HashMap<&str, fluent::FluentValue>
+        use $crate::text::ToText;
+        let mut table: std::collections::HashMap<&str, $crate::text::FluentValue>
             = std::collections::HashMap::new();
         $(
-            let value = fluent::FluentValue::String($value.to_text());
+            let value = $crate::text::FluentValue::String($value.to_text());
             table.insert(stringify!($target), value);
         )*
 
-        let s = crate::text::log_txt_with_args($id, Some(&table));
-        crate::log::push(s);
+        let s = $crate::text::log_txt_with_args($id, Some(&table));
+        $crate::log::push(s);
     }}
 }
 
 /// Instantly add a new line after logging
+#[macro_export]
 macro_rules! game_log_i {
     ($id:expr) => {
         $crate::log::push($crate::text::log_txt($id));
         $crate::log::new_line()
     };
     ($id:expr; $($target:ident = $value:expr),*) => {{
-        use crate::text::ToText;
-        let mut table: std::collections::HashMap<&str, fluent::FluentValue>
+        use $crate::text::ToText;
+        let mut table: std::collections::HashMap<&str, $crate::text::FluentValue>
             = std::collections::HashMap::new();
         $(
-            let value = fluent::FluentValue::String($value.to_text());
+            let value = $crate::text::FluentValue::String($value.to_text());
             table.insert(stringify!($target), value);
         )*
 
-        let s = crate::text::log_txt_with_args($id, Some(&table));
-        crate::log::push(s);
-        crate::log::new_line();
+        let s = $crate::text::log_txt_with_args($id, Some(&table));
+        $crate::log::push(s);
+        $crate::log::new_line();
     }}
 }
@@ -3,6 +3,8 @@ mod to_text;
 
 use crate::config;
 use common::basic;
+#[doc(hidden)]
+pub use fluent::FluentValue;
 use fluent::*;
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -214,6 +216,11 @@ pub fn misc_txt_with_args(id: &str, args: Option<&HashMap<&str, FluentValue>>) -
     }
 }
 
+/// Name of the spell from its id
+pub fn spell_txt(id: &str) -> String {
+    misc_txt(&format!("spell-{}", id))
+}
+
 /// This is helper trait for some data objects that need to be printed in game.
 /// Logging macros use this.
 pub trait ToText {
@@ -230,28 +237,30 @@ pub fn to_txt<T: ToTextId>(a: &T) -> String {
     misc_txt(a.to_textid())
 }
 
+#[macro_export]
 macro_rules! misc_txt_format {
     ($id:expr; $($target:ident = $value:expr),*) => {{
-        let mut table: std::collections::HashMap<&str, fluent::FluentValue>
+        let mut table: std::collections::HashMap<&str, $crate::text::FluentValue>
             = std::collections::HashMap::new();
         $(
-            let value = fluent::FluentValue::String($value.to_text());
+            let value = $crate::text::FluentValue::String($value.to_text());
             table.insert(stringify!($target), value);
         )*
 
-        crate::text::misc_txt_with_args($id, Some(&table))
+        $crate::text::misc_txt_with_args($id, Some(&table))
     }}
 }
 
+#[macro_export]
 macro_rules! ui_txt_format {
     ($id:expr; $($target:ident = $value:expr),*) => {{
-        let mut table: std::collections::HashMap<&str, fluent::FluentValue>
+        let mut table: std::collections::HashMap<&str, $crate::text::FluentValue>
             = std::collections::HashMap::new();
         $(
-            let value = fluent::FluentValue::String($value.to_text());
+            let value = $crate::text::FluentValue::String($value.to_text());
             table.insert(stringify!($target), value);
         )*
 
-        crate::text::ui_txt_with_args($id, Some(&table))
+        $crate::text::ui_txt_with_args($id, Some(&table))
     }}
 }
@@ -12,15 +12,15 @@ impl ToTextId for DungeonKind {
     }
 }
 
-impl ToTextId for CharaStatus {
+impl ToTextId for CharaClass {
     fn to_textid(&self) -> &'static str {
-        match *self {
-            CharaStatus::Hungry => "chara_status-hungry",
-            CharaStatus::Weak => "chara_status-weak",
-            CharaStatus::Starving => "chara_status-starving",
-            CharaStatus::Asleep { .. } => "chara_status-asleep",
-            CharaStatus::Poisoned => "chara_status-poisoned",
-            CharaStatus::Creation { .. } => "chara_status-creation",
+        match self {
+            CharaClass::None => "chara_class-none",
+            CharaClass::Adventurer => "chara_class-adventurer",
+            CharaClass::Rogue => "chara_class-rogue",
+            CharaClass::Sorcerer => "chara_class-sorcerer",
+            CharaClass::Warrior => "chara_class-warrior",
+            CharaClass::Civilian => "chara_class-civilian",
         }
     }
 }
@@ -39,6 +39,7 @@ impl ToTextId for ItemKind {
             Special => "item_kind-special",
             Material => "item_kind-material",
             Object => "item_kind-object",
+            Ammo(ammo_kind) => ammo_kind.to_textid(),
         }
     }
 }
@@ -54,6 +55,18 @@ impl ToTextId for SkillKind {
             Evasion => "skill_kind-evasion",
             MagicDevice => "skill_kind-magic_device",
             Weapon(weapon_kind) => weapon_kind.to_textid(),
+            Throwing => "skill_kind-throwing",
+            Magic(school) => school.to_textid(),
+        }
+    }
+}
+
+impl ToTextId for MagicSchool {
+    fn to_textid(&self) -> &'static str {
+        use MagicSchool::*;
+        match self {
+            Destruction => "magic_school-destruction",
+            Enhancement => "magic_school-enhancement",
         }
     }
 }
@@ -73,6 +86,17 @@ impl ToTextId for WeaponKind {
     }
 }
 
+impl ToTextId for AmmoKind {
+    fn to_textid(&self) -> &'static str {
+        use AmmoKind::*;
+        match self {
+            Arrow => "ammo_kind-arrow",
+            Bolt => "ammo_kind-bolt",
+            Bullet => "ammo_kind-bullet",
+        }
+    }
+}
+
 impl ToTextId for ArmorKind {
     fn to_textid(&self) -> &'static str {
         use ArmorKind::*;
@@ -82,3 +106,16 @@ impl ToTextId for ArmorKind {
         }
     }
 }
+
+impl ToTextId for WeatherKind {
+    fn to_textid(&self) -> &'static str {
+        use WeatherKind::*;
+        match self {
+            Clear => "weather-clear",
+            Rain => "weather-rain",
+            Snow => "weather-snow",
+            Fog => "weather-fog",
+            Storm => "weather-storm",
+        }
+    }
+}
@@ -84,6 +84,8 @@ impl ToText for Command {
             DrinkItem => "command-drink_item",
             EatItem => "command-eat_item",
             ReleaseItem => "command-release_item",
+            ThrowItem => "command-throw_item",
+            CastSpell => "command-cast_spell",
             TargetingMode => "command-targetting_mode",
             TextInput { .. } => "command-text_input",
             TextDelete => "command-text_delete",
@@ -96,6 +98,33 @@ impl ToText for Command {
     }
 }
 
+impl ToText for CharaStatus {
+    fn to_text(&self) -> Cow<'_, str> {
+        let name = misc_txt(&format!("chara_status-{}", self.id));
+        if self.stacks > 1 {
+            format!("{} x{}", name, self.stacks).into()
+        } else {
+            name.into()
+        }
+    }
+}
+
+impl ToText for Element {
+    fn to_text(&self) -> Cow<'_, str> {
+        use Element::*;
+        match self {
+            None => misc_txt("element-none"),
+            Physical => misc_txt("element-physical"),
+            Fire => misc_txt("element-fire"),
+            Cold => misc_txt("element-cold"),
+            Shock => misc_txt("element-shock"),
+            Poison => misc_txt("element-poison"),
+            Spirit => misc_txt("element-spirit"),
+        }
+        .into()
+    }
+}
+
 impl ToText for MedicalEffect {
     fn to_text(&self) -> Cow<str> {
         use MedicalEffect::*;
@@ -118,6 +147,7 @@ impl ToText for Quest {
                 table.insert("monster", fluent::FluentValue::String(idx.to_text()));
                 crate::text::misc_txt_with_args("quest-slay_monsters", Some(&table)).into()
             }
+            Quest::Custom { id } => misc_txt(&format!("quest-{}", id)).into(),
         }
     }
 }
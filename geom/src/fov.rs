@@ -0,0 +1,196 @@
+//! Field of view calculation by symmetric shadowcasting
+
+use super::Vec2d;
+
+/// Slope of a row boundary. The denominator is always positive.
+#[derive(Clone, Copy, Debug)]
+struct Slope {
+    num: i32,
+    den: i32,
+}
+
+impl Slope {
+    /// The slope to the left edge of the tile
+    fn new(depth: i32, col: i32) -> Slope {
+        Slope {
+            num: 2 * col - 1,
+            den: 2 * depth,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Row {
+    depth: i32,
+    start: Slope,
+    end: Slope,
+}
+
+impl Row {
+    fn min_col(&self) -> i32 {
+        // Round depth * start, ties up
+        (2 * self.depth * self.start.num + self.start.den).div_euclid(2 * self.start.den)
+    }
+
+    fn max_col(&self) -> i32 {
+        // Round depth * end, ties down
+        -(self.end.den - 2 * self.depth * self.end.num).div_euclid(2 * self.end.den)
+    }
+
+    /// The center of the tile is in the sector or not
+    fn is_symmetric(&self, col: i32) -> bool {
+        col * self.start.den >= self.depth * self.start.num
+            && col * self.end.den <= self.depth * self.end.num
+    }
+
+    fn next(&self) -> Row {
+        Row {
+            depth: self.depth + 1,
+            ..*self
+        }
+    }
+}
+
+/// Transforms (depth, col) to the offset from origin for north, south, east and west quadrants
+const QUADRANTS: [(i32, i32, i32, i32); 4] =
+    [(1, 0, 0, -1), (1, 0, 0, 1), (0, 1, 1, 0), (0, -1, 1, 0)];
+
+/// Calculate field of view from origin by symmetric shadowcasting.
+/// If a floor tile is visible from another, the opposite is also true.
+/// `is_opaque` must return true for tiles outside of the map,
+/// and `set_visible` is called for each visible tile within `range` in manhattan distance.
+pub fn shadowcasting<B, V>(origin: Vec2d, range: i32, mut is_opaque: B, mut set_visible: V)
+where
+    B: FnMut(Vec2d) -> bool,
+    V: FnMut(Vec2d),
+{
+    set_visible(origin);
+
+    for &(xc, xd, yc, yd) in &QUADRANTS {
+        let transform = |depth: i32, col: i32| {
+            Vec2d(
+                origin.0 + col * xc + depth * xd,
+                origin.1 + col * yc + depth * yd,
+            )
+        };
+        let mut rows = vec![Row {
+            depth: 1,
+            start: Slope { num: -1, den: 1 },
+            end: Slope { num: 1, den: 1 },
+        }];
+
+        while let Some(mut row) = rows.pop() {
+            if row.depth > range {
+                continue;
+            }
+            let mut prev_opaque: Option<bool> = None;
+
+            for col in row.min_col()..=row.max_col() {
+                let pos = transform(row.depth, col);
+                let opaque = is_opaque(pos);
+
+                if (opaque || row.is_symmetric(col)) && origin.mdistance(pos) <= range {
+                    set_visible(pos);
+                }
+                match prev_opaque {
+                    Some(true) if !opaque => {
+                        row.start = Slope::new(row.depth, col);
+                    }
+                    Some(false) if opaque => {
+                        let mut next_row = row.next();
+                        next_row.end = Slope::new(row.depth, col);
+                        rows.push(next_row);
+                    }
+                    _ => (),
+                }
+                prev_opaque = Some(opaque);
+            }
+
+            if prev_opaque == Some(false) {
+                rows.push(row.next());
+            }
+        }
+    }
+}
+
+/// Returns target is visible from origin or not
+pub fn is_visible<B>(origin: Vec2d, target: Vec2d, range: i32, is_opaque: B) -> bool
+where
+    B: FnMut(Vec2d) -> bool,
+{
+    let mut visible = false;
+    shadowcasting(origin, range, is_opaque, |pos| {
+        if pos == target {
+            visible = true;
+        }
+    });
+    visible
+}
+
+#[test]
+fn is_visible_test() {
+    use super::{Array2d, RectIter};
+
+    // Each string is a row, and '#' is a wall
+    let layouts = [
+        vec![
+            "..........",
+            "...#......",
+            "...#..##..",
+            "......#...",
+            "..#.......",
+            "..#...#.#.",
+            "......#...",
+            "...##.....",
+        ],
+        vec![
+            "..........",
+            ".#.#.#.#..",
+            "..........",
+            ".#.#.#.#..",
+            "..........",
+            "####.#####",
+            "..........",
+            "..........",
+        ],
+    ];
+    let range = 6;
+
+    for layout in &layouts {
+        let (w, h) = (layout[0].len() as u32, layout.len() as u32);
+        let walls = Array2d::from_fn(w, h, |p| {
+            layout[p.1 as usize].as_bytes()[p.0 as usize] == b'#'
+        });
+        let is_opaque = |p: Vec2d| !walls.in_range(p) || walls[p];
+        let floors: Vec<Vec2d> = RectIter::new((0, 0), (w as i32 - 1, h as i32 - 1))
+            .filter(|p| !walls[*p])
+            .collect();
+
+        for &a in &floors {
+            for &b in &floors {
+                let visible = is_visible(a, b, range, is_opaque);
+                // Symmetric between floor tiles
+                assert_eq!(
+                    visible,
+                    is_visible(b, a, range, is_opaque),
+                    "{:?} {:?}",
+                    a,
+                    b
+                );
+                // Limited by the range
+                if a.mdistance(b) > range {
+                    assert!(!visible);
+                }
+            }
+        }
+    }
+
+    // Walls block the sight
+    let walls = Array2d::from_fn(5, 5, |p| p.0 == 2);
+    let is_opaque = |p: Vec2d| !walls.in_range(p) || walls[p];
+    assert!(!is_visible(Vec2d(0, 2), Vec2d(4, 2), 10, is_opaque));
+    assert!(!is_visible(Vec2d(1, 0), Vec2d(3, 4), 10, is_opaque));
+    assert!(is_visible(Vec2d(0, 2), Vec2d(1, 4), 10, is_opaque));
+    // The wall itself is visible
+    assert!(is_visible(Vec2d(0, 2), Vec2d(2, 2), 10, is_opaque));
+}
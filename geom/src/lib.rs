@@ -4,6 +4,10 @@ extern crate serde;
 #[macro_use]
 extern crate serde_derive;
 
+mod fov;
+
+pub use self::fov::*;
+
 use std::fmt;
 use std::ops::{Add, Index, IndexMut, Mul, Range, Sub};
 
@@ -2,10 +2,11 @@ use common::script::{Expr, Operator, Value};
 use nom::branch::alt;
 use nom::bytes::complete::tag;
 use nom::character::complete::{char, digit1, multispace0};
-use nom::combinator::complete;
+use nom::combinator::{complete, map_res};
 use nom::multi::fold_many0;
 use nom::sequence::{delimited, pair};
 use nom::IResult;
+use std::str::FromStr;
 
 trait Join {
     fn join(self, op: Operator, e: Expr) -> Expr;
@@ -71,6 +72,21 @@ fn gvar(input: &str) -> IResult<&str, Expr> {
     Ok((input, Expr::GVar(var_name)))
 }
 
+fn scoped_var(input: &str) -> IResult<&str, Expr> {
+    let (input, _) = char('$')(input)?;
+    let (input, scope) = alt((char('c'), char('s'), char('l')))(input)?;
+    let (input, _) = char('(')(input)?;
+    let (input, var_name) = id(input)?;
+    let (input, _) = char(')')(input)?;
+    let expr = match scope {
+        'c' => Expr::CVar(var_name),
+        's' => Expr::SVar(var_name),
+        'l' => Expr::LVar(var_name),
+        _ => unreachable!(),
+    };
+    Ok((input, expr))
+}
+
 fn gvar_special(input: &str) -> IResult<&str, Expr> {
     let (input, _) = tag("$?")(input)?;
     Ok((input, Expr::GVar("?".to_owned())))
@@ -115,6 +131,77 @@ fn has_item(input: &str) -> IResult<&str, Expr> {
     Ok((input, Expr::HasItem(s)))
 }
 
+/// Parse "name()" with no arguments
+fn no_arg_fn<'a>(name: &'static str) -> impl Fn(&'a str) -> IResult<&'a str, ()> {
+    move |input: &'a str| {
+        let (input, _) = tag(name)(input)?;
+        let (input, _) = multispace0(input)?;
+        let (input, _) = char('(')(input)?;
+        let (input, _) = multispace0(input)?;
+        let (input, _) = char(')')(input)?;
+        Ok((input, ()))
+    }
+}
+
+/// Parse "name(id)" and returns id
+fn id_arg_fn<'a>(name: &'static str) -> impl Fn(&'a str) -> IResult<&'a str, String> {
+    move |input: &'a str| {
+        let (input, _) = tag(name)(input)?;
+        let (input, _) = multispace0(input)?;
+        let (input, s) = delimited(
+            char('('),
+            delimited(multispace0, id, multispace0),
+            char(')'),
+        )(input)?;
+        Ok((input, s))
+    }
+}
+
+fn player_level(input: &str) -> IResult<&str, Expr> {
+    let (input, _) = no_arg_fn("player_level")(input)?;
+    Ok((input, Expr::PlayerLevel))
+}
+
+fn skill_level(input: &str) -> IResult<&str, Expr> {
+    let (input, kind) = map_res(id_arg_fn("skill_level"), |s| FromStr::from_str(&s))(input)?;
+    Ok((input, Expr::SkillLevel(kind)))
+}
+
+fn money(input: &str) -> IResult<&str, Expr> {
+    let (input, _) = no_arg_fn("money")(input)?;
+    Ok((input, Expr::Money))
+}
+
+fn is_quest_active(input: &str) -> IResult<&str, Expr> {
+    let (input, s) = id_arg_fn("is_quest_active")(input)?;
+    Ok((input, Expr::IsQuestActive(s)))
+}
+
+fn is_quest_completed(input: &str) -> IResult<&str, Expr> {
+    let (input, s) = id_arg_fn("is_quest_completed")(input)?;
+    Ok((input, Expr::IsQuestCompleted(s)))
+}
+
+fn relationship(input: &str) -> IResult<&str, Expr> {
+    let (input, rel) = map_res(id_arg_fn("relationship"), |s| FromStr::from_str(&s))(input)?;
+    Ok((input, Expr::Relationship(rel)))
+}
+
+fn current_floor(input: &str) -> IResult<&str, Expr> {
+    let (input, _) = no_arg_fn("current_floor")(input)?;
+    Ok((input, Expr::CurrentFloor))
+}
+
+fn in_town(input: &str) -> IResult<&str, Expr> {
+    let (input, s) = id_arg_fn("in_town")(input)?;
+    Ok((input, Expr::InTown(s)))
+}
+
+fn item_count(input: &str) -> IResult<&str, Expr> {
+    let (input, s) = id_arg_fn("item_count")(input)?;
+    Ok((input, Expr::ItemCount(s)))
+}
+
 fn factor(input: &str) -> IResult<&str, Expr> {
     let (input, _) = multispace0(input)?;
     let (input, expr) = alt((
@@ -123,10 +210,20 @@ fn factor(input: &str) -> IResult<&str, Expr> {
         complete(integer),
         complete(gvar),
         complete(gvar_special),
+        complete(scoped_var),
         complete(is_gvar_empty),
         complete(current_time),
         complete(duration_hours),
         complete(has_item),
+        complete(player_level),
+        complete(skill_level),
+        complete(money),
+        complete(is_quest_active),
+        complete(is_quest_completed),
+        complete(relationship),
+        complete(current_floor),
+        complete(in_town),
+        complete(item_count),
         complete(parens),
     ))(input)?;
     let (input, _) = multispace0(input)?;
@@ -257,16 +354,43 @@ pub fn expr(input: &str) -> IResult<&str, Expr> {
 
 #[test]
 fn expr_test() {
+    use common::gamedata::{Relationship, SkillKind, WeaponKind};
     assert_eq!(expr("true"), Ok(("", Expr::Value(Value::Bool(true)))));
     assert_eq!(expr("false"), Ok(("", Expr::Value(Value::Bool(false)))));
     assert_eq!(expr("1234"), Ok(("", Expr::Value(Value::Int(1234)))));
     assert_eq!(expr("$(aa)"), Ok(("", Expr::GVar("aa".to_owned()))));
+    assert_eq!(expr("$c(aa)"), Ok(("", Expr::CVar("aa".to_owned()))));
+    assert_eq!(expr("$s(aa)"), Ok(("", Expr::SVar("aa".to_owned()))));
+    assert_eq!(expr("$l(aa)"), Ok(("", Expr::LVar("aa".to_owned()))));
     assert_eq!(
         expr("is_gvar_empty(bb)"),
         Ok(("", Expr::IsGVarEmpty("bb".to_owned())))
     );
     let a = Expr::HasItem("box".to_owned());
     assert_eq!(expr("has_item(box)"), Ok(("", a)));
+    assert_eq!(expr("player_level()"), Ok(("", Expr::PlayerLevel)));
+    assert_eq!(
+        expr("skill_level(weapon.sword)"),
+        Ok(("", Expr::SkillLevel(SkillKind::Weapon(WeaponKind::Sword))))
+    );
+    assert_eq!(
+        expr("relationship(hostile)"),
+        Ok(("", Expr::Relationship(Relationship::HOSTILE)))
+    );
+    assert_eq!(
+        expr("is_quest_completed(find-ring)"),
+        Ok(("", Expr::IsQuestCompleted("find-ring".to_owned())))
+    );
+    assert_eq!(
+        expr("item_count(herb) >= 3"),
+        Ok((
+            "",
+            Expr::Term(vec![
+                (Operator::None, Expr::ItemCount("herb".to_owned())),
+                (Operator::GreaterEq, Expr::Value(Value::Int(3))),
+            ])
+        ))
+    );
     assert_eq!(
         expr("1 * 2 + 3"),
         Ok((
@@ -2,7 +2,7 @@ use super::img::build_img;
 use crate::error::*;
 use crate::tomlinput::*;
 use anyhow::*;
-use common::gamedata::defs::ElementArray;
+use common::gamedata::defs::{Element, ElementArray};
 use common::gamedata::item::*;
 
 pub fn build_item_object(tomlinput: TomlInput) -> Result<ItemObject, Error> {
@@ -23,6 +23,7 @@ pub fn build_item_object(tomlinput: TomlInput) -> Result<ItemObject, Error> {
         "magic_device" => ItemKind::MagicDevice,
         "weapon" => ItemKind::Weapon(get_optional_field!(item, weapon_kind)),
         "armor" => ItemKind::Armor(get_optional_field!(item, armor_kind)),
+        "ammo" => ItemKind::Ammo(get_optional_field!(item, ammo_kind)),
         "tool" => ItemKind::Tool,
         "material" => ItemKind::Material,
         "special" => ItemKind::Special,
@@ -47,6 +48,7 @@ pub fn build_item_object(tomlinput: TomlInput) -> Result<ItemObject, Error> {
         gen_level: item.gen_level,
         dice_n: item.dice_n.unwrap_or(0),
         dice_x: item.dice_x.unwrap_or(0),
+        element: item.element.unwrap_or(Element::Physical),
         def: item.def.unwrap_or(ElementArray([0, 0, 0, 0, 0, 0])),
         eff: item.eff.unwrap_or(0),
         magical_effect: item.magical_effect,
@@ -57,5 +59,6 @@ pub fn build_item_object(tomlinput: TomlInput) -> Result<ItemObject, Error> {
         charge: item.charge,
         harvest: item.harvest,
         facility: item.facility,
+        light: item.light.unwrap_or(0),
     })
 }
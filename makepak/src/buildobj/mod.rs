@@ -61,9 +61,12 @@ pub fn build_object(tomlinput: TomlInput) -> Result<Object, Error> {
 fn build_deco_object(tomlinput: TomlInput) -> Result<DecoObject, Error> {
     let img = get_optional_field!(tomlinput, image);
 
+    let light = tomlinput.deco.and_then(|deco| deco.light).unwrap_or(0);
+
     Ok(DecoObject {
         id: tomlinput.id,
         img: build_img(img)?.0,
+        light,
     })
 }
 
@@ -207,5 +210,7 @@ fn build_site_gen_object(tomlinput: TomlInput) -> Result<SiteGenObject, Error> {
         map_template_id: sg.map_template_id,
         unique_citizens: sg.unique_citizens.unwrap_or(vec![]),
         shops: sg.shops.unwrap_or(vec![]),
+        trainers: sg.trainers.unwrap_or(vec![]),
+        triggers: sg.triggers.unwrap_or(vec![]),
     })
 }
@@ -1,7 +1,7 @@
 use common::hashmap::HashMap;
 use nom::branch::alt;
 use nom::bytes::complete::tag;
-use nom::character::complete::{char, line_ending, multispace0, space0};
+use nom::character::complete::{char, digit1, line_ending, multispace0, space0};
 use nom::combinator::map_res;
 use nom::error::ParseError;
 use nom::multi::{fold_many0, many0, separated_list};
@@ -12,6 +12,7 @@ use std::str::FromStr;
 use super::expr_parser::*;
 use crate::error::PakCompileError;
 use common::script::*;
+use geom::Vec2d;
 
 fn end_line(input: &str) -> IResult<&str, ()> {
     let (input, _) = space0(input)?;
@@ -125,6 +126,10 @@ fn special_instruction_test() {
         special_instruction("special(shop_sell)\n"),
         Ok(("", Instruction::Special(SpecialInstruction::ShopSell)))
     );
+    assert_eq!(
+        special_instruction("special(train)\n"),
+        Ok(("", Instruction::Special(SpecialInstruction::Train)))
+    );
 }
 
 fn talk_instruction(input: &str) -> IResult<&str, Instruction> {
@@ -160,6 +165,50 @@ fn gset_instruction(input: &str) -> IResult<&str, Instruction> {
     Ok((input, Instruction::GSet(var_name, value)))
 }
 
+fn scoped_set_instruction(input: &str) -> IResult<&str, Instruction> {
+    let (input, _) = multispace0(input)?;
+    let (input, scope) = alt((char('c'), char('s'), char('l')))(input)?;
+    let (input, _) = tag("set")(input)?;
+    let (input, _) = multispace0(input)?;
+    let (input, _) = char('(')(input)?;
+    let (input, var_name) = ws(id)(input)?;
+    let (input, _) = char(',')(input)?;
+    let (input, value) = ws(expr)(input)?;
+    let (input, _) = char(')')(input)?;
+    let (input, _) = end_line(input)?;
+    let instruction = match scope {
+        'c' => Instruction::CSet(var_name, value),
+        's' => Instruction::SSet(var_name, value),
+        'l' => Instruction::LSet(var_name, value),
+        _ => unreachable!(),
+    };
+    Ok((input, instruction))
+}
+
+#[test]
+fn scoped_set_instruction_test() {
+    assert_eq!(
+        scoped_set_instruction("cset(greeted, true)\n"),
+        Ok((
+            "",
+            Instruction::CSet("greeted".to_owned(), Expr::Value(Value::Bool(true)))
+        ))
+    );
+    assert_eq!(
+        scoped_set_instruction("lset(n, $l(n) + 1)\n"),
+        Ok((
+            "",
+            Instruction::LSet(
+                "n".to_owned(),
+                Expr::Term(vec![
+                    (Operator::None, Expr::LVar("n".to_owned())),
+                    (Operator::Add, Expr::Value(Value::Int(1))),
+                ])
+            )
+        ))
+    );
+}
+
 fn receive_money_instruction(input: &str) -> IResult<&str, Instruction> {
     let (input, _) = ws(tag("receive_money"))(input)?;
     let (input, expr) = delimited(char('('), ws(expr), char(')'))(input)?;
@@ -174,6 +223,93 @@ fn remove_item_instruction(input: &str) -> IResult<&str, Instruction> {
     Ok((input, Instruction::RemoveItem(item_id)))
 }
 
+fn give_item_instruction(input: &str) -> IResult<&str, Instruction> {
+    let (input, _) = ws(tag("give_item"))(input)?;
+    let (input, _) = char('(')(input)?;
+    let (input, item_id) = ws(id)(input)?;
+    let (input, _) = char(',')(input)?;
+    let (input, n) = ws(expr)(input)?;
+    let (input, _) = char(')')(input)?;
+    let (input, _) = end_line(input)?;
+    Ok((input, Instruction::GiveItem(item_id, n)))
+}
+
+fn set_relationship_instruction(input: &str) -> IResult<&str, Instruction> {
+    let (input, _) = ws(tag("set_relationship"))(input)?;
+    let (input, rel) = map_res(
+        delimited(char('('), ws(symbol), char(')')),
+        FromStr::from_str,
+    )(input)?;
+    let (input, _) = end_line(input)?;
+    Ok((input, Instruction::SetRelationship(rel)))
+}
+
+fn start_quest_instruction(input: &str) -> IResult<&str, Instruction> {
+    let (input, _) = ws(tag("start_quest"))(input)?;
+    let (input, quest_id) = delimited(char('('), ws(id), char(')'))(input)?;
+    let (input, _) = end_line(input)?;
+    Ok((input, Instruction::StartQuest(quest_id)))
+}
+
+fn complete_quest_instruction(input: &str) -> IResult<&str, Instruction> {
+    let (input, _) = ws(tag("complete_quest"))(input)?;
+    let (input, quest_id) = delimited(char('('), ws(id), char(')'))(input)?;
+    let (input, _) = end_line(input)?;
+    Ok((input, Instruction::CompleteQuest(quest_id)))
+}
+
+fn uint(input: &str) -> IResult<&str, u32> {
+    map_res(digit1, FromStr::from_str)(input)
+}
+
+fn teleport_instruction(input: &str) -> IResult<&str, Instruction> {
+    let (input, _) = ws(tag("teleport"))(input)?;
+    let (input, args) =
+        delimited(char('('), separated_list(char(','), ws(uint)), char(')'))(input)?;
+    let (floor, x, y) = match args.as_slice() {
+        [x, y] => (None, *x, *y),
+        [floor, x, y] => (Some(*floor), *x, *y),
+        _ => {
+            return Err(nom::Err::Error(ParseError::from_error_kind(
+                input,
+                nom::error::ErrorKind::SeparatedList,
+            )));
+        }
+    };
+    let (input, _) = end_line(input)?;
+    Ok((
+        input,
+        Instruction::Teleport(floor, Vec2d(x as i32, y as i32)),
+    ))
+}
+
+#[test]
+fn map_instruction_test() {
+    assert_eq!(
+        give_item_instruction("give_item(herb, 2)\n"),
+        Ok((
+            "",
+            Instruction::GiveItem("herb".to_owned(), Expr::Value(Value::Int(2)))
+        ))
+    );
+    assert_eq!(
+        set_relationship_instruction("set_relationship(hostile)\n"),
+        Ok((
+            "",
+            Instruction::SetRelationship(common::gamedata::Relationship::HOSTILE)
+        ))
+    );
+    assert_eq!(
+        teleport_instruction("teleport(3, 4)\n"),
+        Ok(("", Instruction::Teleport(None, Vec2d(3, 4))))
+    );
+    assert_eq!(
+        teleport_instruction("teleport(1, 3, 4)\n"),
+        Ok(("", Instruction::Teleport(Some(1), Vec2d(3, 4))))
+    );
+    assert!(teleport_instruction("teleport(1)\n").is_err());
+}
+
 #[test]
 fn talk_instruction_test() {
     let result = Instruction::Talk(
@@ -196,9 +332,15 @@ fn instruction(input: &str) -> IResult<&str, Instruction> {
         talk_instruction_with_choices,
         talk_instruction,
         gset_instruction,
+        scoped_set_instruction,
         receive_money_instruction,
         remove_item_instruction,
         special_instruction,
+        give_item_instruction,
+        set_relationship_instruction,
+        start_quest_instruction,
+        complete_quest_instruction,
+        teleport_instruction,
     ))(input)
 }
 
@@ -1,5 +1,5 @@
 use common::gamedata::defs::Harvest;
-use common::gamedata::{self, ElementArray};
+use common::gamedata::{self, Element, ElementArray};
 use common::sitegen;
 use geom::Vec2d;
 
@@ -10,6 +10,7 @@ pub struct TomlInput {
     pub image: Option<ImgInput>,
     pub chara_template: Option<CharaTemplateDepInput>,
     pub item: Option<ItemDepInput>,
+    pub deco: Option<DecoDepInput>,
     pub tile: Option<TileDepInput>,
     pub wall: Option<WallDepInput>,
     pub special_tile: Option<SpecialTileDepInput>,
@@ -65,6 +66,13 @@ pub struct CharaTemplateDepInput {
     pub spd: u16,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DecoDepInput {
+    /// Radius of the light for light sources
+    pub light: Option<u32>,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct TileDepInput {
@@ -98,8 +106,11 @@ pub struct ItemDepInput {
     pub eff: Option<u16>,
     pub dice_n: Option<u16>,
     pub dice_x: Option<u16>,
+    /// For weapons and magic devices. Physical if not given
+    pub element: Option<Element>,
     pub weapon_kind: Option<gamedata::WeaponKind>,
     pub armor_kind: Option<gamedata::ArmorKind>,
+    pub ammo_kind: Option<gamedata::AmmoKind>,
     #[serde(default)]
     pub medical_effect: gamedata::MedicalEffect,
     #[serde(default)]
@@ -115,6 +126,8 @@ pub struct ItemDepInput {
     pub charge: [u8; 2],
     pub harvest: Option<Harvest>,
     pub facility: Option<String>,
+    /// Radius of the light for light sources
+    pub light: Option<u32>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -138,6 +151,8 @@ pub struct SiteGenDepInput {
     pub map_template_id: Vec<String>,
     pub unique_citizens: Option<Vec<sitegen::UniqueCitizenGenData>>,
     pub shops: Option<Vec<sitegen::ShopGenData>>,
+    pub trainers: Option<Vec<sitegen::TrainerGenData>>,
+    pub triggers: Option<Vec<sitegen::EventTriggerGenData>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -30,6 +30,8 @@ pub struct GeneratedMap {
     pub tile: Array2d<TileKind>,
     pub entrance: Vec2d,
     pub exit: Option<Vec2d>,
+    /// Rooms as (top left, right bottom) positions
+    pub rooms: Vec<(Vec2d, Vec2d)>,
 }
 
 #[derive(Clone, PartialEq, Debug)]
@@ -63,6 +65,7 @@ impl MapGenerator {
             tile: Array2d::new(size.0 as u32, size.1 as u32, TileKind::Floor),
             entrance: Vec2d(0, 0),
             exit: None,
+            rooms: Vec::new(),
         };
         MapGenerator {
             map,
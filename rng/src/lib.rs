@@ -7,33 +7,61 @@ pub use rand::Rng;
 use rand::RngCore;
 use rand::SeedableRng;
 use rand_xorshift::XorShiftRng;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 
+/// Random number generator that uses the current stream.
 #[derive(Debug, Clone, Copy)]
 pub struct GameRng;
 
-thread_local!(static XORSHIFT_RNG: RefCell<XorShiftRng> = {
+/// Named random number streams.
+/// Each stream is seeded from the world seed independently,
+/// so that rolls in one subsystem do not change the results of others.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RngStream {
+    /// Used when no stream is selected
+    Default = 0,
+    MapGen,
+    ItemGen,
+    Combat,
+    Ai,
+}
+
+const N_STREAMS: usize = 5;
+
+const FIXED_SEED: u64 = 0x7275696e730a;
+
+thread_local!(static XORSHIFT_RNG: RefCell<Vec<XorShiftRng>> = {
     let xorshift_rng = XorShiftRng::from_seed([0; 16]);
-    RefCell::new(xorshift_rng)
+    RefCell::new(vec![xorshift_rng; N_STREAMS])
 });
 
+thread_local!(static CURRENT_STREAM: Cell<RngStream> = const { Cell::new(RngStream::Default) });
+
+thread_local!(static WORLD_SEED: Cell<u64> = const { Cell::new(FIXED_SEED) });
+
+#[inline]
+fn with_current_rng<R, F: FnOnce(&mut XorShiftRng) -> R>(f: F) -> R {
+    let stream = CURRENT_STREAM.with(|s| s.get());
+    XORSHIFT_RNG.with(|xorshift_rng| f(&mut xorshift_rng.borrow_mut()[stream as usize]))
+}
+
 impl RngCore for GameRng {
     #[inline]
     fn next_u32(&mut self) -> u32 {
-        XORSHIFT_RNG.with(|xorshift_rng| xorshift_rng.borrow_mut().next_u32())
+        with_current_rng(|xorshift_rng| xorshift_rng.next_u32())
     }
 
     #[inline]
     fn next_u64(&mut self) -> u64 {
-        XORSHIFT_RNG.with(|xorshift_rng| xorshift_rng.borrow_mut().next_u64())
+        with_current_rng(|xorshift_rng| xorshift_rng.next_u64())
     }
 
     fn fill_bytes(&mut self, dest: &mut [u8]) {
-        XORSHIFT_RNG.with(|xorshift_rng| xorshift_rng.borrow_mut().fill_bytes(dest))
+        with_current_rng(|xorshift_rng| xorshift_rng.fill_bytes(dest))
     }
 
     fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), ::rand::Error> {
-        XORSHIFT_RNG.with(|xorshift_rng| xorshift_rng.borrow_mut().try_fill_bytes(dest))
+        with_current_rng(|xorshift_rng| xorshift_rng.try_fill_bytes(dest))
     }
 }
 
@@ -41,18 +69,58 @@ pub fn get_rng() -> GameRng {
     GameRng
 }
 
-/// Reseed
+/// Reseed all streams by a new world seed
 pub fn reseed(fixed: bool) {
-    let new_rng = if fixed {
-        XorShiftRng::seed_from_u64(0x7275696e730a)
+    set_world_seed(gen_world_seed(fixed));
+}
+
+/// Generate a new world seed
+pub fn gen_world_seed(fixed: bool) -> u64 {
+    if fixed {
+        FIXED_SEED
     } else {
-        XorShiftRng::from_rng(thread_rng()).expect("reseed from thread rng failed")
-    };
+        thread_rng().gen()
+    }
+}
+
+/// Set the world seed, and reseed all streams by it
+pub fn set_world_seed(seed: u64) {
+    WORLD_SEED.with(|s| s.set(seed));
     XORSHIFT_RNG.with(|xorshift_rng| {
-        xorshift_rng.replace(new_rng);
+        for (i, rng) in xorshift_rng.borrow_mut().iter_mut().enumerate() {
+            *rng = XorShiftRng::seed_from_u64(derive_seed(seed, i as u64, 0));
+        }
     })
 }
 
+/// Reseed the stream by the world seed and the given key.
+/// The stream generates the same sequence for the same world seed and key.
+pub fn reseed_stream(stream: RngStream, key: u64) {
+    let seed = derive_seed(WORLD_SEED.with(|s| s.get()), stream as u64, key);
+    XORSHIFT_RNG.with(|xorshift_rng| {
+        xorshift_rng.borrow_mut()[stream as usize] = XorShiftRng::seed_from_u64(seed);
+    })
+}
+
+/// Call f with the given stream. All random numbers in f are generated from the stream.
+pub fn with_stream<R, F: FnOnce() -> R>(stream: RngStream, f: F) -> R {
+    let prev = CURRENT_STREAM.with(|s| s.replace(stream));
+    let result = f();
+    CURRENT_STREAM.with(|s| s.set(prev));
+    result
+}
+
+fn derive_seed(world_seed: u64, stream: u64, key: u64) -> u64 {
+    splitmix64(splitmix64(world_seed ^ stream) ^ key)
+}
+
+fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
 pub fn next_u32() -> u32 {
     let mut rng = GameRng;
     rng.next_u32()
@@ -97,4 +165,23 @@ mod tests {
         let average = sum / N as f64;
         println!("average is {}", average);
     }
+
+    #[test]
+    fn stream() {
+        set_world_seed(1234);
+        reseed_stream(RngStream::MapGen, 5);
+        let a: Vec<u32> = with_stream(RngStream::MapGen, || (0..10).map(|_| next_u32()).collect());
+
+        // Other streams do not affect MapGen stream
+        set_world_seed(1234);
+        with_stream(RngStream::Combat, || dice(10, 6));
+        let _ = next_u32();
+        reseed_stream(RngStream::MapGen, 5);
+        let b: Vec<u32> = with_stream(RngStream::MapGen, || (0..10).map(|_| next_u32()).collect());
+        assert_eq!(a, b);
+
+        reseed_stream(RngStream::MapGen, 6);
+        let c: Vec<u32> = with_stream(RngStream::MapGen, || (0..10).map(|_| next_u32()).collect());
+        assert_ne!(a, c);
+    }
 }
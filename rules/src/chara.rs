@@ -6,6 +6,14 @@ use std::collections::HashMap;
 pub struct Chara {
     /// Attribute revisions by class
     pub class_revision: HashMap<CharaClass, CharaAttrRevision>,
+    /// Attribute growth per level
+    pub level_growth: LevelGrowth,
+    /// Skill caps and aptitudes by class
+    pub class_skills: HashMap<CharaClass, Vec<ClassSkill>>,
+    /// Skill cap for skills not listed in class_skills
+    pub default_skill_cap: u32,
+    /// Money to train a skill at trainers. Multiplied by the square of (skill level + 1)
+    pub skill_training_cost: i64,
     /// Default value of CharaParams::view_range.
     /// The actual value will be adjusted by character traits, and map attributes, etc.
     pub default_view_range: i32,
@@ -27,4 +35,126 @@ pub struct Chara {
     pub sp_weak: f32,
     /// sp border of starving
     pub sp_starving: f32,
+    /// Damage reduction ratio by race for each element.
+    /// Negative values mean weakness.
+    #[serde(default)]
+    pub race_resistance: HashMap<Race, HashMap<Element, f32>>,
+}
+
+impl Chara {
+    /// Returns the skill cap and the exp multiplier of the class for the skill
+    pub fn skill_aptitude(&self, class: CharaClass, kind: SkillKind) -> (u32, f32) {
+        self.class_skills
+            .get(&class)
+            .and_then(|skills| skills.iter().find(|s| s.skill == kind))
+            .map_or((self.default_skill_cap, 1.0), |s| (s.cap, s.aptitude))
+    }
+}
+
+/// Skill cap and aptitude for a class
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct ClassSkill {
+    pub skill: SkillKind,
+    /// The skill level cannot exceed this value by exp, trainers or skill books
+    pub cap: u32,
+    /// Gained skill exp is multiplied by this value
+    #[serde(default = "default_aptitude")]
+    pub aptitude: f32,
+}
+
+fn default_aptitude() -> f32 {
+    1.0
+}
+
+/// Attributes increase by these values per level above the generation level of the template.
+/// Class revisions multiplied by class_revision_factor are added,
+/// so each class grows toward its strong attributes.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct LevelGrowth {
+    pub hp: f32,
+    pub str: f32,
+    pub vit: f32,
+    pub dex: f32,
+    pub int: f32,
+    pub wil: f32,
+    pub cha: f32,
+    pub spd: f32,
+    pub class_revision_factor: f32,
+}
+
+impl LevelGrowth {
+    /// Total attribute growth for the given levels
+    pub fn growth(
+        &self,
+        class_revision: Option<&CharaAttrRevision>,
+        levels: u32,
+    ) -> CharaAttrRevision {
+        let levels = levels as f32;
+        let factor = self.class_revision_factor;
+        let r = class_revision.copied().unwrap_or_default();
+        let calc = |base: f32, r: f32| ((base + r * factor) * levels).max(0.0);
+        CharaAttrRevision {
+            hp: calc(self.hp, r.hp as f32) as i32,
+            str: calc(self.str, r.str.into()) as i16,
+            vit: calc(self.vit, r.vit.into()) as i16,
+            dex: calc(self.dex, r.dex.into()) as i16,
+            int: calc(self.int, r.int.into()) as i16,
+            wil: calc(self.wil, r.wil.into()) as i16,
+            cha: calc(self.cha, r.cha.into()) as i16,
+            spd: calc(self.spd, r.spd.into()) as i16,
+        }
+    }
+}
+
+#[test]
+fn level_growth_test() {
+    let level_growth = LevelGrowth {
+        hp: 2.0,
+        str: 0.5,
+        vit: 0.5,
+        dex: 0.5,
+        int: 0.5,
+        wil: 0.5,
+        cha: 0.5,
+        spd: 0.0,
+        class_revision_factor: 0.1,
+    };
+
+    let r = level_growth.growth(None, 4);
+    assert_eq!(r.hp, 8);
+    assert_eq!(r.str, 2);
+    assert_eq!(r.spd, 0);
+    assert_eq!(level_growth.growth(None, 0).hp, 0);
+
+    let class_revision = CharaAttrRevision {
+        hp: -5,
+        str: 10,
+        int: -10,
+        spd: -3,
+        ..CharaAttrRevision::default()
+    };
+    let r = level_growth.growth(Some(&class_revision), 4);
+    assert_eq!(r.hp, 6);
+    assert_eq!(r.str, 6);
+    assert_eq!(r.vit, 2);
+    // Negative revisions never make the growth negative
+    assert_eq!(r.int, 0);
+    assert_eq!(r.spd, 0);
+}
+
+#[test]
+fn skill_aptitude_test() {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../assets/rules/chara.json");
+    let chara: Chara = crate::read_from_json(&path);
+
+    let (cap, aptitude) = chara.skill_aptitude(CharaClass::Warrior, SkillKind::Endurance);
+    assert_eq!(cap, 100);
+    assert_eq!(aptitude, 1.5);
+    let (cap, aptitude) = chara.skill_aptitude(CharaClass::Sorcerer, SkillKind::BareHands);
+    assert_eq!(cap, 20);
+    assert_eq!(aptitude, 0.7);
+    // Skills not listed for the class
+    let (cap, aptitude) = chara.skill_aptitude(CharaClass::Warrior, SkillKind::Throwing);
+    assert_eq!(cap, chara.default_skill_cap);
+    assert_eq!(aptitude, 1.0);
 }
@@ -0,0 +1,41 @@
+use common::gamedata::{ItemKindRough, WeaponKind};
+use std::collections::HashMap;
+
+/// Rules for combat calculation
+#[derive(Serialize, Deserialize)]
+pub struct Combat {
+    /// Base probability of critical hits
+    pub critical_base: f32,
+    /// Critical hit probability added per dexterity
+    pub critical_dex_factor: f32,
+    /// Damage multiplier of critical hits
+    pub critical_multiplier: f32,
+    /// Parameters for bare hands attack
+    pub bare_hands: WeaponParams,
+    /// Parameters for thrown items
+    pub throwing: WeaponParams,
+    /// The maximum distance of throwing in tiles
+    pub throw_range: u32,
+    /// Thrown items get one more damage dice face per this weight (gram)
+    pub throw_weight_per_dice: u32,
+    /// Item kinds that can be thrown
+    pub throwable_kinds: Vec<ItemKindRough>,
+    /// Parameters for each weapon kind
+    pub weapons: HashMap<WeaponKind, WeaponParams>,
+}
+
+impl Combat {
+    pub fn weapon(&self, kind: WeaponKind) -> &WeaponParams {
+        self.weapons.get(&kind).unwrap_or(&self.bare_hands)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct WeaponParams {
+    /// Added to accuracy power
+    pub accuracy: u32,
+    /// Added to critical hit probability
+    pub critical: f32,
+    /// Ratio of the target's armour defence that is ignored
+    pub penetration: f32,
+}
@@ -17,4 +17,53 @@ pub struct Exp {
     pub healing: u32,
     /// Probability to gain Healing skill exp
     pub healing_probability: f32,
+    /// Base exp to magic school skills after casting a spell
+    pub cast: u32,
+    /// Base exp to the skill by reading a skill book
+    pub skill_book: u32,
+    /// Character exp for killing a character. Multiplied by the level of the killed character
+    pub kill: u32,
+    /// Character exp given as a quest reward
+    pub quest_reward: u32,
+    /// Character exp needed to the next level. The index is the current level - 1.
+    /// The last value is used for higher levels.
+    pub level_up: Vec<u32>,
+}
+
+impl Exp {
+    /// Character exp needed to level up from the given level
+    pub fn level_up_exp(&self, level: u32) -> u32 {
+        let i = std::cmp::max(level, 1) as usize - 1;
+        *self
+            .level_up
+            .get(i)
+            .or_else(|| self.level_up.last())
+            .expect("level_up in exp rules is empty")
+    }
+}
+
+#[test]
+fn level_up_exp_test() {
+    let exp = Exp {
+        begin_adjust_coeff: 0,
+        adjust_coeff: vec![1.0],
+        base_factor: 1.0,
+        attack: 0,
+        endurance: 0,
+        evasion: 0,
+        healing: 0,
+        healing_probability: 0.0,
+        cast: 0,
+        skill_book: 0,
+        kill: 0,
+        quest_reward: 0,
+        level_up: vec![10, 20, 30],
+    };
+    assert_eq!(exp.level_up_exp(0), 10);
+    assert_eq!(exp.level_up_exp(1), 10);
+    assert_eq!(exp.level_up_exp(2), 20);
+    assert_eq!(exp.level_up_exp(3), 30);
+    // The last value is used for higher levels
+    assert_eq!(exp.level_up_exp(4), 30);
+    assert_eq!(exp.level_up_exp(100), 30);
 }
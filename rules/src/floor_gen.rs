@@ -10,6 +10,12 @@ pub struct FloorGen {
 pub struct FloorGenParams {
     pub map_size: Vec2d,
     pub map_gen_kind: MapGenKind,
+    /// Generated floors are dark and need light sources to see
+    #[serde(default)]
+    pub dark: bool,
+    /// Probability that a room is lit on dark floors
+    #[serde(default)]
+    pub lit_room_probability: f64,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
@@ -7,15 +7,20 @@ extern crate rusted_ruins_geom as geom;
 
 pub mod chara;
 pub mod charagen;
+pub mod combat;
 pub mod creation;
 pub mod dungeon_gen;
 pub mod exp;
 pub mod floor_gen;
 pub mod magic;
 pub mod newgame;
+pub mod npc_ai;
 pub mod params;
 pub mod quest;
+pub mod spell;
+pub mod status;
 pub mod town;
+pub mod weather;
 
 use lazy_static::lazy_static;
 use serde::de::Deserialize;
@@ -27,15 +32,20 @@ use std::sync::Mutex;
 pub struct Rules {
     pub chara: chara::Chara,
     pub chara_gen: charagen::CharaGen,
+    pub combat: combat::Combat,
     pub creation: creation::Creation,
     pub dungeon_gen: dungeon_gen::DungeonGen,
     pub exp: exp::Exp,
     pub floor_gen: floor_gen::FloorGen,
     pub magic: magic::Magic,
     pub newgame: newgame::NewGame,
+    pub npc_ai: npc_ai::NpcAI,
     pub params: params::Params,
     pub quest: quest::Quest,
+    pub spell: spell::Spell,
+    pub status: status::Status,
     pub town: town::Town,
+    pub weather: weather::Weather,
 }
 
 impl Rules {
@@ -43,19 +53,26 @@ impl Rules {
         let mut creation: creation::Creation = read_from_json(&rules_dir.join("creation.json"));
         creation.sort();
 
-        Rules {
+        let rules = Rules {
             chara: read_from_json(&rules_dir.join("chara.json")),
             chara_gen: read_from_json(&rules_dir.join("charagen.json")),
+            combat: read_from_json(&rules_dir.join("combat.json")),
             creation,
             dungeon_gen: read_from_json(&rules_dir.join("dungeon_gen.json")),
             exp: read_from_json(&rules_dir.join("exp.json")),
             floor_gen: read_from_json(&rules_dir.join("floor_gen.json")),
             magic: read_from_json(&rules_dir.join("magic.json")),
             newgame: read_from_json(&rules_dir.join("newgame.json")),
+            npc_ai: read_from_json(&rules_dir.join("npc_ai.json")),
             params: read_from_json(&rules_dir.join("params.json")),
             quest: read_from_json(&rules_dir.join("quest.json")),
+            spell: read_from_json(&rules_dir.join("spell.json")),
+            status: read_from_json(&rules_dir.join("status.json")),
             town: read_from_json(&rules_dir.join("town.json")),
-        }
+            weather: read_from_json(&rules_dir.join("weather.json")),
+        };
+        rules.status.check_ids(&rules.spell);
+        rules
     }
 }
 
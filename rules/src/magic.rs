@@ -1,5 +1,10 @@
+use common::gamedata::Relationship;
+
 /// Various parameters for magic
 #[derive(Serialize, Deserialize)]
 pub struct Magic {
     pub magic_device_base_power: f64,
+    pub spell_base_power: f64,
+    /// Relationships from the caster to the characters damaged by area magic
+    pub area_damage_relationships: Vec<Relationship>,
 }
@@ -0,0 +1,10 @@
+/// Rules for NPC's behavior
+#[derive(Serialize, Deserialize)]
+pub struct NpcAI {
+    /// NPC starts to flee when its hp is lower than this ratio of max hp
+    pub flee_hp_ratio: f32,
+    /// NPC stops fleeing when its hp is recovered over this ratio of max hp
+    pub flee_end_hp_ratio: f32,
+    /// NPC goes back to its home when it is farther than this distance from home
+    pub home_range: i32,
+}
@@ -1,3 +1,5 @@
+use common::gamedata::Race;
+
 /// Various parameters for game playing
 #[derive(Serialize, Deserialize)]
 pub struct Params {
@@ -13,4 +15,32 @@ pub struct Params {
     pub initial_date_day: u32,
     /// Initial game date (hour)
     pub initial_date_hour: u32,
+    /// The maximum number of tiles searched for path finding in one turn, shared by all characters
+    pub path_search_budget: u32,
+    /// Hour when the night ends. Hours between dawn and dusk are daytime
+    pub dawn_hour: u16,
+    /// Hour when the night starts
+    pub dusk_hour: u16,
+    /// View range is multiplied by this factor on open-air maps at night
+    pub night_view_range_factor: f32,
+    /// Characters of these races are generated only at night
+    pub night_races: Vec<Race>,
+}
+
+impl Params {
+    /// The hour is night or not. Night begins at the dusk hour and ends at the dawn hour.
+    pub fn is_night(&self, hour: u16) -> bool {
+        hour < self.dawn_hour || self.dusk_hour <= hour
+    }
+}
+
+#[test]
+fn is_night_test() {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../assets/rules/params.json");
+    let params: Params = crate::read_from_json(&path);
+
+    assert!(params.is_night(params.dawn_hour - 1));
+    assert!(!params.is_night(params.dawn_hour));
+    assert!(!params.is_night(params.dusk_hour - 1));
+    assert!(params.is_night(params.dusk_hour));
 }
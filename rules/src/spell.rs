@@ -0,0 +1,40 @@
+use common::gamedata::{Element, MagicSchool, MagicalEffect};
+use std::collections::HashMap;
+
+/// Definitions of spells that characters can learn
+#[derive(Serialize, Deserialize)]
+pub struct Spell {
+    pub spells: HashMap<String, SpellParams>,
+}
+
+impl Spell {
+    pub fn get(&self, id: &str) -> Option<&SpellParams> {
+        let spell = self.spells.get(id);
+        if spell.is_none() {
+            warn!("Unknown spell \"{}\"", id);
+        }
+        spell
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SpellParams {
+    /// The skill of this school is used to calculate power
+    pub school: MagicSchool,
+    pub effect: MagicalEffect,
+    #[serde(default = "default_element")]
+    pub element: Element,
+    /// SP consumed by casting
+    pub cost: f32,
+    /// Multiplied to the base power in the magic rules
+    #[serde(default = "default_power")]
+    pub power: f64,
+}
+
+fn default_element() -> Element {
+    Element::Physical
+}
+
+fn default_power() -> f64 {
+    1.0
+}
@@ -0,0 +1,148 @@
+use crate::spell::Spell;
+use common::gamedata::{Element, MagicalEffect};
+use std::collections::HashMap;
+
+/// Status ids added by the game code
+const BUILTIN_IDS: &[&str] = &[
+    "hungry", "weak", "starving", "asleep", "poisoned", "creation",
+];
+
+/// Definitions of character status effects
+#[derive(Serialize, Deserialize)]
+pub struct Status {
+    pub effects: HashMap<String, StatusEffect>,
+}
+
+impl Status {
+    pub fn get(&self, id: &str) -> Option<&StatusEffect> {
+        self.effects.get(id)
+    }
+
+    /// Warn about undefined statuses used by the game code, expire hooks and spells.
+    /// Undefined statuses are ignored when they are added to characters.
+    pub(crate) fn check_ids(&self, spell: &Spell) {
+        let hook_ids = self
+            .effects
+            .values()
+            .flat_map(|effect| &effect.on_expire)
+            .filter_map(|hook| match hook {
+                ExpireHook::AddStatus { id } => Some(id.as_str()),
+                _ => None,
+            });
+        let spell_ids = spell
+            .spells
+            .values()
+            .filter_map(|spell| match &spell.effect {
+                MagicalEffect::SelfBuff { status } => Some(status.as_str()),
+                _ => None,
+            });
+
+        for id in BUILTIN_IDS.iter().copied().chain(hook_ids).chain(spell_ids) {
+            if !self.effects.contains_key(id) {
+                warn!("Unknown status effect \"{}\"", id);
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StatusEffect {
+    /// Default duration in turns. None means the status continues until removed
+    #[serde(default)]
+    pub duration: Option<u16>,
+    /// How to add the status to a character who already has it
+    #[serde(default = "default_stacking")]
+    pub stacking: Stacking,
+    /// Maximum number of stacks for Stacking::Stack
+    #[serde(default = "default_max_stacks")]
+    pub max_stacks: u16,
+    /// Statuses in the same group are exclusive. A new status replaces the old one
+    #[serde(default)]
+    pub group: Option<String>,
+    /// Effects applied every turn. They are multiplied by the stacks
+    #[serde(default)]
+    pub per_turn: PerTurnEffect,
+    /// Attribute modifiers. They are multiplied by the stacks
+    #[serde(default)]
+    pub modifier: AttrModifier,
+    /// Damage reduction ratio for each element. They are multiplied by the stacks
+    #[serde(default)]
+    pub resistance: HashMap<Element, f32>,
+    /// The character cannot act while having this status
+    #[serde(default)]
+    pub prevent_action: bool,
+    /// The probability that the moving direction is changed randomly
+    #[serde(default)]
+    pub random_move: f32,
+    /// Log message id when the status is added
+    #[serde(default)]
+    pub add_log: Option<String>,
+    /// Log message id when the character cannot act by this status
+    #[serde(default)]
+    pub prevent_action_log: Option<String>,
+    /// Hooks processed when the duration ends
+    #[serde(default)]
+    pub on_expire: Vec<ExpireHook>,
+}
+
+fn default_stacking() -> Stacking {
+    Stacking::Refresh
+}
+
+fn default_max_stacks() -> u16 {
+    1
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Stacking {
+    /// Keep the current status
+    Ignore,
+    /// Set the longer duration
+    Refresh,
+    /// Add the duration
+    Extend,
+    /// Increase stacks up to max_stacks, and refresh the duration
+    Stack,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PerTurnEffect {
+    /// HP change by the ratio of max HP. Negative values are damage
+    pub hp_ratio: f32,
+    /// HP change by fixed value
+    pub hp: i32,
+    pub sp: f32,
+    /// Element of the damage. The damage is not resisted if not given
+    pub element: Option<Element>,
+    /// Log message id for damage. "chara" and "damage" are given
+    pub damage_log: Option<String>,
+    /// Log message id when the character is killed by the damage
+    pub killed_log: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AttrModifier {
+    pub max_hp: i32,
+    pub str: i16,
+    pub vit: i16,
+    pub dex: i16,
+    pub int: i16,
+    pub wil: i16,
+    pub cha: i16,
+    pub spd: i16,
+    pub view_range: i32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExpireHook {
+    /// Add another status
+    AddStatus { id: String },
+    /// Print a log message. "chara" is given
+    Log { id: String },
+    /// Finish item creation by the data stored in the status
+    FinishCreation,
+}
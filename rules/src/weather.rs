@@ -0,0 +1,50 @@
+use common::gamedata::{Season, WeatherKind};
+use std::collections::HashMap;
+
+/// Rules for weather and seasons
+#[derive(Serialize, Deserialize)]
+pub struct Weather {
+    /// Range of hours until the weather changes
+    pub duration_hours: [u32; 2],
+    /// Weight of each weather by season
+    pub season_weight: HashMap<Season, HashMap<WeatherKind, f32>>,
+    /// Weights are multiplied by these factors in the region (region id)
+    pub region_factor: HashMap<String, HashMap<WeatherKind, f32>>,
+    /// View range on open-air maps is multiplied by this factor
+    pub view_range_factor: HashMap<WeatherKind, f32>,
+    /// Minutes per turn on region maps are multiplied by this factor
+    pub region_move_factor: HashMap<WeatherKind, f32>,
+    /// Crop growth speed by season
+    pub crop_growth_season: HashMap<Season, f32>,
+    /// Crop growth speed is multiplied by this factor
+    pub crop_growth_factor: HashMap<WeatherKind, f32>,
+    /// Sound played repeatedly on open-air maps
+    pub ambience: HashMap<WeatherKind, String>,
+}
+
+impl Weather {
+    /// Weights of each weather in the season and the region, sorted by the weather kind
+    pub fn weights(&self, season: Season, region: &str) -> Vec<(WeatherKind, f32)> {
+        let region_factor = self.region_factor.get(region);
+        let mut weights: Vec<(WeatherKind, f32)> = self
+            .season_weight
+            .get(&season)
+            .map(|weights| {
+                weights
+                    .iter()
+                    .map(|(kind, w)| {
+                        let f = region_factor.and_then(|f| f.get(kind)).unwrap_or(&1.0);
+                        (*kind, w * f)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        weights.sort_by_key(|(kind, _)| *kind);
+        weights
+    }
+}
+
+/// Returns the factor for the weather, or 1.0 if it is not specified
+pub fn factor(table: &HashMap<WeatherKind, f32>, kind: WeatherKind) -> f32 {
+    *table.get(&kind).unwrap_or(&1.0)
+}
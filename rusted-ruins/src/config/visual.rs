@@ -56,6 +56,8 @@ pub struct UIConfig {
     pub game_info_window: GameInfoWindowConfig,
     pub skill_window: SkillWindowConfig,
     pub quest_window: QuestWindowConfig,
+    pub spell_window: SpellWindowConfig,
+    pub train_window: TrainWindowConfig,
     pub label_widget: LabelWidgetConfig,
     pub list_widget: ListWidgetConfig,
     pub time_info: TimeInfoConfig,
@@ -162,6 +164,8 @@ pub struct NewGameDialogConfig {
 pub struct ChooseSaveFileDialogConfig {
     pub rect: CfgRect,
     pub list_size: u32,
+    /// Height of the area showing details of the selected save
+    pub detail_h: u32,
 }
 
 #[derive(Debug, Deserialize)]
@@ -218,6 +222,7 @@ pub struct InfoWindowConfig {
 pub struct StatusWindowConfig {
     pub image_rect: CfgRect,
     pub name_label_rect: CfgRect,
+    pub level_label_rect: CfgRect,
     pub hp_label_rect: CfgRect,
     pub sp_label_rect: CfgRect,
     pub str_label_rect: CfgRect,
@@ -249,6 +254,20 @@ pub struct QuestWindowConfig {
     pub n_row: u32,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct SpellWindowConfig {
+    pub rect: CfgRect,
+    pub n_row: u32,
+    pub column_pos: Vec<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TrainWindowConfig {
+    pub rect: CfgRect,
+    pub n_row: u32,
+    pub column_pos: Vec<i32>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct LabelWidgetConfig {
     pub h: i32,
@@ -266,6 +285,7 @@ pub struct ListWidgetConfig {
 pub struct TimeInfoConfig {
     pub time_label: CfgRect,
     pub date_label: CfgRect,
+    pub weather_label: CfgRect,
 }
 
 #[derive(Debug, Deserialize)]
@@ -3,6 +3,7 @@ use crate::game::{Game, InfoGetter};
 use common::objholder::EffectIdx;
 use common::piece_pattern::*;
 use geom::*;
+use rules::RULES;
 
 pub enum FogPattern {
     None,
@@ -39,20 +40,19 @@ pub fn all(game: &Game) -> Option<EffectIdx> {
     let date = game.gd.time.current_date();
     let hour = date.hour;
     let minute = date.minute;
-    let dawn_hour = 5;
-    let dusk_hour = 18;
+    let dawn_hour = RULES.params.dawn_hour;
+    let dusk_hour = RULES.params.dusk_hour;
     assert!(dawn_hour < dusk_hour);
 
-    if dawn_hour < hour && hour < dusk_hour {
-        // Daytime
-        None
-    } else if hour == dawn_hour {
+    if hour == dawn_hour {
         Some(game.frequent_tex.overlay_idx(twilight(minute)))
     } else if hour == dusk_hour {
         Some(game.frequent_tex.overlay_idx(twilight(60 - minute)))
-    } else {
-        // Night
+    } else if RULES.params.is_night(hour) {
         Some(game.frequent_tex.overlay_idx(Overlay::Night))
+    } else {
+        // Daytime
+        None
     }
 }
 
@@ -25,6 +25,8 @@ pub struct EventHandler {
     waiting_dir_release: WaitingDirRelease,
     mouse_state: Option<MouseState>,
     key_state: KeyState,
+    /// Commands from a replay, processed before user inputs
+    replay_commands: VecDeque<Command>,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -96,9 +98,14 @@ impl EventHandler {
             waiting_dir_release: WaitingDirRelease::No,
             mouse_state: None,
             key_state: KeyState::default(),
+            replay_commands: VecDeque::new(),
         }
     }
 
+    pub fn push_replay_commands(&mut self, commands: Vec<Command>) {
+        self.replay_commands.extend(commands);
+    }
+
     pub fn process_event(&mut self, event: Event) -> bool {
         match event {
             Event::Quit { .. } => {
@@ -213,6 +220,10 @@ impl EventHandler {
     }
 
     pub fn get_command(&mut self, mode: InputMode) -> Option<Command> {
+        if let Some(command) = self.replay_commands.pop_front() {
+            return Some(command);
+        }
+
         // If input mode switched normal, cursor shouldn't move until direction key released once
         if mode == InputMode::Dialog && self.prev_input_mode == InputMode::Normal {
             self.waiting_dir_release = WaitingDirRelease::Waiting;
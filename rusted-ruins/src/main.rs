@@ -1,10 +1,10 @@
 extern crate rusted_ruins_audio as audio;
 extern crate rusted_ruins_common as common;
 extern crate rusted_ruins_geom as geom;
-extern crate rusted_ruins_map_generator as map_generator;
-extern crate rusted_ruins_rng as rng;
 extern crate rusted_ruins_rules as rules;
 #[macro_use]
+extern crate rusted_ruins_game;
+#[macro_use]
 extern crate lazy_static;
 #[macro_use]
 extern crate serde_derive;
@@ -13,27 +13,49 @@ extern crate log as applog;
 
 #[macro_use]
 mod error;
-#[macro_use]
-mod log;
-#[macro_use]
-mod util;
-#[macro_use]
-mod text;
 mod config;
 mod context;
 mod draw;
 mod eventhandler;
-mod game;
 mod screen;
 mod sdltypeconv;
 mod window;
 
+use rusted_ruins_game::{game, log, text};
+
 fn main() {
     setup_logger();
     init_lazy_statics();
     init_obj();
     init_rules();
 
+    if config::CONFIG.headless {
+        let _audio_context = audio::init_null();
+        if let Some(path) = config::CONFIG.replay.as_ref() {
+            match game::replay::load(path).and_then(game::headless::run_replay) {
+                Ok(_) => println!("The replay is reproduced"),
+                Err(e) => {
+                    eprintln!("Failed to reproduce the replay: {}", e);
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+        let n_turns = match config::CONFIG.headless_turns {
+            Some(n_turns) => n_turns,
+            None => {
+                eprintln!("The number of turns or a replay is needed for headless mode");
+                std::process::exit(1);
+            }
+        };
+        let report = game::headless::run_new_game(n_turns);
+        println!(
+            "{} turns processed, player dead: {}",
+            report.turns, report.player_dead
+        );
+        return;
+    }
+
     let sdl_context = SdlContext::init();
     let mut screen = screen::Screen::new(&sdl_context.sdl_context);
 
@@ -56,6 +56,9 @@ impl Screen {
         let mut is_skip_next_frame = false;
         let texture_creator = self.canvas.texture_creator();
         let mut window_manager = WindowManager::new(sdl_context, &texture_creator);
+        if let Some(path) = CONFIG.replay.as_ref() {
+            window_manager.start_replay(path, &mut self.event_handler);
+        }
 
         'mainloop: loop {
             self.event_handler.update(&event_pump);
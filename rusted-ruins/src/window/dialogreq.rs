@@ -28,6 +28,9 @@ pub fn create_dialog_from_request(
         DialogOpenRequest::ShopSell => Box::new(ItemWindow::new(ItemWindowMode::ShopSell, game)),
         DialogOpenRequest::PickUpItem => Box::new(ItemWindow::new(ItemWindowMode::PickUp, game)),
         DialogOpenRequest::Quest => Box::new(super::quest_window::QuestWindow::new(game)),
+        DialogOpenRequest::Train { cid } => {
+            Box::new(super::train_window::TrainWindow::new(game, cid))
+        }
         DialogOpenRequest::GameOver => Box::new(super::exit_window::GameOverWindow::new()),
     })
 }
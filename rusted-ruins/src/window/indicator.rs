@@ -138,11 +138,13 @@ impl Window for FloorInfo {
 pub struct TimeInfo {
     date_label: LabelWidget,
     time_label: LabelWidget,
+    weather_label: LabelWidget,
     year: u32,
     month: u16,
     day: u16,
     hour: u16,
     minute: u16,
+    weather: Option<WeatherKind>,
 }
 
 impl TimeInfo {
@@ -151,14 +153,18 @@ impl TimeInfo {
         let date_label = LabelWidget::bordered(rect, "", FontKind::S);
         let rect: Rect = UI_CFG.time_info.time_label.into();
         let time_label = LabelWidget::bordered(rect, "", FontKind::M);
+        let rect: Rect = UI_CFG.time_info.weather_label.into();
+        let weather_label = LabelWidget::bordered(rect, "", FontKind::S);
         TimeInfo {
             date_label,
             time_label,
+            weather_label,
             year: 0,
             month: 0,
             day: 0,
             hour: 0,
             minute: 0,
+            weather: None,
         }
     }
 }
@@ -199,8 +205,19 @@ impl Window for TimeInfo {
             self.time_label
                 .set_text(&format!("{:02}:{:02}", self.hour, minute10))
         }
+        let weather = game
+            .gd
+            .region
+            .get(game.gd.get_current_mapid().rid())
+            .weather
+            .kind;
+        if self.weather != Some(weather) {
+            self.weather = Some(weather);
+            self.weather_label.set_text(&weather.to_text());
+        }
         self.date_label.draw(context);
         self.time_label.draw(context);
+        self.weather_label.draw(context);
     }
 }
 
@@ -228,7 +245,7 @@ impl StatusInfo {
             for (i, status) in self.status.iter().enumerate() {
                 let label = LabelWidget::bordered(
                     Rect::new(rect.x, rect.y - rect.h * i as i32, 1, 1),
-                    &crate::text::to_txt(status),
+                    &status.to_text(),
                     FontKind::S,
                 );
                 self.labels.push(label);
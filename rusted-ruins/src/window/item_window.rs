@@ -22,6 +22,7 @@ pub enum ItemWindowMode {
     Eat,
     Use,
     Release,
+    Throw,
     ShopSell,
     ShopBuy {
         cid: CharaId,
@@ -188,6 +189,14 @@ impl ItemWindow {
                 );
                 self.update_list(filtered_list);
             }
+            ItemWindowMode::Throw => {
+                let ill = ItemListLocation::Chara {
+                    cid: CharaId::Player,
+                };
+                let filtered_list =
+                    gd.get_filtered_item_list(ill, ItemFilter::new().throwable(true));
+                self.update_list(filtered_list);
+            }
             ItemWindowMode::ShopBuy { cid } => {
                 let ill = ItemListLocation::Shop { cid };
                 let filtered_list = gd.get_filtered_item_list(ill, ItemFilter::new());
@@ -275,6 +284,10 @@ impl ItemWindow {
                 pa.release_item(il);
                 DialogResult::CloseAll
             }
+            ItemWindowMode::Throw => {
+                pa.throw_item(il);
+                DialogResult::CloseAll
+            }
             ItemWindowMode::ShopBuy { .. } => {
                 pa.buy_item(il);
                 self.update_by_mode(pa.gd());
@@ -19,18 +19,22 @@ mod msg_dialog;
 mod newgame_window;
 mod quest_window;
 mod sidebar;
+mod spell_window;
 mod start_window;
 mod status_window;
 mod talk_window;
 mod text_input_dialog;
 mod text_window;
+mod train_window;
 mod widget;
 mod winpos;
 
 use self::log_window::LogWindow;
 use self::main_window::MainWindow;
 use self::widget::WidgetTrait;
+use crate::config::CONFIG;
 use crate::eventhandler::EventHandler;
+use crate::game::replay::{self, Recorder};
 use crate::game::{Command, DoPlayerAction, GameState, InfoGetter};
 use crate::SdlContext;
 use common::gamedata::*;
@@ -39,6 +43,7 @@ use sdl2::keyboard::TextInputUtil;
 use sdl2::render::TextureCreator;
 use sdl2::video::WindowContext;
 use std::any::Any;
+use std::path::Path;
 
 mod commonuse {
     pub use crate::config::{SCREEN_CFG, UI_CFG};
@@ -127,6 +132,7 @@ pub struct WindowManager<'sdl, 't> {
     passed_frame: u32,
     window_stack: Vec<Box<dyn DialogWindow>>,
     targeting_mode: bool,
+    recorder: Option<Recorder>,
 }
 
 impl<'sdl, 't> WindowManager<'sdl, 't> {
@@ -148,6 +154,7 @@ impl<'sdl, 't> WindowManager<'sdl, 't> {
             passed_frame: 0,
             window_stack,
             targeting_mode: false,
+            recorder: None,
         }
     }
 
@@ -170,6 +177,9 @@ impl<'sdl, 't> WindowManager<'sdl, 't> {
 
         if self.game.get_state() == GameState::PlayerTurn {
             if !self.process_command(event_handler) {
+                if let Some(recorder) = self.recorder.take() {
+                    recorder.finish(&self.game.gd);
+                }
                 self.game.end_game();
                 return false;
             }
@@ -256,6 +266,9 @@ impl<'sdl, 't> WindowManager<'sdl, 't> {
             return true;
         }
         let command = command.unwrap();
+        if let Some(recorder) = self.recorder.as_mut() {
+            recorder.record(&command);
+        }
 
         if self.targeting_mode {
             self.process_command_targeting_mode(command);
@@ -436,6 +449,14 @@ impl<'sdl, 't> WindowManager<'sdl, 't> {
                 ));
                 self.push_dialog_window(dialog);
             }
+            Command::ThrowItem => {
+                let item_window = ItemWindow::new(ItemWindowMode::Throw, pa.game());
+                self.push_dialog_window(Box::new(item_window));
+            }
+            Command::CastSpell => {
+                let dialog = Box::new(spell_window::SpellWindow::new(pa.game()));
+                self.push_dialog_window(dialog);
+            }
             Command::TargetingMode => {
                 self.targeting_mode = true;
                 match self.mode {
@@ -472,13 +493,7 @@ impl<'sdl, 't> WindowManager<'sdl, 't> {
                     // Load from file
                     SpecialDialogResult::NewGameStart(gd) => {
                         info!("Load game from file");
-                        self.window_stack.clear();
-                        self.mode = WindowManageMode::OnGame(GameWindows::new());
-
-                        let game = Game::new(*gd);
-                        self.game = game;
-                        self.game.update_before_player_turn();
-                        game_log_i!("start"; version=env!("CARGO_PKG_VERSION"));
+                        self.start_game(*gd, false);
                     }
                     _ => unreachable!(),
                 }
@@ -486,20 +501,16 @@ impl<'sdl, 't> WindowManager<'sdl, 't> {
             WindowManageMode::NewGame(_) => match result {
                 SpecialDialogResult::NewGameStart(gd) => {
                     info!("Create newgame from dialog result");
-                    self.window_stack.clear();
-                    self.mode = WindowManageMode::OnGame(GameWindows::new());
-
-                    let game = Game::new(*gd);
-                    self.game = game;
-                    self.game.update_before_player_turn();
-                    self.game.start_new_game();
-                    game_log_i!("start"; version=env!("CARGO_PKG_VERSION"));
+                    self.start_game(*gd, true);
                 }
                 _ => unreachable!(),
             },
             WindowManageMode::OnGame(_) => match result {
                 SpecialDialogResult::ReturnToStartScreen => {
                     info!("Return to start screen");
+                    if let Some(recorder) = self.recorder.take() {
+                        recorder.finish(&self.game.gd);
+                    }
                     crate::log::clear();
                     self.window_stack.clear();
                     self.push_dialog_window(Box::new(start_window::StartDialog::new()));
@@ -510,6 +521,37 @@ impl<'sdl, 't> WindowManager<'sdl, 't> {
         }
     }
 
+    fn start_game(&mut self, gd: GameData, new_game: bool) {
+        self.window_stack.clear();
+        self.mode = WindowManageMode::OnGame(GameWindows::new());
+        self.game = Game::new(gd);
+
+        if let Some(path) = CONFIG.record.as_ref() {
+            match Recorder::start(path, &mut self.game, new_game) {
+                Ok(recorder) => self.recorder = Some(recorder),
+                Err(e) => warn!("Failed to start recording: {}", e),
+            }
+        }
+
+        self.game.update_before_player_turn();
+        if new_game {
+            self.game.start_new_game();
+        }
+        game_log_i!("start"; version=env!("CARGO_PKG_VERSION"));
+    }
+
+    /// Start the game from the replay, and give its commands to the event handler
+    pub fn start_replay(&mut self, path: &Path, event_handler: &mut EventHandler) {
+        match replay::load(path) {
+            Ok(replay) => {
+                info!("Start replay {}", path.to_string_lossy());
+                self.start_game(replay.gd, replay.header.new_game);
+                event_handler.push_replay_commands(replay.commands);
+            }
+            Err(e) => warn!("Failed to load replay {}: {}", path.to_string_lossy(), e),
+        }
+    }
+
     fn process_command_targeting_mode(&mut self, command: Command) {
         let main_window = match self.mode {
             WindowManageMode::OnGame(ref mut game_windows) => &mut game_windows.main_window,
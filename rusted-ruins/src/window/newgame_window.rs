@@ -5,7 +5,7 @@ use super::widget::*;
 use super::SpecialDialogResult;
 use crate::config::SCREEN_CFG;
 use crate::game::newgame::NewGameBuilder;
-use crate::text;
+use crate::text::{self, ToText};
 use common::gamedata::*;
 use rules::RULES;
 
@@ -157,7 +157,7 @@ impl ChooseClassDialog {
             .newgame
             .class_choices
             .iter()
-            .map(|c| format!("{:?}", c))
+            .map(|c| c.to_text().into_owned())
             .collect();
 
         ChooseClassDialog {
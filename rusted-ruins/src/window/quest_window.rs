@@ -6,7 +6,7 @@ use crate::draw::border::draw_window_border;
 use crate::eventhandler::InputMode;
 use crate::game::quest::available_quests;
 use crate::game::{Animation, Command, DoPlayerAction, Game};
-use crate::text::ToText;
+use crate::text::{misc_txt, ToText};
 use crate::window::{DialogResult, DialogWindow, Window, WindowDrawMode};
 use common::gamedata::Quest;
 use sdl2::rect::Rect;
@@ -135,5 +135,6 @@ fn quest_decription_text(quest: &Quest) -> String {
         Quest::SlayMonsters { idx, goal, .. } => {
             misc_txt_format!("desc-quest-slay_monsters"; monster=idx, n=goal)
         }
+        Quest::Custom { id } => misc_txt(&format!("desc-quest-{}", id)),
     }
 }
@@ -0,0 +1,100 @@
+use super::commonuse::*;
+use super::widget::*;
+use crate::text::spell_txt;
+use common::gamedata::*;
+use rules::RULES;
+
+/// Player chooses a learned spell to cast
+pub struct SpellWindow {
+    rect: Rect,
+    list: ListWidget<(TextCache, TextCache)>,
+    spells: Vec<String>,
+    escape_click: bool,
+}
+
+impl SpellWindow {
+    pub fn new(game: &Game) -> SpellWindow {
+        let rect: Rect = UI_CFG.spell_window.rect.into();
+        let n_row = UI_CFG.spell_window.n_row;
+        let list_h = UI_CFG.list_widget.h_row_default;
+
+        let mut spell_window = SpellWindow {
+            rect,
+            list: ListWidget::with_scroll_bar(
+                (0i32, 0i32, rect.w as u32, n_row * list_h),
+                UI_CFG.spell_window.column_pos.clone(),
+                n_row,
+                true,
+            ),
+            spells: Vec::new(),
+            escape_click: false,
+        };
+        spell_window.update(&game.gd);
+        spell_window
+    }
+
+    fn update(&mut self, gd: &GameData) {
+        self.spells = gd.chara.get(CharaId::Player).spells.clone();
+        let spells = &self.spells;
+        self.list.set_n_item(spells.len() as u32);
+
+        self.list.update_rows_by_func(|i| {
+            let spell_id = &spells[i as usize];
+            let cost = RULES
+                .spell
+                .get(spell_id)
+                .map_or(String::new(), |spell| format!("SP {:.0}", spell.cost));
+
+            let t1 = TextCache::one(
+                spell_txt(spell_id),
+                FontKind::M,
+                UI_CFG.color.normal_font.into(),
+            );
+            let t2 = TextCache::one(cost, FontKind::M, UI_CFG.color.normal_font.into());
+            (t1, t2)
+        });
+    }
+}
+
+impl Window for SpellWindow {
+    fn draw(&mut self, context: &mut Context, _game: &Game, _anim: Option<(&Animation, u32)>) {
+        draw_window_border(context, self.rect);
+        self.list.draw(context);
+    }
+}
+
+impl DialogWindow for SpellWindow {
+    fn process_command(&mut self, command: &Command, pa: &mut DoPlayerAction) -> DialogResult {
+        check_escape_click!(self, command);
+
+        let command = command.relative_to(self.rect);
+
+        if let Some(response) = self.list.process_command(&command) {
+            match response {
+                ListWidgetResponse::Select(i) => {
+                    let spell_id = self.spells[i as usize].clone();
+                    pa.cast_spell(&spell_id);
+                    return DialogResult::CloseAll;
+                }
+                ListWidgetResponse::Scrolled => {
+                    self.update(pa.gd());
+                }
+                _ => (),
+            }
+            return DialogResult::Continue;
+        }
+
+        match command {
+            Command::Cancel => DialogResult::Close,
+            _ => DialogResult::Continue,
+        }
+    }
+
+    fn mode(&self) -> InputMode {
+        InputMode::Dialog
+    }
+
+    fn draw_mode(&self) -> WindowDrawMode {
+        WindowDrawMode::SkipUnderWindows
+    }
+}
@@ -1,11 +1,16 @@
+use super::choose_window::{ChooseWindow, DefaultBehavior};
 use super::commonuse::*;
+use super::msg_dialog::MsgDialog;
 use super::widget::*;
+use super::winpos::{WindowHPos, WindowPos, WindowVPos};
 use super::SpecialDialogResult;
 use crate::config::{SCREEN_CFG, UI_CFG};
-use crate::text;
-use common::gamedata::GameData;
+use crate::context::textrenderer::FontKind;
+use crate::game::saveload::{self, SaveFile};
+use crate::text::{self, ToText};
+use common::gamedata::{GameData, MetaData};
 use std::ffi::OsStr;
-use std::path::PathBuf;
+use std::time::{Duration, UNIX_EPOCH};
 
 pub struct StartWindow {
     title_screen: ImageWidget,
@@ -85,58 +90,171 @@ impl DialogWindow for StartDialog {
 pub struct ChooseSaveFileDialog {
     rect: Rect,
     list: TextListWidget,
-    save_files: Vec<PathBuf>,
+    detail: LabelWidget,
+    /// The index of the save whose details are shown
+    detail_idx: Option<u32>,
+    save_files: Vec<SaveFile>,
+    /// Menu to choose an action for the selected save
+    action_menu: Option<(usize, ChooseWindow)>,
+    /// Confirmation before deleting the selected save
+    delete_dialog: Option<(usize, MsgDialog)>,
 }
 
 impl ChooseSaveFileDialog {
     pub fn new() -> ChooseSaveFileDialog {
-        let save_files =
-            crate::game::saveload::save_file_list().expect("Error at reading save file directory");
+        let rect: Rect = UI_CFG.choose_save_file_dialog.rect.into();
+        let detail_h = UI_CFG.choose_save_file_dialog.detail_h;
+        let detail_rect = Rect::new(0, (rect.height() - detail_h) as i32, rect.width(), detail_h);
 
-        let file_name_list: Vec<String> = save_files
-            .iter()
-            .map(|path| {
-                path.file_stem()
-                    .unwrap_or_else(|| OsStr::new(""))
-                    .to_string_lossy()
-                    .into_owned()
-            })
-            .collect();
-        let rect = UI_CFG.choose_save_file_dialog.rect.into();
-
-        ChooseSaveFileDialog {
+        let mut dialog = ChooseSaveFileDialog {
             rect,
-            list: TextListWidget::text_choices((0, 0, rect.width(), rect.height()), file_name_list),
-            save_files,
+            list: TextListWidget::text_choices((0, 0, 0, 0), vec![]),
+            detail: LabelWidget::new(detail_rect, "", FontKind::S),
+            detail_idx: None,
+            save_files: Vec::new(),
+            action_menu: None,
+            delete_dialog: None,
+        };
+        dialog.reload();
+        dialog
+    }
+
+    fn reload(&mut self) {
+        self.save_files =
+            crate::game::saveload::save_file_list().expect("Error at reading save file directory");
+        let rows: Vec<String> = self.save_files.iter().map(save_file_row).collect();
+        let list_h = self.rect.height() - UI_CFG.choose_save_file_dialog.detail_h;
+        self.list = TextListWidget::text_choices((0, 0, self.rect.width(), list_h), rows);
+        self.detail_idx = None;
+    }
+
+    fn update_detail(&mut self) {
+        let idx = if self.save_files.is_empty() {
+            None
+        } else {
+            Some(self.list.get_current_choice())
+        };
+        if self.detail_idx == idx {
+            return;
+        }
+        self.detail_idx = idx;
+        let text = idx
+            .and_then(|i| self.save_files[i as usize].meta.as_ref())
+            .map(save_file_detail)
+            .unwrap_or_default();
+        self.detail.set_text(&text);
+    }
+
+    fn open_action_menu(&mut self, i: usize) {
+        let winpos = WindowPos::new(
+            WindowHPos::RightX(self.rect.right()),
+            WindowVPos::TopMargin(self.rect.bottom() + UI_CFG.gap_len_between_dialogs),
+        );
+        let choices = vec![
+            text::ui_txt("dialog-choice-load"),
+            text::ui_txt("dialog-choice-duplicate"),
+            text::ui_txt("dialog-choice-delete"),
+            text::ui_txt("dialog-choice-close"),
+        ];
+        let menu = ChooseWindow::new(winpos, choices, DefaultBehavior::Close);
+        self.action_menu = Some((i, menu));
+    }
+
+    fn do_action(&mut self, i: usize, action: u32) -> DialogResult {
+        let path = self.save_files[i].path.clone();
+        match action {
+            0 => match GameData::load(&path) {
+                Ok(o) => {
+                    return DialogResult::Special(SpecialDialogResult::NewGameStart(Box::new(o)));
+                }
+                Err(e) => {
+                    warn!("Failed to load a save file: {}", e);
+                    let dialog = MsgDialog::new(
+                        &saveload::load_error_msg(&*e),
+                        vec![text::ui_txt("dialog-choice-close")],
+                        |_, _| DialogResult::Close,
+                    );
+                    return DialogResult::OpenChildDialog(Box::new(dialog));
+                }
+            },
+            1 => {
+                if let Err(e) = saveload::duplicate_save_file(&path) {
+                    warn!("Failed to duplicate a save file: {}", e);
+                }
+                self.reload();
+            }
+            2 => {
+                let dialog = MsgDialog::with_yesno(&text::ui_txt("dialog-delete_save"), |_, n| {
+                    DialogResult::CloseWithValue(Box::new(n))
+                });
+                self.delete_dialog = Some((i, dialog));
+            }
+            _ => (),
         }
+        DialogResult::Continue
     }
 }
 
 impl Window for ChooseSaveFileDialog {
-    fn draw(&mut self, context: &mut Context, _game: &Game, _anim: Option<(&Animation, u32)>) {
+    fn draw(&mut self, context: &mut Context, game: &Game, anim: Option<(&Animation, u32)>) {
+        self.update_detail();
         draw_window_border(context, self.rect);
         self.list.draw(context);
+        self.detail.draw(context);
+        if let Some((_, menu)) = self.action_menu.as_mut() {
+            menu.draw(context, game, anim);
+        }
+        if let Some((_, dialog)) = self.delete_dialog.as_mut() {
+            dialog.draw(context, game, anim);
+        }
     }
 }
 
 impl DialogWindow for ChooseSaveFileDialog {
-    fn process_command(&mut self, command: &Command, _pa: &mut DoPlayerAction) -> DialogResult {
+    fn process_command(&mut self, command: &Command, pa: &mut DoPlayerAction) -> DialogResult {
+        if let Some((i, dialog)) = self.delete_dialog.as_mut() {
+            let i = *i;
+            match dialog.process_command(command, pa) {
+                DialogResult::CloseWithValue(v) => {
+                    self.delete_dialog = None;
+                    if *v.downcast::<u32>().unwrap() == 0 {
+                        let path = &self.save_files[i].path;
+                        if let Err(e) = saveload::delete_save_file(path) {
+                            warn!("Failed to delete a save file: {}", e);
+                        }
+                        self.reload();
+                    }
+                }
+                DialogResult::Close => {
+                    self.delete_dialog = None;
+                }
+                _ => (),
+            }
+            return DialogResult::Continue;
+        }
+
+        if let Some((i, menu)) = self.action_menu.as_mut() {
+            let i = *i;
+            match menu.process_command(command, pa) {
+                DialogResult::CloseWithValue(v) => {
+                    self.action_menu = None;
+                    let action = *v.downcast::<u32>().unwrap();
+                    return self.do_action(i, action);
+                }
+                DialogResult::Close => {
+                    self.action_menu = None;
+                }
+                _ => (),
+            }
+            return DialogResult::Continue;
+        }
+
         let command = command.relative_to(self.rect);
         if let Some(response) = self.list.process_command(&command) {
             match response {
                 ListWidgetResponse::Select(i) => {
                     // Any item is selected
-                    match GameData::load(&self.save_files[i as usize]) {
-                        Ok(o) => {
-                            return DialogResult::Special(SpecialDialogResult::NewGameStart(
-                                Box::new(o),
-                            ));
-                        }
-                        Err(e) => {
-                            warn!("Failed to load a save file: {}", e);
-                            return DialogResult::Continue;
-                        }
-                    }
+                    self.open_action_menu(i as usize);
                 }
                 _ => (),
             }
@@ -152,3 +270,64 @@ impl DialogWindow for ChooseSaveFileDialog {
         InputMode::Dialog
     }
 }
+
+/// Text for the save file list. Saves made by old versions have only the name.
+fn save_file_row(save_file: &SaveFile) -> String {
+    let meta = match save_file.meta.as_ref() {
+        Some(meta) if meta.timestamp != 0 => meta,
+        Some(meta) => return meta.save_name().to_owned(),
+        None => {
+            return save_file
+                .path
+                .file_stem()
+                .unwrap_or_else(|| OsStr::new(""))
+                .to_string_lossy()
+                .into_owned()
+        }
+    };
+
+    let class = meta
+        .chara_class
+        .map(|c| c.to_text().into_owned())
+        .unwrap_or_default();
+    let location = if let Some(floor) = meta.floor {
+        format!("{} ({})", meta.site_name, floor + 1)
+    } else {
+        meta.site_name.clone()
+    };
+    format!(
+        "{} Lv.{} {} - {}",
+        meta.player_name, meta.level, class, location
+    )
+}
+
+fn save_file_detail(meta: &MetaData) -> String {
+    if meta.timestamp == 0 {
+        return String::new();
+    }
+    let date = if let Some(date) = meta.date {
+        format!(
+            "{}/{:02}/{:02} {:02}:{:02}",
+            date.year, date.month, date.day, date.hour, date.minute
+        )
+    } else {
+        String::new()
+    };
+    format!(
+        "{}  {} {}:{:02}  {} {}",
+        date,
+        text::ui_txt("save_file-play_time"),
+        meta.play_time / 3600,
+        meta.play_time / 60 % 60,
+        text::ui_txt("save_file-saved_at"),
+        format_timestamp(meta.timestamp)
+    )
+}
+
+/// Format UNIX time as "YYYY-MM-DD hh:mm" in UTC
+fn format_timestamp(timestamp: u64) -> String {
+    let time = UNIX_EPOCH + Duration::from_secs(timestamp);
+    // RFC 3339 format is "YYYY-MM-DDThh:mm:ssZ"
+    let s = humantime::format_rfc3339_seconds(time).to_string();
+    s[..16].replace('T', " ")
+}
@@ -8,6 +8,7 @@ use crate::text::ToText;
 use common::basic::SKILL_EXP_LVUP;
 use common::gamedata::*;
 use common::gobj;
+use rules::RULES;
 
 const STATUS_WINDOW_GROUP_SIZE: u32 = 2;
 
@@ -39,6 +40,7 @@ pub struct StatusWindow {
     rect: Rect,
     image: ImageWidget,
     name_label: LabelWidget,
+    level_label: LabelWidget,
     hp_label: LabelWidget,
     sp_label: LabelWidget,
     str_label: LabelWidget,
@@ -57,6 +59,16 @@ impl StatusWindow {
         let chara = gd.chara.get(CharaId::Player);
         let image = ImageWidget::chara(cfg.image_rect, chara.template);
         let name_label = LabelWidget::new(cfg.name_label_rect, &chara.to_text(), FontKind::M);
+        let level_label = LabelWidget::new(
+            cfg.level_label_rect,
+            &format!(
+                "Lv  {} ({} / {})",
+                chara.level,
+                chara.exp,
+                RULES.exp.level_up_exp(chara.level)
+            ),
+            FontKind::MonoM,
+        );
         let hp_label = LabelWidget::new(
             cfg.hp_label_rect,
             &format!("HP  {} / {}", chara.hp, chara.attr.max_hp),
@@ -101,6 +113,7 @@ impl StatusWindow {
             rect,
             image,
             name_label,
+            level_label,
             hp_label,
             sp_label,
             str_label,
@@ -119,6 +132,7 @@ impl Window for StatusWindow {
         draw_window_border(context, self.rect);
         self.image.draw(context);
         self.name_label.draw(context);
+        self.level_label.draw(context);
         self.hp_label.draw(context);
         self.sp_label.draw(context);
         self.str_label.draw(context);
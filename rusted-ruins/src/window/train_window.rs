@@ -0,0 +1,96 @@
+use super::commonuse::*;
+use super::widget::*;
+use crate::game::training::{trainer_skills, training_cost};
+use crate::text::ToText;
+use common::gamedata::*;
+
+/// Player chooses a skill to train at the trainer
+pub struct TrainWindow {
+    rect: Rect,
+    list: ListWidget<(TextCache, TextCache, TextCache)>,
+    trainer: CharaId,
+    skills: &'static [SkillKind],
+    escape_click: bool,
+}
+
+impl TrainWindow {
+    pub fn new(game: &Game, trainer: CharaId) -> TrainWindow {
+        let rect: Rect = UI_CFG.train_window.rect.into();
+        let n_row = UI_CFG.train_window.n_row;
+        let list_h = UI_CFG.list_widget.h_row_default;
+
+        let mut train_window = TrainWindow {
+            rect,
+            list: ListWidget::with_scroll_bar(
+                (0i32, 0i32, rect.w as u32, n_row * list_h),
+                UI_CFG.train_window.column_pos.clone(),
+                n_row,
+                true,
+            ),
+            trainer,
+            skills: trainer_skills(&game.gd, trainer),
+            escape_click: false,
+        };
+        train_window.update(&game.gd);
+        train_window
+    }
+
+    fn update(&mut self, gd: &GameData) {
+        let player = gd.chara.get(CharaId::Player);
+        let skills = self.skills;
+        self.list.set_n_item(skills.len() as u32);
+
+        self.list.update_rows_by_func(|i| {
+            let kind = skills[i as usize];
+            let level = format!("Lv {}", player.skills.get(kind));
+            let cost = format!("{}G", training_cost(player, kind));
+
+            let t1 = TextCache::one(kind.to_text(), FontKind::M, UI_CFG.color.normal_font.into());
+            let t2 = TextCache::one(level, FontKind::M, UI_CFG.color.normal_font.into());
+            let t3 = TextCache::one(cost, FontKind::M, UI_CFG.color.normal_font.into());
+            (t1, t2, t3)
+        });
+    }
+}
+
+impl Window for TrainWindow {
+    fn draw(&mut self, context: &mut Context, _game: &Game, _anim: Option<(&Animation, u32)>) {
+        draw_window_border(context, self.rect);
+        self.list.draw(context);
+    }
+}
+
+impl DialogWindow for TrainWindow {
+    fn process_command(&mut self, command: &Command, pa: &mut DoPlayerAction) -> DialogResult {
+        check_escape_click!(self, command);
+
+        let command = command.relative_to(self.rect);
+
+        if let Some(response) = self.list.process_command(&command) {
+            match response {
+                ListWidgetResponse::Select(i) => {
+                    pa.train_skill(self.trainer, self.skills[i as usize]);
+                    self.update(pa.gd());
+                }
+                ListWidgetResponse::Scrolled => {
+                    self.update(pa.gd());
+                }
+                _ => (),
+            }
+            return DialogResult::Continue;
+        }
+
+        match command {
+            Command::Cancel => DialogResult::Close,
+            _ => DialogResult::Continue,
+        }
+    }
+
+    fn mode(&self) -> InputMode {
+        InputMode::Dialog
+    }
+
+    fn draw_mode(&self) -> WindowDrawMode {
+        WindowDrawMode::SkipUnderWindows
+    }
+}
@@ -0,0 +1,91 @@
+//! Edit game data
+
+use crate::info::find_chara;
+use anyhow::{bail, Result};
+use common::gamedata::*;
+use common::gobj;
+use common::objholder::ItemIdx;
+use common::script::Value;
+
+pub fn set_money(gd: &mut GameData, money: &str) -> Result<()> {
+    let money: i64 = money.parse()?;
+    if money < 0 {
+        bail!("money must not be negative");
+    }
+    let before = gd.player.money();
+    gd.player.set_money(money);
+    println!("Money: {} -> {}", before, money);
+    Ok(())
+}
+
+/// Add items with default flags and quality.
+/// Magic devices get the maximum charges.
+pub fn add_item(gd: &mut GameData, chara: &str, item_id: &str, n: Option<&str>) -> Result<()> {
+    let cid = find_chara(gd, chara)?;
+    let n: u32 = n.map_or(Ok(1), |n| n.parse())?;
+    let idx: ItemIdx = if let Some(idx) = gobj::id_to_idx_checked(item_id) {
+        idx
+    } else {
+        bail!("unknown item id \"{}\"", item_id);
+    };
+
+    let obj = gobj::get_obj(idx);
+    let mut item = Item {
+        idx,
+        kind: obj.kind,
+        flags: obj.default_flags,
+        quality: ItemQuality::default(),
+        attributes: vec![],
+    };
+    if obj.kind == ItemKind::MagicDevice {
+        item.attributes.push(ItemAttribute::Charge {
+            n: obj.charge[1].into(),
+        });
+    }
+
+    gd.chara.get_mut(cid).item_list.append(item, n);
+    println!("Added {} x{} to {:?}", item_id, n, cid);
+    Ok(())
+}
+
+pub fn remove_item(gd: &mut GameData, chara: &str, index: &str, n: Option<&str>) -> Result<()> {
+    let cid = find_chara(gd, chara)?;
+    let i: u32 = index.parse()?;
+    let item_list = &mut gd.chara.get_mut(cid).item_list;
+    if i as usize >= item_list.iter().count() {
+        bail!("item {} is not found", i);
+    }
+
+    let has = item_list.get_number(i);
+    let n = if let Some(n) = n {
+        let n: u32 = n.parse()?;
+        if n > has {
+            bail!("only {} items", has);
+        }
+        ItemMoveNum::Partial(n)
+    } else {
+        ItemMoveNum::All
+    };
+    let item = item_list.remove_and_get(i, n);
+    println!("Removed {} from {:?}", gobj::idx_to_id(item.idx), cid);
+    Ok(())
+}
+
+pub fn set_var(gd: &mut GameData, name: &str, value: &str) -> Result<()> {
+    let value = match value {
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        _ => match value.parse() {
+            Ok(i) => Value::Int(i),
+            Err(_) => bail!("invalid value \"{}\"", value),
+        },
+    };
+    println!(
+        "Variable {}: {:?} -> {:?}",
+        name,
+        gd.vars.global_var(name),
+        value
+    );
+    gd.vars.set_global_var(name, value);
+    Ok(())
+}
@@ -0,0 +1,159 @@
+//! Print information of game data
+
+use anyhow::{bail, Result};
+use common::gamedata::*;
+use common::gobj;
+
+/// Print regions, sites and maps in them.
+/// The sizes of maps that are not loaded are not shown.
+pub fn print_maps(gd: &GameData) {
+    let mut maps = Vec::new();
+    gd.region.visit_all_maps(|mid, map| {
+        let size = if map.is_loaded() {
+            Some((map.w, map.h))
+        } else {
+            None
+        };
+        maps.push((mid, map.id(), size));
+    });
+    maps.sort_by_key(|(mid, _, _)| match mid {
+        MapId::RegionMap { rid } => (format!("{:?}", rid), String::new(), 0),
+        MapId::SiteMap { sid, floor } => {
+            (format!("{:?}", sid.rid), format!("{:?}", sid), *floor + 1)
+        }
+    });
+
+    let current_mid = gd.get_current_mapid();
+    let mut current_site = None;
+    for (mid, id, size) in &maps {
+        let size = size.map_or("not loaded".to_owned(), |(w, h)| format!("{}x{}", w, h));
+        let current = if *mid == current_mid {
+            " (current)"
+        } else {
+            ""
+        };
+        match mid {
+            MapId::RegionMap { rid } => {
+                println!("Region {:?} \"{}\"", rid, gd.region.get(*rid).name);
+                println!("  map {:016x} {}{}", id, size, current);
+            }
+            MapId::SiteMap { sid, floor } => {
+                if current_site != Some(*sid) {
+                    current_site = Some(*sid);
+                    let site = gd.region.get_site(*sid);
+                    println!(
+                        "  Site {:?} \"{}\" floors: {}/{}",
+                        sid,
+                        site.name.as_deref().unwrap_or(""),
+                        site.floor_num(),
+                        site.max_floor()
+                    );
+                }
+                println!("    floor {} map {:016x} {}{}", floor, id, size, current);
+            }
+        }
+    }
+}
+
+/// Characters sorted by id. The index in this list is used to specify a character.
+fn chara_list(gd: &GameData) -> Vec<CharaId> {
+    let mut list: Vec<CharaId> = gd.chara.iter().map(|(cid, _)| *cid).collect();
+    list.sort_by_key(|cid| (*cid != CharaId::Player, format!("{:?}", cid)));
+    list
+}
+
+pub fn print_charas(gd: &GameData) {
+    for (i, cid) in chara_list(gd).into_iter().enumerate() {
+        let chara = gd.chara.get(cid);
+        println!(
+            "{:>4} {} Lv.{} {:?}",
+            i,
+            chara_name(chara),
+            chara.level,
+            cid
+        );
+    }
+}
+
+/// Search a character by "player" or the index in the chara list
+pub fn find_chara(gd: &GameData, s: &str) -> Result<CharaId> {
+    if s == "player" {
+        return Ok(CharaId::Player);
+    }
+    let i: usize = s.parse()?;
+    if let Some(cid) = chara_list(gd).get(i) {
+        Ok(*cid)
+    } else {
+        bail!("character {} is not found", i)
+    }
+}
+
+pub fn print_stats(gd: &GameData, cid: CharaId) {
+    let chara = gd.chara.get(cid);
+    let attr = &chara.attr;
+    println!("{} ({:?})", chara_name(chara), cid);
+    println!("template: {}", gobj::idx_to_id(chara.template));
+    println!(
+        "class: {:?}, level: {}, exp: {}",
+        chara.class, chara.level, chara.exp
+    );
+    println!("HP: {}/{}, SP: {:.0}", chara.hp, attr.max_hp, chara.sp);
+    println!(
+        "STR: {}, VIT: {}, DEX: {}, INT: {}, WIL: {}, CHA: {}, SPD: {}",
+        attr.str, attr.vit, attr.dex, attr.int, attr.wil, attr.cha, attr.spd
+    );
+    println!("relationship: {:?}", chara.rel);
+    println!("AI: {:?}", chara.ai);
+    if !chara.status.is_empty() {
+        println!("status: {:?}", chara.status);
+    }
+
+    let mut skills: Vec<(&SkillKind, &u32)> = chara.skills.skills.iter().collect();
+    skills.sort_by_key(|(kind, _)| format!("{:?}", kind));
+    for (kind, lv) in skills {
+        println!("skill {:?}: {}", kind, lv);
+    }
+    if cid == CharaId::Player {
+        println!("money: {}", gd.player.money());
+    }
+}
+
+pub fn print_inventory(gd: &GameData, cid: CharaId) {
+    let chara = gd.chara.get(cid);
+    println!("{} ({:?})", chara_name(chara), cid);
+    for (i, (item, n)) in chara.item_list.iter().enumerate() {
+        println!(
+            "{:>4} {} x{}{}",
+            i,
+            gobj::idx_to_id(item.idx),
+            n,
+            item_detail(item)
+        );
+    }
+    println!("equipment:");
+    for (esk, n, item) in chara.equip.slot_iter() {
+        let item = item.map_or("-".to_owned(), |item| {
+            format!("{}{}", gobj::idx_to_id(item.idx), item_detail(item))
+        });
+        println!("  {:?} {}: {}", esk, n, item);
+    }
+}
+
+fn item_detail(item: &Item) -> String {
+    let mut s = String::new();
+    if item.quality != ItemQuality::default() {
+        s.push_str(&format!(" {:?}", item.quality));
+    }
+    if !item.attributes.is_empty() {
+        s.push_str(&format!(" {:?}", item.attributes));
+    }
+    s
+}
+
+fn chara_name(chara: &Chara) -> String {
+    if let Some(name) = &chara.name {
+        name.clone()
+    } else {
+        gobj::idx_to_id(chara.template).to_owned()
+    }
+}
@@ -0,0 +1,75 @@
+//! Convert game data to JSON.
+//! Game data includes maps whose keys are not strings,
+//! so it is converted through CBOR values and such keys are written as JSON strings.
+
+use anyhow::Result;
+use common::gamedata::GameData;
+use serde_cbor::Value as CborValue;
+use serde_json::{Map, Number, Value as JsonValue};
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+pub fn dump(gd: &GameData, output: Option<&str>) -> Result<()> {
+    let mut root = Map::new();
+    root.insert(
+        "metadata".to_owned(),
+        to_json(serde_cbor::value::to_value(&gd.meta)?),
+    );
+    root.insert(
+        "gamedata".to_owned(),
+        to_json(serde_cbor::value::to_value(gd)?),
+    );
+    let root = JsonValue::Object(root);
+
+    if let Some(output) = output {
+        let mut file = BufWriter::new(File::create(output)?);
+        serde_json::to_writer_pretty(&mut file, &root)?;
+        file.flush()?;
+    } else {
+        let stdout = std::io::stdout();
+        let mut stdout = stdout.lock();
+        serde_json::to_writer_pretty(&mut stdout, &root)?;
+        writeln!(stdout)?;
+    }
+    Ok(())
+}
+
+fn to_json(value: CborValue) -> JsonValue {
+    match value {
+        CborValue::Null => JsonValue::Null,
+        CborValue::Bool(b) => JsonValue::Bool(b),
+        CborValue::Integer(i) => {
+            if let Ok(i) = i64::try_from(i) {
+                JsonValue::Number(i.into())
+            } else if let Ok(i) = u64::try_from(i) {
+                JsonValue::Number(i.into())
+            } else {
+                JsonValue::String(i.to_string())
+            }
+        }
+        CborValue::Float(f) => Number::from_f64(f).map_or(JsonValue::Null, JsonValue::Number),
+        CborValue::Bytes(bytes) => JsonValue::Array(
+            bytes
+                .into_iter()
+                .map(|b| JsonValue::Number(b.into()))
+                .collect(),
+        ),
+        CborValue::Text(s) => JsonValue::String(s),
+        CborValue::Array(a) => JsonValue::Array(a.into_iter().map(to_json).collect()),
+        CborValue::Map(m) => JsonValue::Object(
+            m.into_iter()
+                .map(|(k, v)| (key_to_string(k), to_json(v)))
+                .collect(),
+        ),
+        CborValue::Tag(_, v) => to_json(*v),
+        _ => JsonValue::Null,
+    }
+}
+
+fn key_to_string(key: CborValue) -> String {
+    match key {
+        CborValue::Text(s) => s,
+        key => to_json(key).to_string(),
+    }
+}
@@ -0,0 +1,296 @@
+extern crate rusted_ruins_common as common;
+
+mod edit;
+mod info;
+mod json;
+#[cfg(test)]
+mod testutil;
+
+use anyhow::{bail, Result};
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use common::gamedata::*;
+use std::path::{Path, PathBuf};
+
+fn main() {
+    env_logger::builder().format_timestamp(None).init();
+    let matches = create_app().get_matches();
+
+    if let Err(e) = run(&matches) {
+        eprintln!("Error: {:#}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run(matches: &ArgMatches) -> Result<()> {
+    let pak_dirs: Vec<PathBuf> = if let Some(dirs) = matches.values_of("assets") {
+        dirs.map(|d| Path::new(d).join("paks")).collect()
+    } else {
+        vec![default_assets_dir().join("paks")]
+    };
+    common::gobj::init(pak_dirs);
+
+    let save_dir = Path::new(matches.value_of("SAVE_DIR").unwrap());
+    // Only editing subcommands may convert old saves or restore backups
+    let read_only = matches!(
+        matches.subcommand_name(),
+        Some("dump") | Some("maps") | Some("charas") | Some("chara")
+    );
+    let mut gd = load(save_dir, read_only)?;
+
+    match matches.subcommand() {
+        ("dump", Some(m)) => json::dump(&gd, m.value_of("output"))?,
+        ("maps", _) => info::print_maps(&gd),
+        ("charas", _) => info::print_charas(&gd),
+        ("chara", Some(m)) => {
+            let cid = info::find_chara(&gd, m.value_of("CHARA").unwrap())?;
+            if m.is_present("items") {
+                info::print_inventory(&gd, cid);
+            } else {
+                info::print_stats(&gd, cid);
+            }
+        }
+        (subcommand, Some(m)) => {
+            match subcommand {
+                "set-money" => edit::set_money(&mut gd, m.value_of("MONEY").unwrap())?,
+                "add-item" => edit::add_item(
+                    &mut gd,
+                    m.value_of("CHARA").unwrap(),
+                    m.value_of("ITEM_ID").unwrap(),
+                    m.value_of("N"),
+                )?,
+                "remove-item" => edit::remove_item(
+                    &mut gd,
+                    m.value_of("CHARA").unwrap(),
+                    m.value_of("INDEX").unwrap(),
+                    m.value_of("N"),
+                )?,
+                "set-var" => edit::set_var(
+                    &mut gd,
+                    m.value_of("NAME").unwrap(),
+                    m.value_of("VALUE").unwrap(),
+                )?,
+                _ => unreachable!(),
+            }
+            save(&gd, save_dir)?;
+        }
+        _ => bail!("no subcommand given"),
+    }
+
+    Ok(())
+}
+
+/// Load the save data. If read_only is true, no files in the save directory are changed.
+fn load(save_dir: &Path, read_only: bool) -> Result<GameData> {
+    if !save_dir.join("metadata").exists() {
+        bail!("{} is not a save directory", save_dir.to_string_lossy());
+    }
+    let result = if read_only {
+        GameData::load_dir(save_dir)
+    } else {
+        GameData::load(save_dir)
+    };
+    match result {
+        Ok(gd) => Ok(gd),
+        Err(e) => bail!("failed to load {}: {}", save_dir.to_string_lossy(), e),
+    }
+}
+
+/// Save the edited game data. The previous data is kept as a backup.
+fn save(gd: &GameData, save_dir: &Path) -> Result<()> {
+    if let Err(e) = gd.save(save_dir) {
+        bail!("failed to save {}: {}", save_dir.to_string_lossy(), e);
+    }
+    println!("Saved to {}", save_dir.to_string_lossy());
+    Ok(())
+}
+
+fn default_assets_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os("RUSTED_RUINS_ASSETS_DIR") {
+        PathBuf::from(dir)
+    } else {
+        PathBuf::from("assets")
+    }
+}
+
+fn create_app() -> App<'static, 'static> {
+    let chara_arg = Arg::with_name("CHARA")
+        .required(true)
+        .help("\"player\" or the number shown by charas subcommand");
+
+    App::new("rusted-ruins-save-tool")
+        .about("Inspects and edits save data of Rusted Ruins")
+        .setting(AppSettings::SubcommandRequired)
+        .arg(
+            Arg::with_name("assets")
+                .long("assets")
+                .value_name("DIR")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Assets directory including paks. Can be given multiple times for addons"),
+        )
+        .arg(
+            Arg::with_name("SAVE_DIR")
+                .required(true)
+                .help("Save directory to open"),
+        )
+        .subcommand(
+            SubCommand::with_name("dump")
+                .about("Dump game data as JSON")
+                .arg(
+                    Arg::with_name("output")
+                        .short("o")
+                        .long("output")
+                        .value_name("FILE")
+                        .help("Output file. Printed to stdout if not given"),
+                ),
+        )
+        .subcommand(SubCommand::with_name("maps").about("List regions, sites and maps"))
+        .subcommand(SubCommand::with_name("charas").about("List characters"))
+        .subcommand(
+            SubCommand::with_name("chara")
+                .about("Print character stats")
+                .arg(chara_arg.clone())
+                .arg(
+                    Arg::with_name("items")
+                        .short("i")
+                        .long("items")
+                        .help("Print inventory and equipment instead of stats"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("set-money")
+                .about("Set the player's money")
+                .arg(Arg::with_name("MONEY").required(true)),
+        )
+        .subcommand(
+            SubCommand::with_name("add-item")
+                .about("Add items to a character's inventory")
+                .arg(chara_arg.clone())
+                .arg(Arg::with_name("ITEM_ID").required(true))
+                .arg(Arg::with_name("N").help("The number of items. Default is 1")),
+        )
+        .subcommand(
+            SubCommand::with_name("remove-item")
+                .about("Remove items from a character's inventory")
+                .arg(chara_arg)
+                .arg(
+                    Arg::with_name("INDEX")
+                        .required(true)
+                        .help("The index shown by chara --items"),
+                )
+                .arg(Arg::with_name("N").help("The number of items. All if not given")),
+        )
+        .subcommand(
+            SubCommand::with_name("set-var")
+                .about("Set a global script variable")
+                .arg(Arg::with_name("NAME").required(true))
+                .arg(
+                    Arg::with_name("VALUE")
+                        .required(true)
+                        .help("Integer, true or false"),
+                ),
+        )
+}
+
+#[cfg(test)]
+fn run_args(save_dir: &Path, args: &[&str]) -> Result<()> {
+    let assets_dir = testutil::assets_dir();
+    let mut all_args = vec![
+        "rusted-ruins-save-tool",
+        "--assets",
+        assets_dir.to_str().unwrap(),
+        save_dir.to_str().unwrap(),
+    ];
+    all_args.extend_from_slice(args);
+    run(&create_app().get_matches_from(all_args))
+}
+
+#[test]
+fn set_money_test() {
+    let _lock = testutil::init();
+    let path = testutil::copy_save_fixture("v0.rrsve", "money.rrsve");
+
+    run_args(&path, &["set-money", "1234"]).unwrap();
+    assert!(run_args(&path, &["set-money", "--", "-1"]).is_err());
+    let gd = GameData::load_dir(&path).unwrap();
+    assert_eq!(gd.player.money(), 1234);
+    // The old save is kept as a backup
+    assert!(common::saveload::backup_dir(&path, 1).exists());
+}
+
+#[test]
+fn item_test() {
+    let _lock = testutil::init();
+    let path = testutil::copy_save_fixture("v0.rrsve", "item.rrsve");
+    let item_index = |gd: &GameData| {
+        let idx: common::objholder::ItemIdx = common::gobj::id_to_idx(testutil::ITEM_ID);
+        gd.chara
+            .get(CharaId::Player)
+            .item_list
+            .iter()
+            .position(|(item, _)| item.idx == idx)
+    };
+
+    run_args(&path, &["add-item", "player", testutil::ITEM_ID, "5"]).unwrap();
+    let gd = GameData::load_dir(&path).unwrap();
+    let i = item_index(&gd).unwrap();
+    assert_eq!(
+        gd.chara.get(CharaId::Player).item_list.get_number(i as u32),
+        5
+    );
+
+    let index = i.to_string();
+    run_args(&path, &["remove-item", "player", &index, "2"]).unwrap();
+    let gd = GameData::load_dir(&path).unwrap();
+    assert_eq!(
+        gd.chara.get(CharaId::Player).item_list.get_number(i as u32),
+        3
+    );
+
+    // Errors do not change the save
+    assert!(run_args(&path, &["remove-item", "player", "99"]).is_err());
+    assert!(run_args(&path, &["remove-item", "player", &index, "4"]).is_err());
+    let gd = GameData::load_dir(&path).unwrap();
+    assert_eq!(
+        gd.chara.get(CharaId::Player).item_list.get_number(i as u32),
+        3
+    );
+
+    run_args(&path, &["remove-item", "player", &index]).unwrap();
+    let gd = GameData::load_dir(&path).unwrap();
+    assert!(item_index(&gd).is_none());
+}
+
+#[test]
+fn set_var_test() {
+    use common::script::Value;
+
+    let _lock = testutil::init();
+    let path = testutil::copy_save_fixture("v0.rrsve", "var.rrsve");
+
+    run_args(&path, &["set-var", "count", "3"]).unwrap();
+    run_args(&path, &["set-var", "flag", "true"]).unwrap();
+    assert!(run_args(&path, &["set-var", "flag", "yes"]).is_err());
+    let gd = GameData::load_dir(&path).unwrap();
+    assert_eq!(gd.vars.global_var("count"), Some(&Value::Int(3)));
+    assert_eq!(gd.vars.global_var("flag"), Some(&Value::Bool(true)));
+}
+
+#[test]
+fn read_only_test() {
+    let _lock = testutil::init();
+    let path = testutil::copy_save_fixture("v0.rrsve", "read-only.rrsve");
+    let files = testutil::read_dir_files(&path);
+    let output = testutil::temp_dir().join("dump.json");
+
+    run_args(&path, &["dump", "-o", output.to_str().unwrap()]).unwrap();
+    run_args(&path, &["maps"]).unwrap();
+    run_args(&path, &["charas"]).unwrap();
+    run_args(&path, &["chara", "player"]).unwrap();
+    run_args(&path, &["chara", "player", "--items"]).unwrap();
+
+    // The old version save is not converted on disk, and no backup is made
+    assert!(testutil::read_dir_files(&path) == files);
+    assert!(!common::saveload::backup_dir(&path, 1).exists());
+    assert!(output.exists());
+}
@@ -0,0 +1,163 @@
+//! Objects and save directories for tests.
+//! Saves are copied from the fixtures of the game crate, and objects used in them
+//! are written to a temporary pak file.
+
+use common::gamedata::*;
+use common::obj::*;
+use common::pakutil::write_object;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, Once};
+
+pub const ITEM_ID: &str = "!test-item";
+
+static INIT: Once = Once::new();
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// Write the test pak. Loading saves changes the global index conversion table,
+/// so tests must hold the returned guard.
+pub fn init() -> MutexGuard<'static, ()> {
+    INIT.call_once(|| write_pak(&assets_dir().join("paks")));
+    LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Temporary directory for this test process
+pub fn temp_dir() -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "rusted-ruins-save-tool-test-{}",
+        std::process::id()
+    ))
+}
+
+/// Assets directory including the test pak
+pub fn assets_dir() -> PathBuf {
+    temp_dir().join("assets")
+}
+
+/// Copy a save in game/testdata/saves to the temporary directory with a new name,
+/// and returns the path of the copy
+pub fn copy_save_fixture(name: &str, new_name: &str) -> PathBuf {
+    let src = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../game/testdata/saves")
+        .join(name);
+    let dest = temp_dir().join("saves").join(new_name);
+    if dest.exists() {
+        fs::remove_dir_all(&dest).unwrap();
+    }
+    copy_dir(&src, &dest);
+    dest
+}
+
+/// Read all files in the directory with their relative paths
+pub fn read_dir_files(dir: &Path) -> Vec<(PathBuf, Vec<u8>)> {
+    let mut files = Vec::new();
+    let mut dirs = vec![dir.to_owned()];
+    while let Some(d) = dirs.pop() {
+        for entry in fs::read_dir(&d).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else {
+                let data = fs::read(&path).unwrap();
+                files.push((path.strip_prefix(dir).unwrap().to_owned(), data));
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+fn copy_dir(src: &Path, dest: &Path) {
+    fs::create_dir_all(dest).unwrap();
+    for entry in fs::read_dir(src).unwrap() {
+        let entry = entry.unwrap();
+        let dest = dest.join(entry.file_name());
+        if entry.file_type().unwrap().is_dir() {
+            copy_dir(&entry.path(), &dest);
+        } else {
+            fs::copy(entry.path(), dest).unwrap();
+        }
+    }
+}
+
+fn write_pak(dir: &Path) {
+    fs::create_dir_all(dir).unwrap();
+    let objects = vec![
+        Object::CharaTemplate(CharaTemplateObject {
+            id: "!test-chara".to_owned(),
+            img: empty_img(),
+            race: Race::Human,
+            gen_weight: 1.0,
+            gen_level: 1,
+            default_ai_kind: NpcAIKind::None,
+            base_attr: CharaBaseAttr {
+                base_hp: 100,
+                str: 10,
+                vit: 10,
+                dex: 10,
+                int: 10,
+                wil: 10,
+                cha: 10,
+                spd: 100,
+            },
+        }),
+        Object::Tile(TileObject {
+            id: "!test-tile".to_owned(),
+            img: empty_img(),
+            kind: TileKind::Ground,
+            symbol_color: (0, 0, 0),
+        }),
+        Object::Item(ItemObject {
+            id: ITEM_ID.to_owned(),
+            img: empty_img(),
+            kind: ItemKind::Object,
+            group: String::new(),
+            default_flags: ItemFlags::empty(),
+            basic_price: 1,
+            w: 100,
+            gen_weight: 0.0,
+            shop_weight: 0.0,
+            gen_level: 0,
+            dice_n: 1,
+            dice_x: 4,
+            element: Element::Physical,
+            def: ElementArray::default(),
+            eff: 0,
+            magical_effect: MagicalEffect::None,
+            medical_effect: MedicalEffect::None,
+            tool_effect: ToolEffect::None,
+            use_effect: UseEffect::None,
+            nutrition: 0,
+            charge: [0, 0],
+            harvest: None,
+            facility: None,
+            light: 0,
+        }),
+    ];
+
+    let mut builder = tar::Builder::new(fs::File::create(dir.join("test.pak")).unwrap());
+    for object in &objects {
+        let mut data = Vec::new();
+        write_object(&mut data, object).unwrap();
+        let mut header = tar::Header::new_gnu();
+        header.set_path(object.get_id()).unwrap();
+        header.set_size(data.len() as u64);
+        header.set_cksum();
+        builder.append(&header, &data[..]).unwrap();
+    }
+    builder.finish().unwrap();
+}
+
+fn empty_img() -> Img {
+    Img {
+        data: Vec::new(),
+        w: 1,
+        h: 1,
+        grid_nx: 1,
+        grid_ny: 1,
+        n_frame: 1,
+        n_pattern: 1,
+        n_anim_frame: 1,
+        duration: 0,
+    }
+}
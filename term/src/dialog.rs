@@ -0,0 +1,935 @@
+//! Dialogs drawn over the map as text and a list of choices.
+//! They correspond to the dialog windows of the SDL frontend.
+
+use super::input::InputMode;
+use crate::config::INPUT_CFG;
+use crate::game::extrait::*;
+use crate::game::item::filter::*;
+use crate::game::item::info::ItemInfoText;
+use crate::game::newgame::NewGameBuilder;
+use crate::game::quest::available_quests;
+use crate::game::saveload::{load_error_msg, save_file_list, SaveFile};
+use crate::game::training::{trainer_skills, training_cost};
+use crate::game::{
+    AdvanceScriptResult, Command, DialogOpenRequest, DoPlayerAction, Game, InfoGetter, TalkText,
+};
+use crate::text::{self, misc_txt, ui_txt, ToText};
+use common::gamedata::*;
+use geom::*;
+use rules::RULES;
+
+pub enum DialogResult {
+    Continue,
+    Close,
+    CloseAll,
+    Quit,
+    OpenChildDialog(Box<dyn Dialog>),
+    Special(SpecialDialogResult),
+}
+
+pub enum SpecialDialogResult {
+    NewGameStart(Box<GameData>),
+    LoadGame(Box<GameData>),
+    ReturnToStartScreen,
+}
+
+/// Contents of a dialog to be drawn
+pub struct DialogView {
+    pub text: String,
+    pub choices: Vec<String>,
+    pub cursor: usize,
+}
+
+pub trait Dialog {
+    fn view(&self) -> DialogView;
+    fn process_command(&mut self, command: &Command, pa: &mut DoPlayerAction) -> DialogResult;
+    fn mode(&self) -> InputMode {
+        InputMode::Dialog
+    }
+    /// Called when the child dialog opened by this dialog is closed
+    fn callback_child_closed(&mut self, _pa: &mut DoPlayerAction) -> DialogResult {
+        DialogResult::Continue
+    }
+}
+
+enum ChoiceResult {
+    None,
+    Select(usize),
+    Cancel,
+}
+
+/// Choices and the cursor on them
+#[derive(Default)]
+struct ChoiceList {
+    choices: Vec<String>,
+    cursor: usize,
+}
+
+impl ChoiceList {
+    fn new(choices: Vec<String>) -> ChoiceList {
+        ChoiceList { choices, cursor: 0 }
+    }
+
+    fn set_choices(&mut self, choices: Vec<String>) {
+        self.choices = choices;
+        if self.cursor >= self.choices.len() {
+            self.cursor = self.choices.len().saturating_sub(1);
+        }
+    }
+
+    fn process_command(&mut self, command: &Command) -> ChoiceResult {
+        let n = self.choices.len();
+        match command {
+            Command::Move { dir } if n > 0 => {
+                match dir.vdir {
+                    VDirection::Up => self.cursor = (self.cursor + n - 1) % n,
+                    VDirection::Down => self.cursor = (self.cursor + 1) % n,
+                    VDirection::None => (),
+                }
+                ChoiceResult::None
+            }
+            Command::Enter if n > 0 => ChoiceResult::Select(self.cursor),
+            Command::Cancel => ChoiceResult::Cancel,
+            _ => ChoiceResult::None,
+        }
+    }
+
+    fn view(&self, text: String) -> DialogView {
+        DialogView {
+            text,
+            choices: self.choices.clone(),
+            cursor: self.cursor,
+        }
+    }
+}
+
+type ChooseCallback = dyn FnMut(&mut DoPlayerAction, usize) -> DialogResult;
+
+/// Shows a message and calls the callback with the chosen index
+pub struct ChooseDialog {
+    text: String,
+    list: ChoiceList,
+    cancelable: bool,
+    callback: Box<ChooseCallback>,
+}
+
+impl ChooseDialog {
+    pub fn new<F>(text: String, choices: Vec<String>, cancelable: bool, callback: F) -> ChooseDialog
+    where
+        F: FnMut(&mut DoPlayerAction, usize) -> DialogResult + 'static,
+    {
+        ChooseDialog {
+            text,
+            list: ChoiceList::new(choices),
+            cancelable,
+            callback: Box::new(callback),
+        }
+    }
+
+    pub fn yesno<F>(text: String, mut callback: F) -> ChooseDialog
+    where
+        F: FnMut(&mut DoPlayerAction, bool) -> DialogResult + 'static,
+    {
+        let choices = vec!["Yes".to_owned(), "No".to_owned()];
+        ChooseDialog::new(text, choices, true, move |pa, i| callback(pa, i == 0))
+    }
+
+    /// Shows a message only. Closed by Enter or Cancel.
+    pub fn msg(text: String) -> ChooseDialog {
+        ChooseDialog::new(text, vec![ui_txt("dialog-choice-close")], true, |_, _| {
+            DialogResult::Close
+        })
+    }
+}
+
+impl Dialog for ChooseDialog {
+    fn view(&self) -> DialogView {
+        self.list.view(self.text.clone())
+    }
+
+    fn process_command(&mut self, command: &Command, pa: &mut DoPlayerAction) -> DialogResult {
+        match self.list.process_command(command) {
+            ChoiceResult::Select(i) => (self.callback)(pa, i),
+            ChoiceResult::Cancel if self.cancelable => DialogResult::Close,
+            _ => DialogResult::Continue,
+        }
+    }
+}
+
+type TextInputCallback = dyn FnMut(&mut DoPlayerAction, &str) -> DialogResult;
+
+pub struct TextInputDialog {
+    text: String,
+    input: String,
+    callback: Box<TextInputCallback>,
+}
+
+impl TextInputDialog {
+    pub fn new<F>(text: String, callback: F) -> TextInputDialog
+    where
+        F: FnMut(&mut DoPlayerAction, &str) -> DialogResult + 'static,
+    {
+        TextInputDialog {
+            text,
+            input: String::new(),
+            callback: Box::new(callback),
+        }
+    }
+}
+
+impl Dialog for TextInputDialog {
+    fn view(&self) -> DialogView {
+        DialogView {
+            text: format!("{}\n> {}_", self.text, self.input),
+            choices: Vec::new(),
+            cursor: 0,
+        }
+    }
+
+    fn process_command(&mut self, command: &Command, pa: &mut DoPlayerAction) -> DialogResult {
+        match command {
+            Command::TextInput { text } => {
+                self.input.push_str(text);
+            }
+            Command::TextDelete => {
+                self.input.pop();
+            }
+            Command::Enter => {
+                return (self.callback)(pa, &self.input);
+            }
+            Command::Cancel => {
+                return DialogResult::Close;
+            }
+            _ => (),
+        }
+        DialogResult::Continue
+    }
+
+    fn mode(&self) -> InputMode {
+        InputMode::TextInput
+    }
+}
+
+pub fn start_dialog() -> ChooseDialog {
+    let choices = vec![
+        ui_txt("dialog-choice-newgame"),
+        ui_txt("dialog-choice-loadgame"),
+        ui_txt("dialog-choice-exit"),
+    ];
+    ChooseDialog::new(
+        format!("Rusted Ruins {}", env!("CARGO_PKG_VERSION")),
+        choices,
+        false,
+        |_, i| match i {
+            0 => DialogResult::OpenChildDialog(Box::new(player_name_dialog())),
+            1 => DialogResult::OpenChildDialog(Box::new(load_game_dialog())),
+            _ => DialogResult::Quit,
+        },
+    )
+}
+
+fn player_name_dialog() -> TextInputDialog {
+    TextInputDialog::new(ui_txt("newgame-inputplayername"), |_, name| {
+        if name.is_empty() {
+            return DialogResult::Continue;
+        }
+        DialogResult::OpenChildDialog(Box::new(choose_class_dialog(name.to_owned())))
+    })
+}
+
+fn choose_class_dialog(player_name: String) -> ChooseDialog {
+    let choices: Vec<String> = RULES
+        .newgame
+        .class_choices
+        .iter()
+        .map(|c| c.to_text().into_owned())
+        .collect();
+    ChooseDialog::new(ui_txt("newgame-chooseclass"), choices, true, move |_, i| {
+        let mut builder = NewGameBuilder::new();
+        builder.set_player_name(&player_name);
+        builder.set_chara_class(RULES.newgame.class_choices[i]);
+        let gd = builder.build();
+        DialogResult::Special(SpecialDialogResult::NewGameStart(Box::new(gd)))
+    })
+}
+
+fn load_game_dialog() -> ChooseDialog {
+    let save_files = match save_file_list() {
+        Ok(save_files) => save_files,
+        Err(e) => {
+            warn!("Failed to read save file directory: {}", e);
+            Vec::new()
+        }
+    };
+    let choices = save_files.iter().map(save_file_row).collect();
+    ChooseDialog::new(
+        ui_txt("dialog-choice-loadgame"),
+        choices,
+        true,
+        move |_, i| match GameData::load(&save_files[i].path) {
+            Ok(gd) => DialogResult::Special(SpecialDialogResult::LoadGame(Box::new(gd))),
+            Err(e) => {
+                warn!("Failed to load a save file: {}", e);
+                let msg = load_error_msg(&*e);
+                let dialog =
+                    ChooseDialog::new(msg, vec![ui_txt("dialog-choice-close")], true, |_, _| {
+                        DialogResult::Close
+                    });
+                DialogResult::OpenChildDialog(Box::new(dialog))
+            }
+        },
+    )
+}
+
+fn save_file_row(save_file: &SaveFile) -> String {
+    let file_name = save_file
+        .path
+        .file_name()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    if let Some(meta) = save_file.meta.as_ref() {
+        format!("{} Lv.{} ({})", meta.player_name, meta.level, file_name)
+    } else {
+        file_name
+    }
+}
+
+pub fn exit_dialog() -> ChooseDialog {
+    let choices = vec![
+        ui_txt("dialog-choice-save_game"),
+        ui_txt("dialog-choice-exit_game"),
+        ui_txt("dialog-choice-close"),
+    ];
+    ChooseDialog::new(ui_txt("dialog-exit"), choices, true, |pa, i| match i {
+        0 => {
+            pa.save_file();
+            DialogResult::Close
+        }
+        1 => DialogResult::Quit,
+        _ => DialogResult::Close,
+    })
+}
+
+fn game_over_dialog() -> ChooseDialog {
+    let choices = vec!["Return to start screen".to_owned(), "Quit".to_owned()];
+    ChooseDialog::new(ui_txt("dialog-gameover"), choices, false, |_, i| match i {
+        0 => DialogResult::Special(SpecialDialogResult::ReturnToStartScreen),
+        _ => DialogResult::Quit,
+    })
+}
+
+pub fn debug_command_dialog() -> TextInputDialog {
+    TextInputDialog::new(String::new(), |pa, s| {
+        pa.exec_debug_command(s);
+        DialogResult::Close
+    })
+}
+
+/// Key bindings for the normal mode
+pub fn help_dialog() -> ChooseDialog {
+    let mut bindings: Vec<String> = INPUT_CFG
+        .normal
+        .iter()
+        .map(|(key, command)| format!("{:>10} : {:?}", key, command))
+        .collect();
+    bindings.sort();
+    ChooseDialog::msg(bindings.join("\n"))
+}
+
+pub fn status_dialog(gd: &GameData) -> ChooseDialog {
+    let player = gd.chara.get(CharaId::Player);
+    let (max_hp, hp) = gd.player_hp();
+    let mut lines = vec![
+        format!("{} ({})", player.to_text(), player.class.to_text()),
+        format!(
+            "Lv {}  HP {}/{}  SP {:.0}",
+            player.level, hp, max_hp, player.sp
+        ),
+        format!(
+            "STR {}  VIT {}  DEX {}  INT {}  WIL {}  CHA {}  SPD {}",
+            player.attr.str,
+            player.attr.vit,
+            player.attr.dex,
+            player.attr.int,
+            player.attr.wil,
+            player.attr.cha,
+            player.attr.spd
+        ),
+        format!("{}G", gd.player.money()),
+    ];
+    lines.extend(
+        player
+            .status
+            .iter()
+            .map(|status| status.to_text().into_owned()),
+    );
+    ChooseDialog::msg(lines.join("\n"))
+}
+
+/// Shown for windows which this frontend does not have
+pub fn unsupported_dialog() -> ChooseDialog {
+    ChooseDialog::msg(ui_txt("term-unsupported"))
+}
+
+pub fn dialog_from_request(req: DialogOpenRequest, game: &mut Game) -> Option<Box<dyn Dialog>> {
+    Some(match req {
+        DialogOpenRequest::YesNo { mut callback, msg } => {
+            Box::new(ChooseDialog::yesno(msg, move |pa, yes| {
+                callback(pa, yes);
+                DialogResult::Close
+            }))
+        }
+        DialogOpenRequest::Talk { talk_text, .. } => Box::new(TalkDialog::new(talk_text)),
+        DialogOpenRequest::ItemInfo { il } => Box::new(item_info_dialog(&game.gd, il)),
+        DialogOpenRequest::ShopBuy { cid } => {
+            Box::new(ItemDialog::new(ItemDialogMode::ShopBuy { cid }, &game.gd))
+        }
+        DialogOpenRequest::ShopSell => {
+            Box::new(ItemDialog::new(ItemDialogMode::ShopSell, &game.gd))
+        }
+        DialogOpenRequest::PickUpItem => {
+            Box::new(ItemDialog::new(ItemDialogMode::PickUp, &game.gd))
+        }
+        DialogOpenRequest::Quest => Box::new(QuestDialog::new(&game.gd)),
+        DialogOpenRequest::Train { cid } => Box::new(TrainDialog::new(&game.gd, cid)),
+        DialogOpenRequest::GameOver => Box::new(game_over_dialog()),
+    })
+}
+
+/// Splits talk text into pages, and shows answers on the final page
+pub struct TalkDialog {
+    talk_text: TalkText,
+    pages: Vec<String>,
+    current_page: usize,
+    list: ChoiceList,
+}
+
+impl TalkDialog {
+    pub fn new(talk_text: TalkText) -> TalkDialog {
+        let mut dialog = TalkDialog {
+            talk_text,
+            pages: Vec::new(),
+            current_page: 0,
+            list: ChoiceList::default(),
+        };
+        dialog.update_page(talk_text);
+        dialog
+    }
+
+    fn update_page(&mut self, talk_text: TalkText) {
+        self.talk_text = talk_text;
+        let text_id = talk_text.text_id;
+        self.pages = if let Some(s) = text::talk_txt_checked(text_id, None) {
+            s.split("\n\n").map(|s| s.to_owned()).collect()
+        } else {
+            vec![text_id.to_owned()]
+        };
+        self.current_page = 0;
+        self.update_choices();
+    }
+
+    fn is_final_page(&self) -> bool {
+        self.current_page + 1 >= self.pages.len()
+    }
+
+    fn update_choices(&mut self) {
+        let choices = match self.talk_text.choices {
+            Some(choices) if self.is_final_page() => {
+                choices.iter().map(|a| text::talk_txt(&a.0)).collect()
+            }
+            _ => Vec::new(),
+        };
+        self.list = ChoiceList::new(choices);
+    }
+
+    fn advance(&mut self, pa: &mut DoPlayerAction, choice: Option<u32>) -> DialogResult {
+        match pa.advance_talk(choice) {
+            AdvanceScriptResult::UpdateTalkText(talk_text) => {
+                self.update_page(talk_text);
+                DialogResult::Continue
+            }
+            AdvanceScriptResult::Continue => DialogResult::Continue,
+            AdvanceScriptResult::Quit => DialogResult::Close,
+        }
+    }
+}
+
+impl Dialog for TalkDialog {
+    fn view(&self) -> DialogView {
+        self.list.view(self.pages[self.current_page].clone())
+    }
+
+    fn process_command(&mut self, command: &Command, pa: &mut DoPlayerAction) -> DialogResult {
+        if !self.list.choices.is_empty() {
+            if let ChoiceResult::Select(i) = self.list.process_command(command) {
+                return self.advance(pa, Some(i as u32));
+            }
+            return DialogResult::Continue;
+        }
+
+        match command {
+            Command::Enter => {
+                if self.is_final_page() {
+                    self.advance(pa, None)
+                } else {
+                    self.current_page += 1;
+                    self.update_choices();
+                    DialogResult::Continue
+                }
+            }
+            _ => DialogResult::Continue,
+        }
+    }
+
+    /// Shop or other dialogs opened by the script are closed
+    fn callback_child_closed(&mut self, pa: &mut DoPlayerAction) -> DialogResult {
+        match pa.advance_script() {
+            AdvanceScriptResult::UpdateTalkText(talk_text) => {
+                self.update_page(talk_text);
+                DialogResult::Continue
+            }
+            AdvanceScriptResult::Continue => DialogResult::Continue,
+            AdvanceScriptResult::Quit => DialogResult::Close,
+        }
+    }
+}
+
+fn item_info_dialog(gd: &GameData, il: ItemLocation) -> ChooseDialog {
+    let info = ItemInfoText::new(gd.get_item(il).0);
+    let mut lines = vec![info.item_name, info.item_kind];
+    lines.extend(info.desc_text.into_iter().map(|(_, s)| s));
+    ChooseDialog::msg(lines.join("\n"))
+}
+
+pub type ItemActionCallback = dyn FnMut(&mut DoPlayerAction, ItemLocation) -> DialogResult;
+
+pub enum ItemDialogMode {
+    List,
+    PickUp,
+    Drop,
+    Drink,
+    Eat,
+    Use,
+    Release,
+    Throw,
+    ShopSell,
+    ShopBuy {
+        cid: CharaId,
+    },
+    Select {
+        ill: ItemListLocation,
+        filter: ItemFilter,
+        action: Box<ItemActionCallback>,
+    },
+}
+
+/// Modes switched by left and right keys like the tabs of the item window
+const N_ITEM_TABS: usize = 6;
+
+fn item_tab_mode(i: usize) -> ItemDialogMode {
+    match i {
+        0 => ItemDialogMode::List,
+        1 => ItemDialogMode::Drop,
+        2 => ItemDialogMode::Drink,
+        3 => ItemDialogMode::Eat,
+        4 => ItemDialogMode::Use,
+        _ => ItemDialogMode::Release,
+    }
+}
+
+pub struct ItemDialog {
+    mode: ItemDialogMode,
+    list: ChoiceList,
+    item_locations: Vec<ItemLocation>,
+}
+
+impl ItemDialog {
+    pub fn new(mode: ItemDialogMode, gd: &GameData) -> ItemDialog {
+        let mut dialog = ItemDialog {
+            mode,
+            list: ChoiceList::default(),
+            item_locations: Vec::new(),
+        };
+        dialog.update(gd);
+        dialog
+    }
+
+    fn update(&mut self, gd: &GameData) {
+        let player_ill = ItemListLocation::Chara {
+            cid: CharaId::Player,
+        };
+        let (ill, filter) = match self.mode {
+            ItemDialogMode::List | ItemDialogMode::Drop => (player_ill, ItemFilter::all()),
+            ItemDialogMode::PickUp => {
+                let ill = ItemListLocation::OnMap {
+                    mid: gd.get_current_mapid(),
+                    pos: gd.player_pos(),
+                };
+                (ill, ItemFilter::all())
+            }
+            ItemDialogMode::Drink => (player_ill, ItemFilter::new().flags(ItemFlags::DRINKABLE)),
+            ItemDialogMode::Eat => (player_ill, ItemFilter::new().flags(ItemFlags::EATABLE)),
+            ItemDialogMode::Use => (player_ill, ItemFilter::new().usable(true)),
+            ItemDialogMode::Throw => (player_ill, ItemFilter::new().throwable(true)),
+            ItemDialogMode::Release => (
+                player_ill,
+                ItemFilter::new().kind_rough(ItemKindRough::MagicDevice),
+            ),
+            ItemDialogMode::ShopBuy { cid } => (ItemListLocation::Shop { cid }, ItemFilter::new()),
+            ItemDialogMode::ShopSell => (player_ill, ItemFilter::new()),
+            ItemDialogMode::Select { ill, filter, .. } => (ill, filter),
+        };
+
+        let list = gd.get_filtered_item_list(ill, filter);
+        self.item_locations = list.clone().map(|(il, _, _)| il).collect();
+        let rows = list
+            .map(|(_, item, n)| {
+                let info = match self.mode {
+                    ItemDialogMode::ShopBuy { .. } => format!("{}G", item.price()),
+                    ItemDialogMode::ShopSell => format!("{}G", item.selling_price()),
+                    _ => format!("{:.2}kg", item.w() as f32 / 1000.0),
+                };
+                format!("{} x {}  {}", item.to_text(), n, info)
+            })
+            .collect();
+        self.list.set_choices(rows);
+    }
+
+    fn tab_index(&self) -> Option<usize> {
+        Some(match self.mode {
+            ItemDialogMode::List => 0,
+            ItemDialogMode::Drop => 1,
+            ItemDialogMode::Drink => 2,
+            ItemDialogMode::Eat => 3,
+            ItemDialogMode::Use => 4,
+            ItemDialogMode::Release => 5,
+            _ => return None,
+        })
+    }
+
+    fn do_action_for_item(&mut self, pa: &mut DoPlayerAction, il: ItemLocation) -> DialogResult {
+        match self.mode {
+            ItemDialogMode::List => {
+                DialogResult::OpenChildDialog(Box::new(item_info_dialog(pa.gd(), il)))
+            }
+            ItemDialogMode::PickUp => {
+                pa.pick_up_item(il, 1);
+                if pa.gd().is_item_on_player_tile() {
+                    self.update(pa.gd());
+                    DialogResult::Continue
+                } else {
+                    DialogResult::Close
+                }
+            }
+            ItemDialogMode::Drop => {
+                pa.drop_item(il, 1);
+                self.update(pa.gd());
+                DialogResult::Continue
+            }
+            ItemDialogMode::Drink => {
+                pa.drink_item(il);
+                DialogResult::CloseAll
+            }
+            ItemDialogMode::Eat => {
+                pa.eat_item(il);
+                DialogResult::CloseAll
+            }
+            ItemDialogMode::Use => {
+                pa.use_item(il);
+                DialogResult::CloseAll
+            }
+            ItemDialogMode::Release => {
+                pa.release_item(il);
+                DialogResult::CloseAll
+            }
+            ItemDialogMode::Throw => {
+                pa.throw_item(il);
+                DialogResult::CloseAll
+            }
+            ItemDialogMode::ShopBuy { .. } => {
+                pa.buy_item(il);
+                self.update(pa.gd());
+                DialogResult::Continue
+            }
+            ItemDialogMode::ShopSell => {
+                pa.sell_item(il);
+                self.update(pa.gd());
+                DialogResult::Continue
+            }
+            ItemDialogMode::Select { ref mut action, .. } => action(pa, il),
+        }
+    }
+}
+
+impl Dialog for ItemDialog {
+    fn view(&self) -> DialogView {
+        let title = match self.mode {
+            ItemDialogMode::List => "tab_text-item_list",
+            ItemDialogMode::Drop => "tab_text-item_drop",
+            ItemDialogMode::Drink => "tab_text-item_drink",
+            ItemDialogMode::Eat => "tab_text-item_eat",
+            ItemDialogMode::Use => "tab_text-item_use",
+            ItemDialogMode::Release => "tab_text-item_release",
+            _ => "",
+        };
+        let title = if title.is_empty() {
+            String::new()
+        } else {
+            ui_txt(title)
+        };
+        self.list.view(title)
+    }
+
+    fn process_command(&mut self, command: &Command, pa: &mut DoPlayerAction) -> DialogResult {
+        if let (Command::Move { dir }, Some(i)) = (command, self.tab_index()) {
+            let next = match dir.hdir {
+                HDirection::Left => (i + N_ITEM_TABS - 1) % N_ITEM_TABS,
+                HDirection::Right => (i + 1) % N_ITEM_TABS,
+                HDirection::None => i,
+            };
+            if next != i {
+                self.mode = item_tab_mode(next);
+                self.list.cursor = 0;
+                self.update(pa.gd());
+                return DialogResult::Continue;
+            }
+        }
+
+        if let Command::ItemInfomation = command {
+            if let Some(il) = self.item_locations.get(self.list.cursor) {
+                return DialogResult::OpenChildDialog(Box::new(item_info_dialog(pa.gd(), *il)));
+            }
+        }
+
+        match self.list.process_command(command) {
+            ChoiceResult::Select(i) => {
+                let il = self.item_locations[i];
+                self.do_action_for_item(pa, il)
+            }
+            ChoiceResult::Cancel => DialogResult::Close,
+            ChoiceResult::None => DialogResult::Continue,
+        }
+    }
+}
+
+/// Lists equipment slots, and opens the item list to change the selected one
+pub struct EquipDialog {
+    cid: CharaId,
+    list: ChoiceList,
+}
+
+impl EquipDialog {
+    pub fn new(gd: &GameData, cid: CharaId) -> EquipDialog {
+        let mut dialog = EquipDialog {
+            cid,
+            list: ChoiceList::default(),
+        };
+        dialog.update(gd);
+        dialog
+    }
+
+    fn update(&mut self, gd: &GameData) {
+        let rows = gd
+            .get_equip_list(self.cid)
+            .slot_iter()
+            .map(|(esk, _, item)| {
+                let item_text = item.map_or("-".to_owned(), |item| item.to_text().into_owned());
+                format!("{:?}: {}", esk, item_text)
+            })
+            .collect();
+        self.list.set_choices(rows);
+    }
+}
+
+impl Dialog for EquipDialog {
+    fn view(&self) -> DialogView {
+        self.list.view(String::new())
+    }
+
+    fn process_command(&mut self, command: &Command, pa: &mut DoPlayerAction) -> DialogResult {
+        match self.list.process_command(command) {
+            ChoiceResult::Select(i) => {
+                let cid = self.cid;
+                let (esk, esk_i, _) = pa.gd().get_equip_list(cid).slot_iter().nth(i).unwrap();
+                let slot = (esk, esk_i);
+                let mode = ItemDialogMode::Select {
+                    ill: ItemListLocation::Chara {
+                        cid: CharaId::Player,
+                    },
+                    filter: ItemFilter::new().equip_slot_kind(esk),
+                    action: Box::new(move |pa, il| {
+                        pa.change_equipment(cid, slot, il);
+                        DialogResult::Close
+                    }),
+                };
+                DialogResult::OpenChildDialog(Box::new(ItemDialog::new(mode, pa.gd())))
+            }
+            ChoiceResult::Cancel => DialogResult::Close,
+            ChoiceResult::None => DialogResult::Continue,
+        }
+    }
+
+    fn callback_child_closed(&mut self, pa: &mut DoPlayerAction) -> DialogResult {
+        self.update(pa.gd());
+        DialogResult::Continue
+    }
+}
+
+/// Player chooses a learned spell to cast
+pub struct SpellDialog {
+    spells: Vec<String>,
+    list: ChoiceList,
+}
+
+impl SpellDialog {
+    pub fn new(gd: &GameData) -> SpellDialog {
+        let spells = gd.chara.get(CharaId::Player).spells.clone();
+        let rows = spells
+            .iter()
+            .map(|spell_id| {
+                let cost = RULES
+                    .spell
+                    .get(spell_id)
+                    .map_or(String::new(), |spell| format!("SP {:.0}", spell.cost));
+                format!("{}  {}", text::spell_txt(spell_id), cost)
+            })
+            .collect();
+        SpellDialog {
+            spells,
+            list: ChoiceList::new(rows),
+        }
+    }
+}
+
+impl Dialog for SpellDialog {
+    fn view(&self) -> DialogView {
+        self.list.view(String::new())
+    }
+
+    fn process_command(&mut self, command: &Command, pa: &mut DoPlayerAction) -> DialogResult {
+        match self.list.process_command(command) {
+            ChoiceResult::Select(i) => {
+                let spell_id = self.spells[i].clone();
+                pa.cast_spell(&spell_id);
+                DialogResult::CloseAll
+            }
+            ChoiceResult::Cancel => DialogResult::Close,
+            ChoiceResult::None => DialogResult::Continue,
+        }
+    }
+}
+
+pub struct QuestDialog {
+    list: ChoiceList,
+    description: Vec<String>,
+}
+
+impl QuestDialog {
+    fn new(gd: &GameData) -> QuestDialog {
+        let mut dialog = QuestDialog {
+            list: ChoiceList::default(),
+            description: Vec::new(),
+        };
+        dialog.update(gd);
+        dialog
+    }
+
+    fn update(&mut self, gd: &GameData) {
+        let quests = available_quests(gd);
+        self.list.set_choices(
+            quests
+                .iter()
+                .map(|quest| quest.to_text().into_owned())
+                .collect(),
+        );
+        self.description = quests.iter().map(quest_decription_text).collect();
+    }
+}
+
+impl Dialog for QuestDialog {
+    fn view(&self) -> DialogView {
+        let description = self
+            .description
+            .get(self.list.cursor)
+            .cloned()
+            .unwrap_or_default();
+        self.list.view(description)
+    }
+
+    fn process_command(&mut self, command: &Command, _pa: &mut DoPlayerAction) -> DialogResult {
+        match self.list.process_command(command) {
+            ChoiceResult::Select(i) => {
+                let dialog =
+                    ChooseDialog::yesno(ui_txt("dialog-undertake_quest"), move |pa, yes| {
+                        if yes {
+                            pa.undertake_quest(i as u32);
+                        }
+                        DialogResult::Close
+                    });
+                DialogResult::OpenChildDialog(Box::new(dialog))
+            }
+            ChoiceResult::Cancel => DialogResult::Close,
+            ChoiceResult::None => DialogResult::Continue,
+        }
+    }
+
+    fn callback_child_closed(&mut self, pa: &mut DoPlayerAction) -> DialogResult {
+        self.update(pa.gd());
+        DialogResult::Continue
+    }
+}
+
+fn quest_decription_text(quest: &Quest) -> String {
+    match quest {
+        Quest::SlayMonsters { idx, goal, .. } => {
+            misc_txt_format!("desc-quest-slay_monsters"; monster=idx, n=goal)
+        }
+        Quest::Custom { id } => misc_txt(&format!("desc-quest-{}", id)),
+    }
+}
+
+pub struct TrainDialog {
+    trainer: CharaId,
+    skills: &'static [SkillKind],
+    list: ChoiceList,
+}
+
+impl TrainDialog {
+    fn new(gd: &GameData, trainer: CharaId) -> TrainDialog {
+        let mut dialog = TrainDialog {
+            trainer,
+            skills: trainer_skills(gd, trainer),
+            list: ChoiceList::default(),
+        };
+        dialog.update(gd);
+        dialog
+    }
+
+    fn update(&mut self, gd: &GameData) {
+        let player = gd.chara.get(CharaId::Player);
+        let rows = self
+            .skills
+            .iter()
+            .map(|kind| format!("{}  {}G", kind.to_text(), training_cost(player, *kind)))
+            .collect();
+        self.list.set_choices(rows);
+    }
+}
+
+impl Dialog for TrainDialog {
+    fn view(&self) -> DialogView {
+        self.list.view(String::new())
+    }
+
+    fn process_command(&mut self, command: &Command, pa: &mut DoPlayerAction) -> DialogResult {
+        match self.list.process_command(command) {
+            ChoiceResult::Select(i) => {
+                pa.train_skill(self.trainer, self.skills[i]);
+                self.update(pa.gd());
+                DialogResult::Continue
+            }
+            ChoiceResult::Cancel => DialogResult::Close,
+            ChoiceResult::None => DialogResult::Continue,
+        }
+    }
+}
@@ -0,0 +1,176 @@
+//! Characters and colors to represent tiles and objects on the map
+
+use crate::game::view::ViewMap;
+use common::gamedata::*;
+use common::gobj;
+use common::obj::TileKind;
+use common::objholder::ItemIdx;
+use geom::*;
+
+pub type Rgb = (u8, u8, u8);
+
+const PLAYER_COLOR: Rgb = (255, 255, 0);
+const DECO_COLOR: Rgb = (170, 140, 90);
+const SPECIAL_COLOR: Rgb = (255, 255, 255);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Glyph {
+    pub c: char,
+    pub color: Rgb,
+}
+
+impl Glyph {
+    pub const BLANK: Glyph = Glyph {
+        c: ' ',
+        color: (0, 0, 0),
+    };
+
+    pub fn new(c: char, color: Rgb) -> Glyph {
+        Glyph { c, color }
+    }
+
+    /// Tiles out of sight are drawn dark
+    fn darken(self) -> Glyph {
+        let (r, g, b) = self.color;
+        Glyph::new(self.c, (r / 2, g / 2, b / 2))
+    }
+}
+
+/// The glyph of the tile on the current map.
+/// Characters are shown only in the player's sight, and other objects as observed.
+pub fn tile_glyph(gd: &GameData, view_map: &ViewMap, p: Vec2d) -> Glyph {
+    let map = gd.get_current_map();
+    if !map.is_inside(p) {
+        return Glyph::BLANK;
+    }
+    let visible = view_map.get_tile_visible(p);
+
+    if visible {
+        if let Some(cid) = map.get_chara(p) {
+            return chara_glyph(gd, cid);
+        }
+    }
+
+    let observed = &map.observed_tile[p];
+    if !observed.tile {
+        return Glyph::BLANK;
+    }
+
+    let glyph = if let Some(wall_idx) = observed.wall.idx() {
+        Glyph::new('#', gobj::get_obj(wall_idx).symbol_color)
+    } else if let Some(glyph) = special_glyph(observed.special) {
+        glyph
+    } else if let Some(item_idx) = observed.items.first() {
+        item_glyph(*item_idx)
+    } else if observed.deco.is_some() {
+        Glyph::new('&', DECO_COLOR)
+    } else {
+        let tile = gobj::get_obj(map.tile[p].main_tile());
+        let c = match tile.kind {
+            TileKind::Ground => '.',
+            TileKind::Water => '~',
+        };
+        Glyph::new(c, tile.symbol_color)
+    };
+
+    if visible {
+        glyph
+    } else {
+        glyph.darken()
+    }
+}
+
+fn chara_glyph(gd: &GameData, cid: CharaId) -> Glyph {
+    if cid == CharaId::Player {
+        return Glyph::new('@', PLAYER_COLOR);
+    }
+    let chara = gd.chara.get(cid);
+    race_glyph(gobj::get_obj(chara.template).race, chara.rel)
+}
+
+fn race_glyph(race: Race, rel: Relationship) -> Glyph {
+    let c = match race {
+        Race::Animal => 'a',
+        Race::Human => 'h',
+        Race::Bug => 'b',
+        Race::Slime => 's',
+        Race::Devil => 'd',
+        Race::Phantom => 'p',
+        Race::Ghost => 'g',
+    };
+    let color = match rel {
+        Relationship::ALLY => (80, 200, 255),
+        Relationship::FRIENDLY => (80, 255, 80),
+        Relationship::NEUTRAL => (220, 220, 220),
+        Relationship::HOSTILE => (255, 80, 80),
+    };
+    Glyph::new(c, color)
+}
+
+fn special_glyph(special: SpecialTileKind) -> Option<Glyph> {
+    let c = match special {
+        SpecialTileKind::None => return None,
+        SpecialTileKind::Stairs { kind, .. } => match kind {
+            StairsKind::DownStairs => '>',
+            StairsKind::UpStairs => '<',
+        },
+        SpecialTileKind::SiteSymbol { kind } => match kind {
+            SiteSymbolKind::Cave => 'O',
+            SiteSymbolKind::Ruin => 'R',
+            SiteSymbolKind::Tower => 'I',
+            SiteSymbolKind::Town => 'T',
+            SiteSymbolKind::Village => 'V',
+            SiteSymbolKind::HomeInitial => 'H',
+        },
+    };
+    Some(Glyph::new(c, SPECIAL_COLOR))
+}
+
+fn item_glyph(idx: ItemIdx) -> Glyph {
+    item_kind_glyph(gobj::get_obj(idx).kind)
+}
+
+fn item_kind_glyph(kind: ItemKind) -> Glyph {
+    match kind {
+        ItemKind::Potion => Glyph::new('!', (200, 80, 255)),
+        ItemKind::Food => Glyph::new('%', (255, 160, 60)),
+        ItemKind::MagicDevice => Glyph::new('-', (80, 160, 255)),
+        ItemKind::Weapon(_) => Glyph::new('/', (200, 200, 220)),
+        ItemKind::Armor(_) => Glyph::new('[', (200, 200, 220)),
+        ItemKind::Tool => Glyph::new('(', (180, 140, 100)),
+        ItemKind::Container => Glyph::new('=', (180, 140, 100)),
+        ItemKind::Special => Glyph::new('*', (255, 220, 80)),
+        ItemKind::Material => Glyph::new(',', (160, 160, 160)),
+        ItemKind::Object => Glyph::new('?', (160, 160, 160)),
+        ItemKind::Ammo(_) => Glyph::new('{', (200, 200, 220)),
+    }
+}
+
+#[test]
+fn glyph_test() {
+    assert_eq!(special_glyph(SpecialTileKind::None), None);
+    let stairs = SpecialTileKind::Stairs {
+        dest_floor: 1,
+        kind: StairsKind::DownStairs,
+    };
+    assert_eq!(special_glyph(stairs).unwrap().c, '>');
+    let town = SpecialTileKind::SiteSymbol {
+        kind: SiteSymbolKind::Town,
+    };
+    assert_eq!(special_glyph(town).unwrap().c, 'T');
+
+    assert_eq!(item_kind_glyph(ItemKind::Potion).c, '!');
+    assert_eq!(item_kind_glyph(ItemKind::Food).c, '%');
+
+    // Hostile and friendly characters of the same race differ only in color
+    let hostile = race_glyph(Race::Slime, Relationship::HOSTILE);
+    let friendly = race_glyph(Race::Slime, Relationship::FRIENDLY);
+    assert_eq!(hostile.c, 's');
+    assert_eq!(hostile.c, friendly.c);
+    assert_ne!(hostile.color, friendly.color);
+
+    assert_eq!(
+        Glyph::new('#', (200, 100, 51)).darken().color,
+        (100, 50, 25)
+    );
+}
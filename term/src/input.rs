@@ -0,0 +1,124 @@
+//! Converts key presses to Command by the key bindings in input.toml
+
+use crate::config::INPUT_CFG;
+use crate::game::Command;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use geom::*;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InputMode {
+    Normal,
+    Dialog,
+    Targeting,
+    TextInput,
+}
+
+pub fn conv_key(key: KeyEvent, mode: InputMode) -> Option<Command> {
+    // Raw mode catches Ctrl-C, so it asks to exit instead of killing the process
+    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+        return Some(if mode == InputMode::Normal {
+            Command::OpenExitWin
+        } else {
+            Command::Cancel
+        });
+    }
+
+    if mode == InputMode::TextInput {
+        return match key.code {
+            KeyCode::Char(c) => Some(Command::TextInput {
+                text: c.to_string(),
+            }),
+            KeyCode::Backspace => Some(Command::TextDelete),
+            KeyCode::Enter => Some(Command::Enter),
+            KeyCode::Esc => Some(Command::Cancel),
+            _ => None,
+        };
+    }
+
+    if let Some(dir) = key_to_dir(key.code) {
+        return Some(Command::Move { dir });
+    }
+
+    let table = match mode {
+        InputMode::Normal => &INPUT_CFG.normal,
+        InputMode::Dialog => &INPUT_CFG.dialog,
+        InputMode::Targeting => &INPUT_CFG.targeting,
+        InputMode::TextInput => unreachable!(),
+    };
+    table.get(&key_name(key.code)?).cloned()
+}
+
+/// Arrow keys and numpad digits are used for moving
+fn key_to_dir(code: KeyCode) -> Option<Direction> {
+    Some(match code {
+        KeyCode::Up | KeyCode::Char('8') => Direction::N,
+        KeyCode::Down | KeyCode::Char('2') => Direction::S,
+        KeyCode::Left | KeyCode::Char('4') => Direction::W,
+        KeyCode::Right | KeyCode::Char('6') => Direction::E,
+        KeyCode::Char('7') | KeyCode::Home => Direction::NW,
+        KeyCode::Char('9') | KeyCode::PageUp => Direction::NE,
+        KeyCode::Char('1') | KeyCode::End => Direction::SW,
+        KeyCode::Char('3') | KeyCode::PageDown => Direction::SE,
+        _ => return None,
+    })
+}
+
+/// Key names used in input.toml, which are the same as SDL keycode names
+fn key_name(code: KeyCode) -> Option<String> {
+    Some(match code {
+        KeyCode::Char(c) => c.to_ascii_lowercase().to_string(),
+        KeyCode::Enter => "return".to_owned(),
+        KeyCode::Esc => "escape".to_owned(),
+        KeyCode::Tab => "tab".to_owned(),
+        KeyCode::Backspace => "backspace".to_owned(),
+        KeyCode::Delete => "delete".to_owned(),
+        KeyCode::F(n) => format!("f{}", n),
+        _ => return None,
+    })
+}
+
+#[test]
+fn conv_key_test() {
+    std::env::set_var(
+        "RUSTED_RUINS_ASSETS_DIR",
+        concat!(env!("CARGO_MANIFEST_DIR"), "/../assets"),
+    );
+    let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+    assert_eq!(
+        conv_key(key(KeyCode::Up), InputMode::Normal),
+        Some(Command::Move { dir: Direction::N })
+    );
+    assert_eq!(
+        conv_key(key(KeyCode::Char('3')), InputMode::Targeting),
+        Some(Command::Move { dir: Direction::SE })
+    );
+    // Bindings differ by the mode
+    assert_eq!(
+        conv_key(key(KeyCode::Esc), InputMode::Normal),
+        Some(Command::OpenExitWin)
+    );
+    assert_eq!(
+        conv_key(key(KeyCode::Esc), InputMode::Dialog),
+        Some(Command::Cancel)
+    );
+    assert_eq!(
+        conv_key(key(KeyCode::Char('I')), InputMode::Normal),
+        Some(Command::OpenItemMenu)
+    );
+    assert_eq!(conv_key(key(KeyCode::Insert), InputMode::Normal), None);
+    // Keys for moving are typed as text in text input
+    assert_eq!(
+        conv_key(key(KeyCode::Char('8')), InputMode::TextInput),
+        Some(Command::TextInput {
+            text: "8".to_owned()
+        })
+    );
+
+    let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+    assert_eq!(
+        conv_key(ctrl_c, InputMode::Normal),
+        Some(Command::OpenExitWin)
+    );
+    assert_eq!(conv_key(ctrl_c, InputMode::Dialog), Some(Command::Cancel));
+}
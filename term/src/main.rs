@@ -0,0 +1,315 @@
+//! Terminal frontend. The map, log and dialogs are drawn as colored glyphs,
+//! and all game logic is shared with the SDL frontend.
+
+extern crate rusted_ruins_common as common;
+extern crate rusted_ruins_geom as geom;
+extern crate rusted_ruins_rules as rules;
+#[macro_use]
+extern crate rusted_ruins_game;
+#[macro_use]
+extern crate log as applog;
+
+mod dialog;
+mod glyph;
+mod input;
+mod screen;
+
+use rusted_ruins_game::{config, game, log, text};
+
+use crate::config::CONFIG;
+use crate::dialog::*;
+use crate::game::replay::{self, Recorder};
+use crate::game::{Command, DoPlayerAction, Game, GameState, InfoGetter};
+use crate::input::InputMode;
+use common::gamedata::*;
+use crossterm::event::{self, Event, KeyEventKind};
+use crossterm::{cursor, execute, terminal};
+use geom::*;
+use std::collections::VecDeque;
+use std::io;
+
+fn main() {
+    setup_logger();
+    config::init();
+    text::init();
+    log::init();
+    init_obj();
+    rules::init(&*config::ASSETS_DIR);
+
+    if let Err(e) = run() {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run() -> io::Result<()> {
+    terminal::enable_raw_mode()?;
+    execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+
+    let result = TermFrontend::new().and_then(|mut frontend| frontend.main_loop());
+
+    execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+    result
+}
+
+struct TermFrontend {
+    game: Game,
+    on_game: bool,
+    dialogs: Vec<Box<dyn Dialog>>,
+    /// The tile pointed in targeting mode
+    target_cursor: Option<Vec2d>,
+    screen: screen::Screen,
+    recorder: Option<Recorder>,
+    /// Commands from a replay, processed before key inputs
+    replay_commands: VecDeque<Command>,
+}
+
+impl TermFrontend {
+    fn new() -> io::Result<TermFrontend> {
+        let mut frontend = TermFrontend {
+            game: Game::empty(),
+            on_game: false,
+            dialogs: vec![Box::new(start_dialog())],
+            target_cursor: None,
+            screen: screen::Screen::new()?,
+            recorder: None,
+            replay_commands: VecDeque::new(),
+        };
+        if let Some(path) = CONFIG.replay.as_ref() {
+            match replay::load(path) {
+                Ok(replay) => {
+                    info!("Start replay {}", path.to_string_lossy());
+                    frontend.start_game(replay.gd, replay.header.new_game);
+                    frontend.replay_commands = replay.commands.into();
+                }
+                Err(e) => warn!("Failed to load replay {}: {}", path.to_string_lossy(), e),
+            }
+        }
+        Ok(frontend)
+    }
+
+    fn main_loop(&mut self) -> io::Result<()> {
+        loop {
+            if self.on_game && self.game.get_state() == GameState::WaitingForNextTurn {
+                self.game.advance_turn();
+            }
+            // Animations are not shown in the terminal
+            while self.game.pop_animation().is_some() {}
+
+            if let Some(req) = self.game.pop_dialog_open_request() {
+                if let Some(dialog) = dialog_from_request(req, &mut self.game) {
+                    self.dialogs.push(dialog);
+                }
+            }
+
+            if self.game.get_state() == GameState::PlayerTurn {
+                self.draw()?;
+                let command = match self.replay_commands.pop_front() {
+                    Some(command) => Some(command),
+                    None => read_command(self.mode())?,
+                };
+                if let Some(command) = command {
+                    if let Some(recorder) = self.recorder.as_mut() {
+                        recorder.record(&command);
+                    }
+                    if !self.process_command(command) {
+                        if let Some(recorder) = self.recorder.take() {
+                            recorder.finish(&self.game.gd);
+                        }
+                        self.game.end_game();
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+
+    fn draw(&mut self) -> io::Result<()> {
+        let views: Vec<DialogView> = self.dialogs.iter().map(|dialog| dialog.view()).collect();
+        if self.on_game {
+            self.game.update_before_drawing();
+            self.screen
+                .draw(Some(&self.game), &views, self.target_cursor)
+        } else {
+            self.screen.draw(None, &views, None)
+        }
+    }
+
+    fn mode(&self) -> InputMode {
+        if let Some(dialog) = self.dialogs.last() {
+            dialog.mode()
+        } else if self.target_cursor.is_some() {
+            InputMode::Targeting
+        } else {
+            InputMode::Normal
+        }
+    }
+
+    /// If return value is false, quit.
+    fn process_command(&mut self, command: Command) -> bool {
+        if let Some(target_cursor) = self.target_cursor.as_mut() {
+            match command {
+                Command::Move { dir } => {
+                    *target_cursor = *target_cursor + dir.as_vec();
+                }
+                Command::Cancel => {
+                    self.target_cursor = None;
+                }
+                Command::Enter if self.game.set_target(*target_cursor) => {
+                    self.target_cursor = None;
+                }
+                _ => (),
+            }
+            return true;
+        }
+
+        if !self.dialogs.is_empty() {
+            return self.process_command_dialog(command);
+        }
+
+        let mut pa = DoPlayerAction::new(&mut self.game);
+        let dialog: Box<dyn Dialog> = match command {
+            Command::Move { dir } => {
+                pa.try_move(dir);
+                return true;
+            }
+            Command::Enter => {
+                // If player is on stairs, move from this map
+                if pa.gd().on_map_entrance() {
+                    pa.goto_next_floor(Direction::none(), true);
+                }
+                return true;
+            }
+            Command::OpenExitWin => Box::new(exit_dialog()),
+            Command::OpenHelpWin => Box::new(help_dialog()),
+            Command::OpenItemMenu => Box::new(ItemDialog::new(ItemDialogMode::List, pa.gd())),
+            Command::OpenDebugCommandWin => Box::new(debug_command_dialog()),
+            Command::OpenEquipWin => Box::new(EquipDialog::new(pa.gd(), CharaId::Player)),
+            Command::OpenStatusWin => Box::new(status_dialog(pa.gd())),
+            Command::OpenCreationWin | Command::OpenGameInfoWin => Box::new(unsupported_dialog()),
+            Command::PickUpItem => {
+                if !pa.gd().is_item_on_player_tile() {
+                    return true;
+                }
+                Box::new(ItemDialog::new(ItemDialogMode::PickUp, pa.gd()))
+            }
+            Command::DropItem => Box::new(ItemDialog::new(ItemDialogMode::Drop, pa.gd())),
+            Command::DrinkItem => Box::new(ItemDialog::new(ItemDialogMode::Drink, pa.gd())),
+            Command::EatItem => Box::new(ItemDialog::new(ItemDialogMode::Eat, pa.gd())),
+            Command::ReleaseItem => Box::new(ItemDialog::new(ItemDialogMode::Release, pa.gd())),
+            Command::ThrowItem => Box::new(ItemDialog::new(ItemDialogMode::Throw, pa.gd())),
+            Command::CastSpell => Box::new(SpellDialog::new(pa.gd())),
+            Command::TargetingMode => {
+                self.target_cursor = Some(self.game.gd.player_pos());
+                return true;
+            }
+            _ => {
+                return true;
+            }
+        };
+        self.dialogs.push(dialog);
+        true
+    }
+
+    fn process_command_dialog(&mut self, command: Command) -> bool {
+        let mut tail = self.dialogs.len() - 1;
+        let mut dialog_result = {
+            let mut pa = DoPlayerAction::new(&mut self.game);
+            self.dialogs[tail].process_command(&command, &mut pa)
+        };
+        loop {
+            match dialog_result {
+                DialogResult::Continue => (),
+                DialogResult::Close => {
+                    self.dialogs.pop();
+                    if tail > 0 {
+                        tail -= 1;
+                        let mut pa = DoPlayerAction::new(&mut self.game);
+                        dialog_result = self.dialogs[tail].callback_child_closed(&mut pa);
+                        continue;
+                    }
+                }
+                DialogResult::CloseAll => {
+                    self.dialogs.clear();
+                }
+                DialogResult::Quit => {
+                    return false;
+                }
+                DialogResult::OpenChildDialog(child) => {
+                    self.dialogs.push(child);
+                }
+                DialogResult::Special(result) => {
+                    self.process_special_result(result);
+                }
+            }
+            return true;
+        }
+    }
+
+    fn process_special_result(&mut self, result: SpecialDialogResult) {
+        match result {
+            SpecialDialogResult::NewGameStart(gd) => {
+                info!("Create newgame from dialog result");
+                self.start_game(*gd, true);
+            }
+            SpecialDialogResult::LoadGame(gd) => {
+                info!("Load game from file");
+                self.start_game(*gd, false);
+            }
+            SpecialDialogResult::ReturnToStartScreen => {
+                info!("Return to start screen");
+                if let Some(recorder) = self.recorder.take() {
+                    recorder.finish(&self.game.gd);
+                }
+                crate::log::clear();
+                self.screen.clear_log();
+                self.dialogs.clear();
+                self.dialogs.push(Box::new(start_dialog()));
+                self.on_game = false;
+            }
+        }
+    }
+
+    fn start_game(&mut self, gd: GameData, new_game: bool) {
+        self.dialogs.clear();
+        self.target_cursor = None;
+        self.on_game = true;
+        self.game = Game::new(gd);
+
+        if let Some(path) = CONFIG.record.as_ref() {
+            match Recorder::start(path, &mut self.game, new_game) {
+                Ok(recorder) => self.recorder = Some(recorder),
+                Err(e) => warn!("Failed to start recording: {}", e),
+            }
+        }
+
+        self.game.update_before_player_turn();
+        if new_game {
+            self.game.start_new_game();
+        }
+        game_log_i!("start"; version=env!("CARGO_PKG_VERSION"));
+    }
+}
+
+/// Wait for a key press. Returns None for other events such as resizing.
+fn read_command(mode: InputMode) -> io::Result<Option<Command>> {
+    match event::read()? {
+        Event::Key(key) if key.kind != KeyEventKind::Release => Ok(input::conv_key(key, mode)),
+        _ => Ok(None),
+    }
+}
+
+fn init_obj() {
+    let mut data_dirs = config::get_data_dirs();
+    for d in data_dirs.iter_mut() {
+        info!("Loading objects from \"{}\"", d.to_string_lossy());
+        d.push("paks");
+    }
+    common::gobj::init(data_dirs);
+}
+
+/// Setup logger. Messages go to stderr, so redirect it to see them while playing.
+fn setup_logger() {
+    env_logger::builder().format_timestamp(None).init();
+}
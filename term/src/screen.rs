@@ -0,0 +1,279 @@
+//! Draws the game to the terminal through a buffer of glyphs
+
+use super::dialog::DialogView;
+use super::glyph::*;
+use crate::game::{Game, InfoGetter};
+use crate::text::ToText;
+use common::gamedata::*;
+use crossterm::{cursor, queue, style, terminal};
+use geom::*;
+use std::collections::VecDeque;
+use std::io::{self, Write};
+
+const LOG_ROWS: u16 = 6;
+const TEXT_COLOR: Rgb = (255, 255, 255);
+const STATUS_COLOR: Rgb = (200, 200, 200);
+const DIALOG_BORDER_COLOR: Rgb = (150, 150, 150);
+const DIALOG_CURSOR_COLOR: Rgb = (255, 255, 0);
+
+pub struct Screen {
+    w: u16,
+    h: u16,
+    cells: Vec<Glyph>,
+    /// Terminal cursor position shown in targeting mode
+    cursor: Option<(u16, u16)>,
+    log: LogLines,
+}
+
+impl Screen {
+    pub fn new() -> io::Result<Screen> {
+        let (w, h) = terminal::size()?;
+        Ok(Screen {
+            w,
+            h,
+            cells: vec![Glyph::BLANK; w as usize * h as usize],
+            cursor: None,
+            log: LogLines::default(),
+        })
+    }
+
+    pub fn clear_log(&mut self) {
+        self.log = LogLines::default();
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        let (w, h) = terminal::size()?;
+        self.w = w;
+        self.h = h;
+        self.cells.clear();
+        self.cells.resize(w as usize * h as usize, Glyph::BLANK);
+        self.cursor = None;
+        Ok(())
+    }
+
+    fn put(&mut self, x: i32, y: i32, glyph: Glyph) {
+        if 0 <= x && x < self.w as i32 && 0 <= y && y < self.h as i32 {
+            self.cells[y as usize * self.w as usize + x as usize] = glyph;
+        }
+    }
+
+    fn put_str(&mut self, x: i32, y: i32, s: &str, color: Rgb) {
+        for (i, c) in s.chars().enumerate() {
+            self.put(x + i as i32, y, Glyph::new(c, color));
+        }
+    }
+
+    /// Draw the map, status line, log and dialogs.
+    /// If game is None, only dialogs are drawn.
+    pub fn draw(
+        &mut self,
+        game: Option<&Game>,
+        dialogs: &[DialogView],
+        target_cursor: Option<Vec2d>,
+    ) -> io::Result<()> {
+        self.clear()?;
+        if let Some(game) = game {
+            self.draw_status_line(game);
+            self.draw_map(game, target_cursor);
+            self.draw_log();
+        }
+        for view in dialogs {
+            self.draw_dialog(view);
+        }
+        self.flush()
+    }
+
+    fn map_rows(&self) -> i32 {
+        self.h as i32 - LOG_ROWS as i32 - 1
+    }
+
+    fn draw_map(&mut self, game: &Game, target_cursor: Option<Vec2d>) {
+        let gd = &game.gd;
+        let center = target_cursor.unwrap_or_else(|| gd.player_pos());
+        let top_left = Vec2d(center.0 - self.w as i32 / 2, center.1 - self.map_rows() / 2);
+
+        for y in 0..self.map_rows() {
+            for x in 0..self.w as i32 {
+                let glyph = tile_glyph(gd, &game.view_map, top_left + Vec2d(x, y));
+                self.put(x, y + 1, glyph);
+            }
+        }
+
+        if let Some(p) = target_cursor {
+            let p = p - top_left;
+            self.cursor = Some((p.0 as u16, (p.1 + 1) as u16));
+        }
+    }
+
+    fn draw_status_line(&mut self, game: &Game) {
+        let gd = &game.gd;
+        let player = gd.chara.get(CharaId::Player);
+        let (max_hp, hp) = gd.player_hp();
+        let mid = gd.get_current_mapid();
+        let place = match mid {
+            MapId::SiteMap { sid, floor } => {
+                format!("{} ({})", gd.region.get_site(sid).to_text(), floor + 1)
+            }
+            MapId::RegionMap { rid } => gd.region.get(rid).name.clone(),
+        };
+        let date = gd.time.current_date();
+        let weather = gd.region.get(mid.rid()).weather.kind;
+
+        let mut line = format!(
+            "{}  HP {}/{}  SP {:.0}  {}  {}/{:02}/{:02} {:02}:{:02} {}",
+            player.to_text(),
+            hp,
+            max_hp,
+            player.sp,
+            place,
+            date.year,
+            date.month,
+            date.day,
+            date.hour,
+            date.minute,
+            weather.to_text(),
+        );
+        for status in &player.status {
+            line.push_str("  ");
+            line.push_str(&status.to_text());
+        }
+        self.put_str(0, 0, &line, STATUS_COLOR);
+    }
+
+    fn draw_log(&mut self) {
+        self.log.update();
+        let y0 = self.h as i32 - LOG_ROWS as i32;
+        let skip = self.log.lines.len().saturating_sub(LOG_ROWS as usize);
+        let lines: Vec<String> = self.log.lines.iter().skip(skip).cloned().collect();
+        for (i, line) in lines.iter().enumerate() {
+            self.put_str(0, y0 + i as i32, line, TEXT_COLOR);
+        }
+    }
+
+    fn draw_dialog(&mut self, view: &DialogView) {
+        let max_w = (self.w as usize).saturating_sub(4).max(3);
+        let mut text_lines: Vec<String> = Vec::new();
+        for line in view.text.lines() {
+            wrap_line(line, max_w - 2, &mut text_lines);
+        }
+        let choice_lines: Vec<String> = view
+            .choices
+            .iter()
+            .map(|choice| format!("  {}", choice))
+            .collect();
+
+        let content_w = text_lines
+            .iter()
+            .chain(choice_lines.iter())
+            .map(|s| s.chars().count())
+            .max()
+            .unwrap_or(0)
+            .min(max_w - 2);
+        let max_rows = (self.h as usize).saturating_sub(4).max(1);
+        let n_text = text_lines.len().min(max_rows);
+        let n_choice = choice_lines.len().min(max_rows - n_text);
+        // Scroll choices to keep the cursor visible
+        let first_choice = if n_choice > 0 && view.cursor >= n_choice {
+            view.cursor + 1 - n_choice
+        } else {
+            0
+        };
+
+        let w = content_w as i32 + 4;
+        let h = (n_text + n_choice) as i32 + 2;
+        let x0 = (self.w as i32 - w) / 2;
+        let y0 = (self.h as i32 - h) / 2;
+
+        for y in y0..(y0 + h) {
+            for x in x0..(x0 + w) {
+                let c = match (x == x0 || x == x0 + w - 1, y == y0 || y == y0 + h - 1) {
+                    (true, true) => '+',
+                    (false, true) => '-',
+                    (true, false) => '|',
+                    (false, false) => ' ',
+                };
+                self.put(x, y, Glyph::new(c, DIALOG_BORDER_COLOR));
+            }
+        }
+
+        for (i, line) in text_lines.iter().take(n_text).enumerate() {
+            self.put_str(x0 + 2, y0 + 1 + i as i32, line, TEXT_COLOR);
+        }
+        for i in 0..n_choice {
+            let y = y0 + 1 + (n_text + i) as i32;
+            let choice = &choice_lines[first_choice + i];
+            if first_choice + i == view.cursor {
+                self.put_str(x0 + 2, y, choice, DIALOG_CURSOR_COLOR);
+                self.put(x0 + 2, y, Glyph::new('>', DIALOG_CURSOR_COLOR));
+            } else {
+                self.put_str(x0 + 2, y, choice, TEXT_COLOR);
+            }
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let mut out = io::stdout();
+        queue!(out, cursor::Hide)?;
+        let mut run = String::new();
+        for y in 0..self.h {
+            queue!(out, cursor::MoveTo(0, y))?;
+            let row = &self.cells[y as usize * self.w as usize..(y as usize + 1) * self.w as usize];
+            let mut color = row[0].color;
+            for glyph in row {
+                if glyph.color != color && glyph.c != ' ' {
+                    print_run(&mut out, &mut run, color)?;
+                    color = glyph.color;
+                }
+                run.push(glyph.c);
+            }
+            print_run(&mut out, &mut run, color)?;
+        }
+        if let Some((x, y)) = self.cursor {
+            queue!(out, cursor::MoveTo(x, y), cursor::Show)?;
+        }
+        out.flush()
+    }
+}
+
+fn print_run(out: &mut io::Stdout, run: &mut String, color: Rgb) -> io::Result<()> {
+    let (r, g, b) = color;
+    queue!(
+        out,
+        style::SetForegroundColor(style::Color::Rgb { r, g, b }),
+        style::Print(&run)
+    )?;
+    run.clear();
+    Ok(())
+}
+
+/// Split a line into lines which are shorter than w
+fn wrap_line(line: &str, w: usize, lines: &mut Vec<String>) {
+    let chars: Vec<char> = line.chars().collect();
+    if chars.is_empty() {
+        lines.push(String::new());
+        return;
+    }
+    for chunk in chars.chunks(w.max(1)) {
+        lines.push(chunk.iter().collect());
+    }
+}
+
+/// Recent lines of the game log
+#[derive(Default)]
+struct LogLines {
+    lines: VecDeque<String>,
+    latest_line: usize,
+}
+
+impl LogLines {
+    fn update(&mut self) {
+        let lines = &mut self.lines;
+        crate::log::with_lines(self.latest_line, |s| {
+            lines.push_back(s.concat());
+            if lines.len() > LOG_ROWS as usize {
+                lines.pop_front();
+            }
+        });
+        self.latest_line = crate::log::latest_line();
+    }
+}